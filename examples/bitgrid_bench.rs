@@ -0,0 +1,40 @@
+//! Compares the generic `Grid<bool>` stepper with `BitGrid` on Conway's
+//! Game of Life over a random 1024x1000 grid.
+//!
+//! Run with `cargo run --release --example bitgrid_bench`.
+
+use std::time::Instant;
+
+use aoc25::bitgrid::BitGrid;
+use aoc25::grid::Grid;
+use aoc25::utils::Rng;
+
+const HEIGHT: usize = 1024;
+// Not a multiple of 64, so the partial last word of each row is exercised.
+const WIDTH: usize = 1000;
+const GENERATIONS: usize = 100;
+
+fn main() {
+    let mut rng = Rng::new(2025);
+    let rows = (0..HEIGHT)
+        .map(|_| (0..WIDTH).map(|_| rng.gen_bool(0.5)).collect())
+        .collect();
+    let mut grid = Grid::from_rows(rows);
+    let mut bits = BitGrid::from(&grid);
+
+    let start = Instant::now();
+    grid.step_n(GENERATIONS, |&alive, nbrs| {
+        let n = nbrs.count(|&cell| cell);
+        n == 3 || (alive && n == 2)
+    });
+    let generic_time = start.elapsed();
+
+    let start = Instant::now();
+    bits.step_life(GENERATIONS, &[3], &[2, 3]);
+    let bitgrid_time = start.elapsed();
+
+    assert!(bits.to_grid() == grid);
+    println!("live cells: {}", bits.count_ones());
+    println!("generic: {generic_time:?}");
+    println!("bitgrid: {bitgrid_time:?}");
+}
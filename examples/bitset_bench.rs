@@ -0,0 +1,46 @@
+//! Compares hashed and bitset-indexed BFS on an open 4096x4096 grid.
+//!
+//! Run with `cargo run --release --example bitset_bench`.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use aoc25::graph::{shortest_path, shortest_path_indexed};
+
+const N: usize = 4096;
+
+fn nbrs((r, c): (usize, usize)) -> Vec<(usize, usize)> {
+    let mut out = Vec::with_capacity(4);
+    if r > 0 {
+        out.push((r - 1, c));
+    }
+    if r + 1 < N {
+        out.push((r + 1, c));
+    }
+    if c > 0 {
+        out.push((r, c - 1));
+    }
+    if c + 1 < N {
+        out.push((r, c + 1));
+    }
+    out
+}
+
+fn main() {
+    let tgt = (N - 1, N - 1);
+
+    let start = Instant::now();
+    let hashed = shortest_path((0, 0), &tgt, |v| {
+        nbrs(v).into_iter().collect::<HashSet<_>>()
+    });
+    let hashed_time = start.elapsed();
+
+    let start = Instant::now();
+    let indexed =
+        shortest_path_indexed((0, 0), &tgt, nbrs, |&(r, c)| r * N + c);
+    let indexed_time = start.elapsed();
+
+    assert_eq!(hashed.map(|p| p.len()), indexed.map(|p| p.len()));
+    println!("hashed:  {hashed_time:?}");
+    println!("indexed: {indexed_time:?}");
+}
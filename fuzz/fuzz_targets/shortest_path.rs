@@ -0,0 +1,35 @@
+//! Feeds arbitrary digraphs into `shortest_path` and checks that any path
+//! it returns is a real, simple walk from `src` to `tgt`.
+//!
+//! Run with `cargo fuzz run shortest_path` from the repository root.
+
+#![no_main]
+
+use std::collections::HashSet;
+
+use aoc25::graph::{shortest_path, shortest_path_indexed};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let [n, src, tgt, edges @ ..] = data else {
+        return;
+    };
+    let n = usize::from(*n % 64) + 1;
+    let (src, tgt) = (usize::from(*src) % n, usize::from(*tgt) % n);
+    let mut adj = vec![vec![]; n];
+    for pair in edges.chunks_exact(2) {
+        adj[usize::from(pair[0]) % n].push(usize::from(pair[1]) % n);
+    }
+
+    let path = shortest_path(src, &tgt, |v| adj[v].clone());
+    let indexed = shortest_path_indexed(src, &tgt, |v| adj[v].clone(), |&v| v);
+    assert_eq!(path.as_ref().map(Vec::len), indexed.as_ref().map(Vec::len));
+
+    for path in [path, indexed].into_iter().flatten() {
+        assert_eq!(path.first(), Some(&src));
+        assert_eq!(path.last(), Some(&tgt));
+        assert!(path.windows(2).all(|e| adj[e[0]].contains(&e[1])));
+        let distinct: HashSet<_> = path.iter().collect();
+        assert_eq!(distinct.len(), path.len(), "path repeats a vertex");
+    }
+});
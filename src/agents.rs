@@ -0,0 +1,226 @@
+//! A tick-based simulation of agents moving over a grid, for cart-crash and
+//! blizzard puzzles.
+//!
+//! ```ignore
+//! let mut sim = Simulation::new(carts, Movement::Sequential);
+//! let crash = sim.run_until(&mut drive, &mut |_| Resolve::Remove, |_, c| {
+//!     c.first().map(|c| c.pos)
+//! });
+//! ```
+
+use crate::direction::{Coords, Direction};
+use crate::hashing::{FastMap, FastSet};
+
+/// Something that moves around the grid, with whatever extra `state` its
+/// behavior needs (e.g. a cart's next turn at an intersection).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Agent<S> {
+    pub pos: Coords,
+    pub heading: Direction,
+    pub state: S,
+}
+
+impl<S> Agent<S> {
+    pub const fn new(pos: Coords, heading: Direction, state: S) -> Self {
+        Self {
+            pos,
+            heading,
+            state,
+        }
+    }
+}
+
+/// When agents are checked for collisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Movement {
+    /// Agents move one at a time in reading order, and each is checked as
+    /// soon as it moves. A removed agent takes no further turns.
+    Sequential,
+    /// Every agent moves, then agents sharing a cell are checked. Agents
+    /// that swap cells pass through each other.
+    Simultaneous,
+}
+
+/// What to do with the agents in a [`Collision`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolve {
+    Keep,
+    Remove,
+}
+
+/// Two or more agents on the same cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Collision {
+    /// The tick it happened in, counting from 1.
+    pub tick: usize,
+    pub pos: Coords,
+    /// The ids of the agents involved, in increasing order.
+    pub agents: Vec<usize>,
+}
+
+/// A set of agents and the number of ticks simulated so far.
+///
+/// Agents are identified by their index in the initial list, which stays
+/// the same as others are removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation<S> {
+    agents: Vec<Option<Agent<S>>>,
+    movement: Movement,
+    tick: usize,
+}
+
+impl<S> Simulation<S> {
+    pub fn new(
+        agents: impl IntoIterator<Item = Agent<S>>,
+        movement: Movement,
+    ) -> Self {
+        Self {
+            agents: agents.into_iter().map(Some).collect(),
+            movement,
+            tick: 0,
+        }
+    }
+
+    /// Number of ticks simulated so far.
+    #[must_use]
+    pub const fn tick(&self) -> usize {
+        self.tick
+    }
+
+    #[must_use]
+    pub fn get(&self, id: usize) -> Option<&Agent<S>> {
+        self.agents.get(id)?.as_ref()
+    }
+
+    /// The remaining agents and their ids, in id order.
+    pub fn agents(&self) -> impl Iterator<Item = (usize, &Agent<S>)> {
+        self.agents
+            .iter()
+            .enumerate()
+            .filter_map(|(id, agent)| Some((id, agent.as_ref()?)))
+    }
+
+    /// Number of agents remaining.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.agents.iter().flatten().count()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cells holding at least one agent.
+    #[must_use]
+    pub fn occupied(&self) -> FastSet<Coords> {
+        self.agents().map(|(_, agent)| agent.pos).collect()
+    }
+
+    /// The remaining ids in reading order of their agents' positions, ties
+    /// broken by id.
+    fn reading_order(&self) -> Vec<usize> {
+        let mut order: Vec<_> =
+            self.agents().map(|(id, a)| (a.pos, id)).collect();
+        order.sort_unstable();
+        order.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// The ids of the remaining agents on `pos`.
+    fn agents_at(&self, pos: Coords) -> Vec<usize> {
+        self.agents()
+            .filter(|(_, agent)| agent.pos == pos)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn resolve<H>(&mut self, collision: &Collision, on_collision: &mut H)
+    where
+        H: FnMut(&Collision) -> Resolve,
+    {
+        if on_collision(collision) == Resolve::Remove {
+            for &id in &collision.agents {
+                self.agents[id] = None;
+            }
+        }
+    }
+
+    /// Runs one tick: `behavior` moves each agent in reading order, and
+    /// `on_collision` decides the fate of any agents that end up sharing a
+    /// cell. Returns the tick's collisions in the order they happened.
+    pub fn step<B, H>(
+        &mut self,
+        behavior: &mut B,
+        on_collision: &mut H,
+    ) -> Vec<Collision>
+    where
+        B: FnMut(&mut Agent<S>),
+        H: FnMut(&Collision) -> Resolve,
+    {
+        self.tick += 1;
+        let mut collisions = vec![];
+        for id in self.reading_order() {
+            let Some(agent) = &mut self.agents[id] else {
+                continue;
+            };
+            behavior(agent);
+            if self.movement == Movement::Simultaneous {
+                continue;
+            }
+            let pos = agent.pos;
+            let agents = self.agents_at(pos);
+            if agents.len() > 1 {
+                let collision = Collision {
+                    tick: self.tick,
+                    pos,
+                    agents,
+                };
+                self.resolve(&collision, on_collision);
+                collisions.push(collision);
+            }
+        }
+        if self.movement == Movement::Simultaneous {
+            let mut by_pos: FastMap<Coords, Vec<usize>> = FastMap::default();
+            for (id, agent) in self.agents() {
+                by_pos.entry(agent.pos).or_default().push(id);
+            }
+            let mut shared: Vec<_> = by_pos
+                .into_iter()
+                .filter(|(_, agents)| agents.len() > 1)
+                .collect();
+            shared.sort_unstable();
+            for (pos, agents) in shared {
+                let collision = Collision {
+                    tick: self.tick,
+                    pos,
+                    agents,
+                };
+                self.resolve(&collision, on_collision);
+                collisions.push(collision);
+            }
+        }
+        collisions
+    }
+
+    /// Steps until `done` returns a value, and returns it. `done` is asked
+    /// after every tick with the simulation and that tick's collisions, so
+    /// it can stop on the first crash or on e.g. one agent remaining.
+    pub fn run_until<B, H, D, T>(
+        &mut self,
+        behavior: &mut B,
+        on_collision: &mut H,
+        mut done: D,
+    ) -> T
+    where
+        B: FnMut(&mut Agent<S>),
+        H: FnMut(&Collision) -> Resolve,
+        D: FnMut(&Self, &[Collision]) -> Option<T>,
+    {
+        loop {
+            let collisions = self.step(behavior, on_collision);
+            if let Some(out) = done(self, &collisions) {
+                return out;
+            }
+        }
+    }
+}
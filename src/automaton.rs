@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+
+use crate::direction::{Coords, ICoords};
+use crate::grid::Grid;
+use crate::neighborhood::Neighborhood;
+use crate::simulate::{NotConverged, fixed_point};
+use crate::utils::nth_iterate;
+
+/// The in-bounds Moore neighbors of a cell, handed to automaton rules.
+#[derive(Debug, Clone, Copy)]
+pub struct NeighborCounts<'a, T> {
+    cells: [Option<&'a T>; 8],
+}
+
+impl<'a, T> NeighborCounts<'a, T> {
+    /// Number of neighbors satisfying `pred`.
+    pub fn count<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.iter().filter(|&cell| pred(cell)).count()
+    }
+
+    /// Number of neighbors equal to `value`.
+    pub fn count_of(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.count(|cell| cell == value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.cells.iter().flatten().copied()
+    }
+}
+
+impl<T> Grid<T> {
+    fn neighbor_counts(&self, pos: Coords) -> NeighborCounts<'_, T> {
+        let mut cells = [None; 8];
+        for (slot, &d) in cells.iter_mut().zip(Neighborhood::Moore.offsets()) {
+            *slot = pos.shift(d).and_then(|nbr| self.get(nbr));
+        }
+        NeighborCounts { cells }
+    }
+
+    /// Writes the generation after `self` into `out`, which must have the
+    /// same dimensions.
+    fn next_generation_into<R>(&self, out: &mut Self, rule: &R)
+    where
+        R: Fn(&T, NeighborCounts<'_, T>) -> T,
+    {
+        for r in 0..self.height() {
+            for c in 0..self.width() {
+                let pos = Coords(r, c);
+                out[pos] = rule(&self[pos], self.neighbor_counts(pos));
+            }
+        }
+    }
+
+    /// Advances one synchronous generation: every cell's next value is
+    /// computed from the current generation before any is replaced.
+    pub fn step_automaton<R>(&mut self, rule: R)
+    where
+        T: Clone,
+        R: Fn(&T, NeighborCounts<'_, T>) -> T,
+    {
+        self.step_n(1, rule);
+    }
+
+    /// Advances `n` generations, double-buffered: each generation is
+    /// written into a back buffer which is then swapped in, so only one
+    /// extra grid is allocated however large `n` is.
+    pub fn step_n<R>(&mut self, n: usize, rule: R)
+    where
+        T: Clone,
+        R: Fn(&T, NeighborCounts<'_, T>) -> T,
+    {
+        if n == 0 {
+            return;
+        }
+        let mut back = self.clone();
+        for _ in 0..n {
+            self.next_generation_into(&mut back, &rule);
+            std::mem::swap(self, &mut back);
+        }
+    }
+
+    /// Advances `n` generations, skipping ahead once the grid starts
+    /// repeating, for questions like "what does generation 10^9 look like".
+    pub fn step_n_cycle<R>(&mut self, n: u64, rule: R)
+    where
+        T: Clone + Eq + Hash,
+        R: Fn(&T, NeighborCounts<'_, T>) -> T,
+    {
+        *self = nth_iterate(self.clone(), n, |grid| {
+            let mut next = grid.clone();
+            grid.next_generation_into(&mut next, &rule);
+            next
+        });
+    }
+
+    /// Advances generations until one leaves the grid unchanged, returning
+    /// the settled grid and the number of generations that changed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the latest grid if it had not settled after `max_iters`
+    /// generations.
+    pub fn stabilize<R>(
+        self,
+        rule: R,
+        max_iters: usize,
+    ) -> Result<(Self, usize), NotConverged<Self>>
+    where
+        T: Clone + PartialEq,
+        R: Fn(&T, NeighborCounts<'_, T>) -> T,
+    {
+        fixed_point(
+            self,
+            |grid| {
+                let mut next = grid.clone();
+                grid.next_generation_into(&mut next, &rule);
+                next
+            },
+            max_iters,
+        )
+    }
+}
+
+/// One generation of a two-state automaton on an unbounded plane, where
+/// only live cells are stored. `rule(alive, live_neighbors)` decides
+/// whether a cell lives in the next generation.
+pub fn step_sparse<S, R>(
+    live: &HashSet<ICoords, S>,
+    nbhd: Neighborhood,
+    rule: R,
+) -> HashSet<ICoords>
+where
+    S: BuildHasher,
+    R: Fn(bool, usize) -> bool,
+{
+    let mut counts: HashMap<ICoords, usize> = HashMap::new();
+    for &pos in live {
+        for nbr in nbhd.of_signed(pos) {
+            *counts.entry(nbr).or_default() += 1;
+        }
+    }
+    let mut next: HashSet<ICoords> = counts
+        .iter()
+        .filter(|&(pos, &n)| rule(live.contains(pos), n))
+        .map(|(&pos, _)| pos)
+        .collect();
+    // Live cells with no live neighbors never appear in `counts`.
+    if rule(true, 0) {
+        next.extend(live.iter().filter(|pos| !counts.contains_key(pos)));
+    }
+    next
+}
@@ -0,0 +1,180 @@
+use crate::direction::Coords;
+use crate::grid::Grid;
+
+/// A dense boolean grid packing each row into `u64` words, for large
+/// life-like automata. Neighbor counts are computed 64 cells at a time by
+/// shifting whole rows and OR-ing them into bit-sliced counters.
+///
+/// An alternative backend to [`Grid<bool>`] with
+/// [`Grid::step_automaton`], not a replacement: it only supports
+/// birth/survival rules over the Moore neighborhood.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    height: usize,
+    width: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    #[must_use]
+    pub fn new(height: usize, width: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            height,
+            width,
+            words_per_row,
+            words: vec![0; height * words_per_row],
+        }
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    #[must_use]
+    pub fn get(&self, Coords(r, c): Coords) -> bool {
+        assert!(
+            r < self.height && c < self.width,
+            "bit grid index out of bounds"
+        );
+        self.words[r * self.words_per_row + c / 64] >> (c % 64) & 1 == 1
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    pub fn set(&mut self, Coords(r, c): Coords, value: bool) {
+        assert!(
+            r < self.height && c < self.width,
+            "bit grid index out of bounds"
+        );
+        let word = &mut self.words[r * self.words_per_row + c / 64];
+        if value {
+            *word |= 1 << (c % 64);
+        } else {
+            *word &= !(1 << (c % 64));
+        }
+    }
+
+    /// Number of set cells.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn row(&self, r: usize) -> &[u64] {
+        &self.words[r * self.words_per_row..(r + 1) * self.words_per_row]
+    }
+
+    /// Mask of the in-bounds bits of word `w` in a row.
+    const fn word_mask(&self, w: usize) -> u64 {
+        let used = self.width - w * 64;
+        if used >= 64 {
+            u64::MAX
+        } else {
+            (1 << used) - 1
+        }
+    }
+
+    /// Writes the next generation of the life-like rule into `out`: a dead
+    /// cell with a neighbor count in `birth` comes alive, and a live cell
+    /// with a count in `survive` stays alive.
+    fn next_generation_into(
+        &self,
+        out: &mut Self,
+        birth: &[u8],
+        survive: &[u8],
+    ) {
+        let zeros = vec![0; self.words_per_row];
+        for r in 0..self.height {
+            let above = if r > 0 { self.row(r - 1) } else { &zeros };
+            let here = self.row(r);
+            let below = if r + 1 < self.height {
+                self.row(r + 1)
+            } else {
+                &zeros
+            };
+            for w in 0..self.words_per_row {
+                // Bit-sliced 4-bit counters: bit k of each cell's count is
+                // in `sums[k]`.
+                let mut sums = [0u64; 4];
+                for (i, row) in [above, here, below].into_iter().enumerate() {
+                    let x = row[w];
+                    let prev = if w > 0 { row[w - 1] } else { 0 };
+                    let next = row.get(w + 1).copied().unwrap_or(0);
+                    let west = x << 1 | prev >> 63;
+                    let east = x >> 1 | next << 63;
+                    let planes = if i == 1 {
+                        [west, east, 0]
+                    } else {
+                        [west, x, east]
+                    };
+                    for mut carry in planes {
+                        for sum in &mut sums {
+                            let bit = *sum ^ carry;
+                            carry &= *sum;
+                            *sum = bit;
+                        }
+                    }
+                }
+                let equals = |n: u8| {
+                    sums.iter().enumerate().fold(u64::MAX, |acc, (k, &s)| {
+                        acc & if n >> k & 1 == 1 { s } else { !s }
+                    })
+                };
+                let alive = here[w];
+                let born = birth.iter().fold(0, |acc, &n| acc | equals(n));
+                let kept = survive.iter().fold(0, |acc, &n| acc | equals(n));
+                out.words[r * self.words_per_row + w] =
+                    (!alive & born | alive & kept) & self.word_mask(w);
+            }
+        }
+    }
+
+    /// Advances `n` generations of a life-like birth/survival rule, e.g.
+    /// `step_life(n, &[3], &[2, 3])` for Conway's Game of Life. Cells
+    /// outside the grid count as dead.
+    pub fn step_life(&mut self, n: usize, birth: &[u8], survive: &[u8]) {
+        if n == 0 {
+            return;
+        }
+        let mut back = self.clone();
+        for _ in 0..n {
+            self.next_generation_into(&mut back, birth, survive);
+            std::mem::swap(self, &mut back);
+        }
+    }
+
+    #[must_use]
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_rows(
+            (0..self.height)
+                .map(|r| {
+                    (0..self.width).map(|c| self.get(Coords(r, c))).collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = Self::new(grid.height(), grid.width());
+        for (pos, &cell) in grid.iter() {
+            if cell {
+                bits.set(pos, true);
+            }
+        }
+        bits
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// The day whose parsed input should be cached, set when `--cache` is
+/// passed.
+static CACHE_DAY: Mutex<Option<u8>> = Mutex::new(None);
+
+/// Called by the runner before dispatching to a solver.
+///
+/// # Panics
+///
+/// Panics if the cache lock is poisoned.
+pub fn configure(day: u8, enabled: bool) {
+    *CACHE_DAY.lock().unwrap() = enabled.then_some(day);
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across builds, over the
+/// parsed type's name, the crate version and the input. A file written for
+/// another type, or by another version, then fails the check instead of
+/// possibly decoding into the wrong shape.
+fn fingerprint<T>(input: &str) -> u64 {
+    let parts = [std::any::type_name::<T>(), env!("CARGO_PKG_VERSION"), input];
+    parts
+        .iter()
+        .flat_map(|part| part.bytes().chain([0]))
+        .fold(0xcbf2_9ce4_8422_2325, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+fn load<T: DeserializeOwned>(path: &Path, input: &str) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    let (stamp, body) = bytes.split_first_chunk::<8>()?;
+    if u64::from_le_bytes(*stamp) != fingerprint::<T>(input) {
+        return None;
+    }
+    let config = bincode::config::standard();
+    bincode::serde::decode_from_slice(body, config)
+        .ok()
+        .map(|(parsed, _)| parsed)
+}
+
+fn store<T: Serialize>(path: &Path, input: &str, parsed: &T) -> io::Result<()> {
+    let config = bincode::config::standard();
+    let body = bincode::serde::encode_to_vec(parsed, config)
+        .map_err(io::Error::other)?;
+    let mut bytes = fingerprint::<T>(input).to_le_bytes().to_vec();
+    bytes.extend(body);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("bin.tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(tmp, path)
+}
+
+/// Parses `input` with `parse`, or with `--cache` reuses the result saved
+/// to `./cache/dayN.bin` by an earlier run on the same input.
+///
+/// The cache is keyed on a fingerprint of the input, the type `T` and the
+/// crate version, so editing or switching input files, or changing what
+/// the day parses into, reparses rather than returning stale data. A type
+/// that changes shape but keeps its name is only caught by a version bump;
+/// delete `./cache` after such a change.
+///
+/// # Panics
+///
+/// Panics if the cache lock is poisoned.
+pub fn cached<T, F>(input: &str, parse: F) -> T
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> T,
+{
+    let Some(day) = *CACHE_DAY.lock().unwrap() else {
+        return parse(input);
+    };
+    let path = PathBuf::from(format!("./cache/day{day}.bin"));
+    if let Some(parsed) = load(&path, input) {
+        eprintln!("Using cached parse from {}.", path.display());
+        return parsed;
+    }
+    let parsed = parse(input);
+    if let Err(err) = store(&path, input, &parsed) {
+        eprintln!("Failed to write parse cache: {err}");
+    }
+    parsed
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Solver state that can be saved mid-run and restored with `--resume`.
+pub trait Checkpoint: Serialize + DeserializeOwned {}
+
+impl<T: Serialize + DeserializeOwned> Checkpoint for T {}
+
+#[derive(Debug, Clone, Copy)]
+struct RunInfo {
+    day: u8,
+    part: u8,
+    resume: bool,
+}
+
+static RUN_INFO: Mutex<Option<RunInfo>> = Mutex::new(None);
+
+/// Called by the runner before dispatching to a solver.
+///
+/// # Panics
+///
+/// Panics if the run info lock is poisoned.
+pub fn configure(day: u8, part: u8, resume: bool) {
+    *RUN_INFO.lock().unwrap() = Some(RunInfo { day, part, resume });
+}
+
+/// Periodically saves solver state to `./checkpoints/dayNN-partP.json`.
+///
+/// Typical use in a long brute-force loop:
+///
+/// ```ignore
+/// let mut ckpt = Checkpointer::new(Duration::from_secs(30));
+/// let mut state = ckpt.restore().unwrap_or_else(State::initial);
+/// while !state.done() {
+///     state.advance();
+///     ckpt.maybe_save(&state);
+/// }
+/// ckpt.finish();
+/// ```
+pub struct Checkpointer {
+    path: Option<PathBuf>,
+    resume: bool,
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl Checkpointer {
+    /// A checkpointer for the current run, saving at most once per
+    /// `interval`. Outside the runner (e.g. in tests) it never touches disk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the run info lock is poisoned.
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        let info = *RUN_INFO.lock().unwrap();
+        Self {
+            path: info.map(|info| {
+                PathBuf::from(format!(
+                    "./checkpoints/day{:02}-part{}.json",
+                    info.day, info.part
+                ))
+            }),
+            resume: info.is_some_and(|info| info.resume),
+            interval,
+            last_save: Instant::now(),
+        }
+    }
+
+    /// The saved state, if `--resume` was passed and a checkpoint exists.
+    #[must_use]
+    pub fn restore<T: Checkpoint>(&self) -> Option<T> {
+        if !self.resume {
+            return None;
+        }
+        let text = fs::read_to_string(self.path.as_ref()?).ok()?;
+        match serde_json::from_str(&text) {
+            Ok(state) => {
+                eprintln!("Resumed from checkpoint.");
+                Some(state)
+            }
+            Err(err) => {
+                eprintln!("Ignoring unreadable checkpoint: {err}");
+                None
+            }
+        }
+    }
+
+    /// Saves `state` if at least `interval` has passed since the last save.
+    pub fn maybe_save<T: Checkpoint>(&mut self, state: &T) {
+        if self.last_save.elapsed() >= self.interval {
+            if let Err(err) = self.save(state) {
+                eprintln!("Failed to write checkpoint: {err}");
+            }
+            self.last_save = Instant::now();
+        }
+    }
+
+    /// Saves `state` now. The file is replaced atomically, so an interrupted
+    /// save never corrupts the previous checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from serializing or writing the checkpoint.
+    pub fn save<T: Checkpoint>(&self, state: &T) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(state)?)?;
+        fs::rename(tmp, path)
+    }
+
+    /// Deletes the checkpoint once the solver has finished.
+    pub fn finish(self) {
+        if let Some(path) = self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
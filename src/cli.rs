@@ -0,0 +1,379 @@
+use std::fmt;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: aoc25 <command> [flags]
+
+Commands:
+  run <day> [part]      Solve one day, both parts unless one is given
+  all                   Solve every implemented day
+  new <day>             Create src/days/dayNN.rs from a template
+  bench <day> [part]    Time repeated solves
+  bench-report [day]    Show recorded timings and flag slowdowns
+  fetch <day>           Download the puzzle input
+  submit <day> <part>   Solve and submit the answer
+  gen <day> [size]      Print a random input, for stress tests
+
+Flags:
+  --input PATH          Read the input from PATH instead of the inputs dir
+  --example             Use the example embedded with aoc_test! as input
+  --time                Report how long each part took, and record it for
+                        bench-report
+  --check               Compare answers with ./answers/dayNN-partP.txt, or
+                        the example's expected answer with --example
+  --json                Print one JSON object per answer
+  --verify-against-naive
+                        Also solve with the day's naive solver, and fail
+                        if the answers differ
+  --iterations N        Solves per part for bench (default 10)
+  --threshold PCT       Slowdown over the best time that bench-report
+                        flags (default 10)
+  --resume              Resume from a saved checkpoint
+  --cache               Reuse the parsed input from an earlier run
+  --step                Show each frame a solver draws, waiting for Enter
+  --delay MS            With --step, advance every MS milliseconds instead
+  --record              Save each frame a solver draws as a PPM image under
+                        ./viz/dayNN/partP/
+  --seed N              Seed for gen, to repeat an input (default random,
+                        printed to stderr)
+  -v, -vv               Log solver progress to stderr: -v for debug!
+                        messages, -vv for trace! as well
+
+`aoc25 <day> <part> [path]` is shorthand for `run`.
+
+Defaults for the session cookie, inputs dir, year and flags are read from
+~/.config/aoc2025/config.toml and ./.aoc.toml.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Solve `day`, both parts if `part` is `None`.
+    Run {
+        day: u8,
+        part: Option<u8>,
+    },
+    All,
+    New {
+        day: u8,
+    },
+    Bench {
+        day: u8,
+        part: Option<u8>,
+    },
+    /// Summarize recorded timings, for every day if `day` is `None`.
+    BenchReport {
+        day: Option<u8>,
+    },
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+    },
+    /// Print a random input for `day`, of the day's default size if `size`
+    /// is `None`.
+    Gen {
+        day: u8,
+        size: Option<usize>,
+    },
+}
+
+// Independent on/off switches, one per command-line flag.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flags {
+    pub input: Option<PathBuf>,
+    pub example: bool,
+    pub time: bool,
+    pub check: bool,
+    pub json: bool,
+    /// Check answers against the day's naive solver.
+    pub verify_naive: bool,
+    pub iterations: usize,
+    /// Percentage slowdown at which `bench-report` flags a part.
+    pub threshold: u32,
+    pub resume: bool,
+    pub cache: bool,
+    /// Show solver frames one at a time.
+    pub step: bool,
+    /// Milliseconds between frames with `--step`, rather than waiting for
+    /// Enter.
+    pub delay_ms: Option<u64>,
+    /// Save solver frames as images.
+    pub record: bool,
+    /// How many `-v`s were given: 1 shows `debug!`, 2 also `trace!`.
+    pub verbosity: u8,
+    /// Seed for `gen`; a random one is used if `None`.
+    pub seed: Option<u64>,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self {
+            input: None,
+            example: false,
+            time: false,
+            check: false,
+            json: false,
+            verify_naive: false,
+            iterations: 10,
+            threshold: 10,
+            resume: false,
+            cache: false,
+            step: false,
+            delay_ms: None,
+            record: false,
+            verbosity: 0,
+            seed: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    pub flags: Flags,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    /// A flag such as `--input` was given without its value.
+    MissingValue(&'static str),
+    /// A positional argument the command needs is absent.
+    MissingArgument(&'static str),
+    UnexpectedArgument(String),
+    InvalidDay(String),
+    InvalidPart(String),
+    InvalidIterations(String),
+    InvalidThreshold(String),
+    InvalidDelay(String),
+    InvalidSeed(String),
+    InvalidSize(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingCommand => write!(f, "no command given"),
+            Self::UnknownCommand(c) => write!(f, "unknown command `{c}`"),
+            Self::UnknownFlag(flag) => write!(f, "unknown flag `{flag}`"),
+            Self::MissingValue(flag) => write!(f, "`{flag}` needs a value"),
+            Self::MissingArgument(arg) => write!(f, "missing <{arg}>"),
+            Self::UnexpectedArgument(arg) => {
+                write!(f, "unexpected argument `{arg}`")
+            }
+            Self::InvalidDay(d) => write!(f, "day must be 1 to 25, not `{d}`"),
+            Self::InvalidPart(p) => write!(f, "part must be 1 or 2, not `{p}`"),
+            Self::InvalidIterations(n) => {
+                write!(f, "iterations must be a positive integer, not `{n}`")
+            }
+            Self::InvalidThreshold(t) => {
+                write!(f, "threshold must be a whole percentage, not `{t}`")
+            }
+            Self::InvalidDelay(ms) => {
+                write!(f, "delay must be a whole number of ms, not `{ms}`")
+            }
+            Self::InvalidSeed(seed) => {
+                write!(f, "seed must be a non-negative integer, not `{seed}`")
+            }
+            Self::InvalidSize(size) => {
+                write!(f, "size must be a positive integer, not `{size}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+fn parse_day(s: &str) -> Result<u8, CliError> {
+    match s.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(CliError::InvalidDay(s.to_string())),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, CliError> {
+    match s.parse() {
+        Ok(part @ 1..=2) => Ok(part),
+        _ => Err(CliError::InvalidPart(s.to_string())),
+    }
+}
+
+fn parse_size(s: &str) -> Result<usize, CliError> {
+    match s.parse() {
+        Ok(size) if size > 0 => Ok(size),
+        _ => Err(CliError::InvalidSize(s.to_string())),
+    }
+}
+
+fn required_day<'a>(
+    ops: &mut impl Iterator<Item = &'a str>,
+) -> Result<u8, CliError> {
+    ops.next()
+        .ok_or(CliError::MissingArgument("day"))
+        .and_then(parse_day)
+}
+
+fn required_part<'a>(
+    ops: &mut impl Iterator<Item = &'a str>,
+) -> Result<u8, CliError> {
+    ops.next()
+        .ok_or(CliError::MissingArgument("part"))
+        .and_then(parse_part)
+}
+
+fn optional_part<'a>(
+    ops: &mut impl Iterator<Item = &'a str>,
+) -> Result<Option<u8>, CliError> {
+    ops.next().map(parse_part).transpose()
+}
+
+/// The command named `name`, taking its operands from `ops`. The `<day>
+/// <part> [path]` shorthand sets `--input` from its path.
+fn parse_command<'a>(
+    name: &str,
+    ops: &mut impl Iterator<Item = &'a str>,
+    flags: &mut Flags,
+) -> Result<Command, CliError> {
+    Ok(match name {
+        "run" => Command::Run {
+            day: required_day(ops)?,
+            part: optional_part(ops)?,
+        },
+        "all" => Command::All,
+        "new" => Command::New {
+            day: required_day(ops)?,
+        },
+        "bench" => Command::Bench {
+            day: required_day(ops)?,
+            part: optional_part(ops)?,
+        },
+        "bench-report" => Command::BenchReport {
+            day: ops.next().map(parse_day).transpose()?,
+        },
+        "fetch" => Command::Fetch {
+            day: required_day(ops)?,
+        },
+        "submit" => Command::Submit {
+            day: required_day(ops)?,
+            part: required_part(ops)?,
+        },
+        "gen" => Command::Gen {
+            day: required_day(ops)?,
+            size: ops.next().map(parse_size).transpose()?,
+        },
+        // The original `<day> <part> [path]` form.
+        _ if name.bytes().all(|b| b.is_ascii_digit()) => {
+            let command = Command::Run {
+                day: parse_day(name)?,
+                part: Some(required_part(ops)?),
+            };
+            if let Some(path) = ops.next() {
+                flags.input = Some(PathBuf::from(path));
+            }
+            command
+        }
+        _ => return Err(CliError::UnknownCommand(name.to_string())),
+    })
+}
+
+/// Parses the command line, `args[0]` being the program name. Flags may
+/// appear anywhere after it.
+///
+/// # Errors
+///
+/// Returns an error describing the first problem with the arguments.
+pub fn parse_args(args: &[String]) -> Result<Cli, CliError> {
+    parse_args_with(args, Flags::default())
+}
+
+/// Like [`parse_args`], but starting from `defaults` (e.g. from the config
+/// file) rather than the built-in flag values.
+///
+/// # Errors
+///
+/// Returns an error describing the first problem with the arguments.
+pub fn parse_args_with(
+    args: &[String],
+    defaults: Flags,
+) -> Result<Cli, CliError> {
+    let mut flags = defaults;
+    let mut positional = vec![];
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path =
+                    rest.next().ok_or(CliError::MissingValue("--input"))?;
+                flags.input = Some(PathBuf::from(path));
+            }
+            "--iterations" => {
+                let n = rest
+                    .next()
+                    .ok_or(CliError::MissingValue("--iterations"))?;
+                flags.iterations = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(CliError::InvalidIterations(n.clone())),
+                };
+            }
+            "--example" => flags.example = true,
+            "--threshold" => {
+                let t =
+                    rest.next().ok_or(CliError::MissingValue("--threshold"))?;
+                flags.threshold = t
+                    .parse()
+                    .map_err(|_| CliError::InvalidThreshold(t.clone()))?;
+            }
+            "--delay" => {
+                let ms =
+                    rest.next().ok_or(CliError::MissingValue("--delay"))?;
+                flags.delay_ms = Some(
+                    ms.parse()
+                        .map_err(|_| CliError::InvalidDelay(ms.clone()))?,
+                );
+                flags.step = true;
+            }
+            "--seed" => {
+                let seed =
+                    rest.next().ok_or(CliError::MissingValue("--seed"))?;
+                flags.seed = Some(
+                    seed.parse()
+                        .map_err(|_| CliError::InvalidSeed(seed.clone()))?,
+                );
+            }
+            "--step" => flags.step = true,
+            "--record" => flags.record = true,
+            "--time" => flags.time = true,
+            "--check" => flags.check = true,
+            "--json" => flags.json = true,
+            "--verify-against-naive" => flags.verify_naive = true,
+            "--resume" => flags.resume = true,
+            "--cache" => flags.cache = true,
+            "--verbose" => flags.verbosity = flags.verbosity.saturating_add(1),
+            flag if flag.len() > 1
+                && flag.starts_with('-')
+                && flag[1..].bytes().all(|b| b == b'v') =>
+            {
+                let count = u8::try_from(flag.len() - 1).unwrap_or(u8::MAX);
+                flags.verbosity = flags.verbosity.saturating_add(count);
+            }
+            flag if flag.starts_with("--") => {
+                return Err(CliError::UnknownFlag(flag.to_string()));
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let (&name, operands) =
+        positional.split_first().ok_or(CliError::MissingCommand)?;
+    let mut operands = operands.iter().copied();
+    let ops = &mut operands;
+    let command = parse_command(name, ops, &mut flags)?;
+    if let Some(extra) = operands.next() {
+        return Err(CliError::UnexpectedArgument(extra.to_string()));
+    }
+    Ok(Cli { command, flags })
+}
@@ -1,71 +1,692 @@
+use super::cache;
+use super::checkpoint;
+use super::cli::{self, Cli, Command, Flags};
+use super::config::Config;
 use super::days;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::process;
-
-pub type LinesIterator = std::io::Lines<std::io::BufReader<std::fs::File>>;
-
-pub fn run_w_args(args: &[String]) -> String {
-    if args.len() < 3 || args.len() > 4 {
-        eprintln!(
-            "Usage: {} <day number> <part 1 or 2> [path (optional)]",
-            args[0]
-        );
-        process::exit(1);
-    }
-
-    let day_number: u8 = match args[1].parse() {
-        Ok(n) if n > 0 => n,
-        _ => {
-            eprintln!(
-                "Please provide a valid positive integer for the day number"
-            );
-            process::exit(1);
+use super::profiling;
+use super::solver::{Answer, Solver};
+use super::stepper::{self, StepMode};
+use super::timings::{self, Source, Timing};
+use super::viz;
+use serde::Serialize;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, RandomState};
+use std::io::{self, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode, Stdio};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+/// The number of `-v` flags, read by [`debug!`](crate::debug) and
+/// [`trace!`](crate::trace).
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// The current log level: 0 is silent, 1 shows `debug!` and 2 also
+/// `trace!`.
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Sets the log level; the runner does this from `-v` before each solve.
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+/// Prints a progress message to stderr when run with `-v` or more. The
+/// arguments are only evaluated when it prints.
+///
+/// ```ignore
+/// debug!("{} beams after row {r}", beams.len());
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::common::verbosity() >= 1 {
+            eprintln!("[debug] {}", format_args!($($arg)*));
         }
     };
+}
 
-    let part: u8 = match args[2].parse() {
-        Ok(n) if (n == 1) || (n == 2) => n,
-        _ => {
-            eprintln!("Please provide either 1 or 2 to indicate which part");
-            process::exit(1);
+/// Like [`debug!`](crate::debug), but only with `-vv`, for messages
+/// too frequent to want normally, such as one per loop iteration.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::common::verbosity() >= 2 {
+            eprintln!("[trace] {}", format_args!($($arg)*));
         }
     };
+}
 
-    let path: String = if args.len() == 4 {
-        args[3].clone()
-    } else {
-        format!("./inputs/input{day_number}.txt")
-    };
+/// Days with a solver, in order, from [`days::SOLVERS`].
+pub const DAYS: &[u8] = &{
+    let mut days = [0; days::SOLVERS.len()];
+    let mut i = 0;
+    while i < days.len() {
+        days[i] = days::SOLVERS[i].day;
+        i += 1;
+    }
+    days
+};
+
+/// The registered solver for `day`.
+#[must_use]
+pub fn solver(day: u8) -> Option<&'static Solver> {
+    days::SOLVERS.iter().find(|solver| solver.day == day)
+}
+
+/// The error for a day with no solver, listing those that have one.
+fn not_implemented(day: u8) -> String {
+    let days: Vec<String> = DAYS.iter().map(ToString::to_string).collect();
+    format!(
+        "Day {day} is not implemented. Available days: {}.",
+        days.join(", ")
+    )
+}
 
-    println!("Running part {part} of day {day_number} using input {path}.");
+/// Solves one part of `day`, or returns `None` if the day has no solver.
+#[must_use]
+pub fn solve_day(day: u8, part: u8, input: &str) -> Option<Answer> {
+    solver(day).map(|solver| (solver.solve)(input, part))
+}
+
+/// Solves one part of `day` with its naive solver, or returns `None` if the
+/// day has none.
+#[must_use]
+pub fn solve_naive(day: u8, part: u8, input: &str) -> Option<Answer> {
+    let naive = solver(day)?.naive?;
+    Some(naive(input, part))
+}
+
+/// A random input for `day` from `seed`, at the day's default size unless
+/// `size` is given, or `None` if the day has no generator.
+#[must_use]
+pub fn generate_input(
+    day: u8,
+    size: Option<usize>,
+    seed: u64,
+) -> Option<String> {
+    let generate = solver(day)?.generate?;
+    Some(generate(size, seed))
+}
+
+/// Why [`try_solve`] could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    InvalidDay(u8),
+    InvalidPart(u8),
+    NotImplemented(u8),
+    /// The solver panicked, e.g. on malformed input, with this message.
+    Panicked(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidDay(day) => {
+                write!(f, "day must be 1 to 25, not {day}")
+            }
+            Self::InvalidPart(part) => {
+                write!(f, "part must be 1 or 2, not {part}")
+            }
+            Self::NotImplemented(day) => f.write_str(&not_implemented(*day)),
+            Self::Panicked(msg) => write!(f, "The solver panicked: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// [`solve_day`] for embedding: validates its arguments, catches solver
+/// panics, and returns the answer as it would be printed. Only the solver
+/// runs, so no files are read or written.
+///
+/// # Errors
+///
+/// Returns why there is no answer. Panics are only caught where unwinding
+/// is supported; on `wasm32-unknown-unknown` they abort instead.
+pub fn try_solve(day: u8, part: u8, input: &str) -> Result<String, SolveError> {
+    if !(1..=25).contains(&day) {
+        return Err(SolveError::InvalidDay(day));
+    }
+    if !(1..=2).contains(&part) {
+        return Err(SolveError::InvalidPart(part));
+    }
+    let answer =
+        panic::catch_unwind(AssertUnwindSafe(|| solve_day(day, part, input)))
+            .map_err(|payload| {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            SolveError::Panicked(msg)
+        })?;
+    answer
+        .map(|answer| answer.to_string())
+        .ok_or(SolveError::NotImplemented(day))
+}
+
+/// The example embedded for one part by [`aoc_test!`](crate::aoc_test),
+/// with its expected answer.
+#[must_use]
+pub fn example(day: u8, part: u8) -> Option<(&'static str, &'static str)> {
+    let index = usize::from(part).checked_sub(1)?;
+    *solver(day)?.examples.get(index)?
+}
+
+/// Runs the requested day and part, returning the answer.
+///
+/// Accepts the same arguments as the binary, but only a `run` of a single
+/// part.
+///
+/// # Panics
+///
+/// Panics with the error message if the arguments, config or input are
+/// invalid, or if the requested day has not been implemented, in which
+/// case the message lists the days that have. Being a
+/// library entry point, it never exits the process.
+#[must_use]
+pub fn run_w_args(args: &[String]) -> Answer {
+    let config = Config::load().unwrap_or_else(|err| panic!("{err}"));
+    let cli = cli::parse_args_with(args, config.flags())
+        .unwrap_or_else(|err| panic!("{err}\n\n{}", cli::USAGE));
+    let Command::Run {
+        day,
+        part: Some(part),
+    } = cli.command
+    else {
+        panic!("run_w_args needs `run <day> <part>`\n\n{}", cli::USAGE);
+    };
+    println!(
+        "Running part {part} of day {day} using {}.",
+        input_name(day, part, &cli.flags, &config)
+    );
     println!();
+    let input = load_input(day, part, &cli.flags, &config)
+        .unwrap_or_else(|err| panic!("{err}"));
+    configure(day, part, &cli.flags).unwrap_or_else(|err| panic!("{err}"));
+    solve_day(day, part, &input)
+        .unwrap_or_else(|| panic!("{}", not_implemented(day)))
+}
+
+/// Entry point for the binary: parses `args` and carries out the command.
+#[must_use]
+pub fn run_cli(args: &[String]) -> ExitCode {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let cli = match cli::parse_args_with(args, config.flags()) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match execute(&cli, &config) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Carries out a command, returning whether every check passed.
+fn execute(cli: &Cli, config: &Config) -> Result<bool, String> {
+    let flags = &cli.flags;
+    match cli.command {
+        Command::Run { day, part } => {
+            let mut ok = true;
+            for part in parts(part) {
+                let input = load_input(day, part, flags, config)?;
+                if !flags.json {
+                    println!(
+                        "Running part {part} of day {day} using {}.",
+                        input_name(day, part, flags, config)
+                    );
+                    println!();
+                }
+                ok &= run_part(day, part, &input, flags)?;
+            }
+            Ok(ok)
+        }
+        Command::All => {
+            let mut ok = true;
+            for &day in DAYS {
+                for part in [1, 2] {
+                    let input = load_input(day, part, flags, config)?;
+                    if !flags.json {
+                        println!("Day {day} part {part}:");
+                    }
+                    ok &= run_part(day, part, &input, flags)?;
+                }
+            }
+            Ok(ok)
+        }
+        Command::New { day } => new_day(day).map(|()| true),
+        Command::Bench { day, part } => {
+            for part in parts(part) {
+                let input = load_input(day, part, flags, config)?;
+                bench_part(day, part, &input, flags)?;
+            }
+            Ok(true)
+        }
+        Command::BenchReport { day } => {
+            let recorded = timings::load()
+                .map_err(|err| format!("{}: {err}", timings::TIMINGS_PATH))?;
+            let (report, ok) = timings::report(&recorded, day, flags.threshold);
+            print!("{report}");
+            Ok(ok)
+        }
+        Command::Fetch { day } => fetch_input(day, config).map(|()| true),
+        Command::Submit { day, part } => {
+            if flags.example {
+                return Err("Refusing to submit an example's answer.".into());
+            }
+            let input = load_input(day, part, flags, config)?;
+            configure(day, part, flags)?;
+            let answer = solve_day(day, part, &input)
+                .ok_or_else(|| not_implemented(day))?;
+            submit_answer(day, part, &answer, config)
+        }
+        Command::Gen { day, size } => {
+            let seed = flags.seed.unwrap_or_else(|| {
+                let seed = RandomState::new().hash_one(());
+                eprintln!("Seed: {seed}");
+                seed
+            });
+            let input = generate_input(day, size, seed)
+                .ok_or_else(|| format!("Day {day} has no input generator."))?;
+            print!("{input}");
+            Ok(true)
+        }
+    }
+}
+
+/// The requested part, or both.
+fn parts(part: Option<u8>) -> Vec<u8> {
+    part.map_or_else(|| vec![1, 2], |part| vec![part])
+}
+
+fn configure(day: u8, part: u8, flags: &Flags) -> Result<(), String> {
+    checkpoint::configure(day, part, flags.resume);
+    cache::configure(day, flags.cache);
+    set_verbosity(flags.verbosity);
+    let mode = match (flags.step, flags.delay_ms) {
+        (false, _) => StepMode::Off,
+        (true, None) => StepMode::Enter,
+        (true, Some(ms)) => StepMode::Delay(Duration::from_millis(ms)),
+    };
+    let record = flags
+        .record
+        .then(|| PathBuf::from(format!("./viz/day{day:02}/part{part}")));
+    stepper::configure(mode, record).map_err(|err| format!("./viz: {err}"))
+}
+
+fn input_path(day: u8, flags: &Flags, config: &Config) -> PathBuf {
+    flags
+        .input
+        .clone()
+        .unwrap_or_else(|| config.inputs().join(format!("input{day}.txt")))
+}
+
+fn answer_path(day: u8, part: u8) -> PathBuf {
+    PathBuf::from(format!("./answers/day{day:02}-part{part}.txt"))
+}
 
-    let mut lines: LinesIterator = read_lines(path).unwrap_or_else(|err| {
-        eprintln!("{err}");
-        process::exit(1);
-    });
+/// Where the input for a part comes from, for messages.
+fn input_name(day: u8, part: u8, flags: &Flags, config: &Config) -> String {
+    if flags.example {
+        format!("the part {part} example")
+    } else {
+        format!("input {}", input_path(day, flags, config).display())
+    }
+}
 
-    match part {
-        1 => match day_number {
-            7 => days::day07::run1(&mut lines),
-            _ => panic!("Incomplete day."),
-        },
-        2 => match day_number {
-            7 => days::day07::run2(&mut lines),
-            _ => panic!("Incomplete day."),
-        },
-        _ => {
-            panic!("YOU SHOULD NEVER SEE THIS!!!!")
+fn load_input(
+    day: u8,
+    part: u8,
+    flags: &Flags,
+    config: &Config,
+) -> Result<String, String> {
+    if flags.example {
+        return example(day, part)
+            .map(|(example, _)| example.to_string())
+            .ok_or_else(|| format!("Day {day} part {part} has no example."));
+    }
+    let path = input_path(day, flags, config);
+    read_input(&path).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// One solved part, as printed by `--json`.
+#[derive(Debug, Serialize)]
+struct Report {
+    day: u8,
+    part: u8,
+    answer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correct: Option<bool>,
+    /// Whether the naive solver agreed, with `--verify-against-naive`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    naive_agrees: Option<bool>,
+    /// Heap usage, with the `profiling` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<usize>,
+}
+
+/// Solves and reports one part, returning whether its check passed (or
+/// `true` without `--check`) and the naive solver agreed (or `true`
+/// without `--verify-against-naive`).
+fn run_part(
+    day: u8,
+    part: u8,
+    input: &str,
+    flags: &Flags,
+) -> Result<bool, String> {
+    configure(day, part, flags)?;
+    let start = Instant::now();
+    let (answer, memory) = profiling::measure(|| solve_day(day, part, input));
+    let answer = answer.ok_or_else(|| not_implemented(day))?;
+    let elapsed = start.elapsed();
+    if flags.time {
+        save_timing(day, part, elapsed, Source::Run, flags);
+    }
+    let expected = if flags.check && flags.example {
+        example(day, part).map(|(_, expected)| expected.to_string())
+    } else if flags.check {
+        let path = answer_path(day, part);
+        let text = fs::read_to_string(&path).map_err(|err| {
+            format!("No expected answer at {}: {err}", path.display())
+        })?;
+        Some(text.trim_end().to_string())
+    } else {
+        None
+    };
+    let correct = expected.as_ref().map(|e| answer == e.as_str());
+    let naive = if flags.verify_naive {
+        let naive = solve_naive(day, part, input)
+            .ok_or_else(|| format!("Day {day} has no naive solver."))?;
+        Some(naive)
+    } else {
+        None
+    };
+    let naive_agrees = naive.as_ref().map(|naive| *naive == answer);
+    let report = Report {
+        day,
+        part,
+        answer: answer.to_string(),
+        elapsed_ms: flags.time.then_some(elapsed.as_secs_f64() * 1000.0),
+        correct,
+        naive_agrees,
+        peak_bytes: memory.map(|m| m.peak_bytes),
+        allocations: memory.map(|m| m.allocations),
+    };
+    if flags.json {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        println!("{answer}");
+        if flags.time {
+            println!("Time: {elapsed:?}");
+        }
+        if let Some(memory) = memory {
+            println!("Memory: {memory}");
+        }
+        match (correct, expected) {
+            (Some(true), _) => println!("Check: OK"),
+            (Some(false), Some(expected)) => {
+                println!("Check: FAIL (- expected, + actual)");
+                let actual = answer.to_string();
+                print!("{}", viz::render_diff(&expected, &actual, use_color()));
+            }
+            _ => {}
         }
+        match naive {
+            Some(naive) if naive == answer => println!("Naive: OK"),
+            Some(naive) => println!("Naive: FAIL (naive answer {naive})"),
+            None => {}
+        }
+    }
+    Ok(correct != Some(false) && naive_agrees != Some(false))
+}
+
+/// Whether to color output: only on a terminal, and not if `NO_COLOR` is
+/// set.
+fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// Appends a timing to the history read by `bench-report`, unless it was
+/// taken on an example. Failing to save it is only worth a warning.
+fn save_timing(
+    day: u8,
+    part: u8,
+    elapsed: Duration,
+    source: Source,
+    flags: &Flags,
+) {
+    if flags.example {
+        return;
+    }
+    let timing = Timing::now(day, part, elapsed, source);
+    if let Err(err) = timings::record(&timing) {
+        eprintln!("Could not save timing to {}: {err}", timings::TIMINGS_PATH);
+    }
+}
+
+fn bench_part(
+    day: u8,
+    part: u8,
+    input: &str,
+    flags: &Flags,
+) -> Result<(), String> {
+    let iterations = flags.iterations;
+    let mut times: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            solve_day(day, part, input).ok_or_else(|| not_implemented(day))?;
+            Ok(start.elapsed())
+        })
+        .collect::<Result<_, String>>()?;
+    times.sort_unstable();
+    let total: Duration = times.iter().sum();
+    let runs = u32::try_from(iterations).unwrap_or(u32::MAX);
+    println!(
+        "Day {day} part {part}: min {:?}, median {:?}, mean {:?} over \
+         {iterations} runs",
+        times[0],
+        times[times.len() / 2],
+        total / runs,
+    );
+    save_timing(day, part, times[times.len() / 2], Source::Bench, flags);
+    Ok(())
+}
+
+const DAY_TEMPLATE: &str = "\
+use crate::solver::{Answer, Day};
+
+pub struct DayNN;
+
+impl Day for DayNN {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Self::Parsed {
+        input.lines().map(str::to_string).collect()
+    }
+
+    fn part1(_parsed: &Self::Parsed) -> Answer {
+        todo!()
+    }
+
+    fn part2(_parsed: &Self::Parsed) -> Answer {
+        todo!()
+    }
+}
+";
+
+/// `src/days.rs` with `day`'s module declared and a bare entry added to
+/// `SOLVERS`, each in order of day.
+fn register_day(days_rs: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{day:02}");
+    let mut lines: Vec<String> = days_rs.lines().map(str::to_string).collect();
+    let decl = format!("pub mod {name};");
+    let is_mod = |l: &String| l.starts_with("pub mod day");
+    let at = lines
+        .iter()
+        .position(|l| is_mod(l) && *l > decl)
+        .or_else(|| lines.iter().rposition(is_mod).map(|i| i + 1))
+        .unwrap_or(0);
+    lines.insert(at, decl);
+
+    let entry = format!("    Solver::new::<{name}::Day{day:02}>({day}),");
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub const SOLVERS"))
+        .ok_or("src/days.rs has no SOLVERS list.")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .ok_or("SOLVERS in src/days.rs is not closed by `];`.")?;
+    let at = (start..end)
+        .find(|&i| {
+            lines[i]
+                .strip_prefix("    Solver::new::<")
+                .is_some_and(|rest| rest > name.as_str())
+        })
+        .unwrap_or(end);
+    lines.insert(at, entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Writes `src/days/dayNN.rs` from a template and registers it in
+/// [`days::SOLVERS`], which makes it runnable.
+fn new_day(day: u8) -> Result<(), String> {
+    let name = format!("day{day:02}");
+    let path = PathBuf::from(format!("./src/days/{name}.rs"));
+    if path.exists() {
+        return Err(format!("{} already exists.", path.display()));
+    }
+    let days_rs = Path::new("./src/days.rs");
+    let registry =
+        fs::read_to_string(days_rs).map_err(|err| err.to_string())?;
+    let registry = register_day(&registry, day)?;
+    let source = DAY_TEMPLATE.replace("DayNN", &format!("Day{day:02}"));
+    fs::write(&path, source).map_err(|err| err.to_string())?;
+    fs::write(days_rs, registry).map_err(|err| err.to_string())?;
+    println!(
+        "Created {} and registered it in src/days.rs.",
+        path.display()
+    );
+    println!(
+        "Examples added with aoc_test! can be registered there with \
+         with_example() for --example."
+    );
+    Ok(())
+}
+
+/// Runs `curl` against adventofcode.com with the session cookie, returning
+/// the response body.
+///
+/// The cookie goes to curl as a config file on stdin rather than as an
+/// argument, where other local users could read it from the process list.
+fn curl(url: &str, extra: &[&str], config: &Config) -> Result<String, String> {
+    let session = config.session().ok_or_else(|| {
+        "Set AOC_SESSION or `session` in the config to your session cookie."
+            .to_string()
+    })?;
+    let quoted = session.replace('\\', "\\\\").replace('"', "\\\"");
+    let curl_config = format!("cookie = \"session={quoted}\"\n");
+    let mut child = process::Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .args(extra)
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run curl: {err}"))?;
+    // Dropping stdin once written closes it, so curl sees the end of the
+    // config.
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(curl_config.as_bytes())
+            .map_err(|err| format!("Failed to pass curl its config: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to run curl: {err}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn fetch_input(day: u8, config: &Config) -> Result<(), String> {
+    let year = config.year();
+    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    let body = curl(&url, &[], config)?;
+    let dir = config.inputs();
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let path = dir.join(format!("input{day}.txt"));
+    fs::write(&path, body).map_err(|err| err.to_string())?;
+    println!("Saved {}.", path.display());
+    Ok(())
+}
+
+/// Submits `answer`, returning whether it was accepted. Accepted answers
+/// are saved for `--check`.
+fn submit_answer(
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    config: &Config,
+) -> Result<bool, String> {
+    let year = config.year();
+    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+    let level = format!("level={part}");
+    let answer_field = format!("answer={answer}");
+    let data = ["--data", &level, "--data-urlencode", &answer_field];
+    let body = curl(&url, &data, config)?;
+    println!("Submitted {answer} for day {day} part {part}.");
+    if body.contains("That's the right answer") {
+        println!("Correct!");
+        let path = answer_path(day, part);
+        fs::create_dir_all("./answers").map_err(|err| err.to_string())?;
+        fs::write(&path, format!("{answer}\n"))
+            .map_err(|err| err.to_string())?;
+        Ok(true)
+    } else if body.contains("That's not the right answer") {
+        println!("Wrong answer.");
+        Ok(false)
+    } else if body.contains("You gave an answer too recently") {
+        println!("Rate limited; wait before submitting again.");
+        Ok(false)
+    } else if body.contains("You don't seem to be solving the right level") {
+        println!("This part is already solved or not yet unlocked.");
+        Ok(false)
+    } else {
+        Err(format!("Unrecognized response:\n{body}"))
     }
 }
 
-fn read_lines<P>(filename: P) -> io::Result<LinesIterator>
+/// Reads the whole puzzle input at once, so solvers can borrow `&str`
+/// lines from it instead of allocating a `String` per line.
+fn read_input<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    fs::read_to_string(filename)
 }
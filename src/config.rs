@@ -0,0 +1,191 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::cli::Flags;
+
+/// The puzzle year when no config sets one.
+pub const DEFAULT_YEAR: u16 = 2025;
+
+/// Runner settings from `~/.config/aoc2025/config.toml` and a repo-local
+/// `.aoc.toml`, the latter taking precedence key by key:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// inputs = "./inputs"
+/// year = 2025
+///
+/// [defaults]
+/// time = true
+/// iterations = 20
+/// threshold = 5
+/// ```
+///
+/// Every key is optional. Flags given on the command line are applied on
+/// top of `[defaults]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    pub inputs: Option<PathBuf>,
+    pub year: Option<u16>,
+    #[serde(default)]
+    pub defaults: FlagDefaults,
+}
+
+/// The `[defaults]` table: values for command-line flags.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagDefaults {
+    pub time: Option<bool>,
+    pub check: Option<bool>,
+    pub json: Option<bool>,
+    pub iterations: Option<usize>,
+    pub threshold: Option<u32>,
+    pub resume: Option<bool>,
+    pub cache: Option<bool>,
+    pub verbosity: Option<u8>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+    /// The file parsed, but a value is out of range.
+    Invalid(PathBuf, &'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Parse(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Invalid(path, msg) => write!(f, "{}: {msg}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Loads and merges the user and repo-local config files. Missing files
+    /// are treated as empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file exists but cannot be read or is
+    /// not valid.
+    pub fn load() -> Result<Self, ConfigError> {
+        let user = match user_config_path() {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        let repo = Self::from_file(Path::new("./.aoc.toml"))?;
+        Ok(user.merge(repo))
+    }
+
+    /// Reads one config file, or the empty config if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(err) => return Err(ConfigError::Io(path.to_path_buf(), err)),
+        };
+        let config: Self = toml::from_str(&text)
+            .map_err(|err| ConfigError::Parse(path.to_path_buf(), err))?;
+        if config.defaults.iterations == Some(0) {
+            return Err(ConfigError::Invalid(
+                path.to_path_buf(),
+                "`iterations` must be positive",
+            ));
+        }
+        Ok(config)
+    }
+
+    /// `self` with every key set in `over` replaced.
+    #[must_use]
+    pub fn merge(self, over: Self) -> Self {
+        let (d, o) = (self.defaults, over.defaults);
+        Self {
+            session: over.session.or(self.session),
+            inputs: over.inputs.or(self.inputs),
+            year: over.year.or(self.year),
+            defaults: FlagDefaults {
+                time: o.time.or(d.time),
+                check: o.check.or(d.check),
+                json: o.json.or(d.json),
+                iterations: o.iterations.or(d.iterations),
+                threshold: o.threshold.or(d.threshold),
+                resume: o.resume.or(d.resume),
+                cache: o.cache.or(d.cache),
+                verbosity: o.verbosity.or(d.verbosity),
+            },
+        }
+    }
+
+    /// The flags to use before the command line is parsed.
+    #[must_use]
+    pub fn flags(&self) -> Flags {
+        let d = &self.defaults;
+        let base = Flags::default();
+        Flags {
+            input: None,
+            example: false,
+            step: false,
+            delay_ms: None,
+            record: false,
+            seed: None,
+            verify_naive: false,
+            time: d.time.unwrap_or(base.time),
+            check: d.check.unwrap_or(base.check),
+            json: d.json.unwrap_or(base.json),
+            iterations: d.iterations.unwrap_or(base.iterations),
+            threshold: d.threshold.unwrap_or(base.threshold),
+            resume: d.resume.unwrap_or(base.resume),
+            cache: d.cache.unwrap_or(base.cache),
+            verbosity: d.verbosity.unwrap_or(base.verbosity),
+        }
+    }
+
+    #[must_use]
+    pub fn year(&self) -> u16 {
+        self.year.unwrap_or(DEFAULT_YEAR)
+    }
+
+    /// The directory holding `inputN.txt` files.
+    #[must_use]
+    pub fn inputs(&self) -> PathBuf {
+        self.inputs
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("./inputs"))
+    }
+
+    /// The session cookie, with `AOC_SESSION` overriding the config files.
+    #[must_use]
+    pub fn session(&self) -> Option<String> {
+        env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| self.session.clone())
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc2025/config.toml`, falling back to `~/.config`.
+fn user_config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME").map(|h| Path::new(&h).join(".config"))
+        })?;
+    Some(base.join("aoc2025").join("config.toml"))
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+use std::ops::{AddAssign, Index, SubAssign};
+
+/// A multiset: counts how many times each value occurs.
+///
+/// Values whose count drops to zero are removed, so `len` is the number of
+/// distinct values present. Counters combine with `+=` and (saturating) `-=`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, t: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(t).or_insert(0) += n;
+        }
+    }
+
+    /// Removes up to `n` occurrences of `t`, returning how many were removed.
+    pub fn remove(&mut self, t: &T, n: usize) -> usize {
+        let Some(count) = self.counts.get_mut(t) else {
+            return 0;
+        };
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(t);
+        }
+        removed
+    }
+
+    #[must_use]
+    pub fn get(&self, t: &T) -> usize {
+        self.counts.get(t).copied().unwrap_or(0)
+    }
+
+    /// Sum of all counts.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Number of distinct values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> hash_map::Iter<'_, T, usize> {
+        self.counts.iter()
+    }
+
+    /// The `k` most common values with their counts, most common first. Ties
+    /// are broken by the ordering of the values.
+    #[must_use]
+    pub fn most_common(&self, k: usize) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut all: Vec<(&T, usize)> =
+            self.counts.iter().map(|(t, &n)| (t, n)).collect();
+        all.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        all.truncate(k);
+        all
+    }
+
+    /// Applies `f` to every value, merging the counts of values that map to
+    /// the same result. This is the usual "evolve every stone" step.
+    #[must_use]
+    pub fn map<U, F>(&self, mut f: F) -> Counter<U>
+    where
+        U: Eq + Hash,
+        F: FnMut(&T) -> U,
+    {
+        let mut out = Counter::new();
+        for (t, &n) in &self.counts {
+            out.add(f(t), n);
+        }
+        out
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<(T, usize)> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = (T, usize)>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.add(t, 1);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Extend<(T, usize)> for Counter<T> {
+    fn extend<I: IntoIterator<Item = (T, usize)>>(&mut self, iter: I) {
+        for (t, n) in iter {
+            self.add(t, n);
+        }
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<'a, T: Eq + Hash> IntoIterator for &'a Counter<T> {
+    type Item = (&'a T, &'a usize);
+    type IntoIter = hash_map::Iter<'a, T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter()
+    }
+}
+
+impl<T: Eq + Hash> Index<&T> for Counter<T> {
+    type Output = usize;
+
+    /// Missing values have count zero.
+    fn index(&self, t: &T) -> &usize {
+        self.counts.get(t).unwrap_or(&0)
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.extend(rhs);
+    }
+}
+
+impl<T: Eq + Hash> SubAssign for Counter<T> {
+    /// Saturating: counts never go below zero.
+    fn sub_assign(&mut self, rhs: Self) {
+        for (t, n) in rhs {
+            self.remove(&t, n);
+        }
+    }
+}
@@ -1 +1,17 @@
+//! The days with a solver. A day is runnable once it has a `pub mod` here
+//! and an entry in [`SOLVERS`]; `aoc25 new` adds both.
+
 pub mod day07;
+
+use crate::solver::Solver;
+
+/// Every day's solver, in order of day.
+// Laid out by hand, one entry per line, for `aoc25 new` to insert into.
+#[rustfmt::skip]
+pub const SOLVERS: &[Solver] = &[
+    Solver::new::<day07::Day07>(7)
+        .with_naive::<day07::Day07>()
+        .with_generator::<day07::Day07>()
+        .with_example(1, day07::part1::EXAMPLE, day07::part1::EXPECTED)
+        .with_example(2, day07::part2::EXAMPLE, day07::part2::EXPECTED),
+];
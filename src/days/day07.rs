@@ -1,9 +1,17 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::BTreeMap;
 
-use crate::common::LinesIterator;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Node {
+use crate::generate::InputGenerator;
+use crate::grid::{Bounds, Grid};
+use crate::hashing::FastSet;
+use crate::solver::{Answer, Day, Naive};
+use crate::stepper;
+use crate::utils::{Memo, Rng};
+use crate::viz::Visualizable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Node {
     Start,
     Empty,
     Splitter,
@@ -19,16 +27,53 @@ impl Node {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Manifold {
-    grid: Vec<Vec<Node>>,
+/// The manifold with every cell a beam has passed through, for `--step`.
+struct BeamFrame<'a> {
+    grid: &'a [Vec<Node>],
+    beams: &'a FastSet<(usize, usize)>,
+}
+
+impl Visualizable for BeamFrame<'_> {
+    fn frame(&self) -> Grid<char> {
+        let rows = self.grid.iter().enumerate().map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(c, node)| match node {
+                    Node::Start => 'S',
+                    Node::Splitter => '^',
+                    Node::Empty if self.beams.contains(&(r, c)) => '|',
+                    Node::Empty => '.',
+                })
+                .collect()
+        });
+        Grid::from_rows(rows.collect())
+    }
+}
+
+/// What a run of the beam simulation saw. Beams that meet are merged, so
+/// the counts are of distinct beam positions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimStats {
+    /// Splitters that a beam reached.
+    pub splits: usize,
+    /// Beams that left through the bottom row.
+    pub beams_exited: usize,
+    /// Split beams lost off the side of the manifold.
+    pub beams_lost: usize,
+    /// Rows holding beams, from the start's row down.
+    pub steps: usize,
+}
+
+/// The parsed grid with its start and bounds found, shared read-only by
+/// both parts.
+#[derive(Debug, Clone, Copy)]
+struct Manifold<'a> {
+    grid: &'a [Vec<Node>],
     start: (usize, usize),
-    n: usize,
-    m: usize,
-    num_splits: usize,
+    bounds: Bounds,
 }
 
-impl Manifold {
+impl<'a> Manifold<'a> {
     fn _find_start(grid: &[Vec<Node>]) -> (usize, usize) {
         grid.iter()
             .enumerate()
@@ -40,68 +85,92 @@ impl Manifold {
             .unwrap()
     }
 
-    fn from_grid(grid: Vec<Vec<Node>>) -> Self {
-        let start = Self::_find_start(&grid);
-        let n = grid.len();
-        let m = grid[0].len();
+    fn from_grid(grid: &'a [Vec<Node>]) -> Self {
+        let start = Self::_find_start(grid);
+        let bounds = Bounds::new(grid.len(), grid[0].len());
         Self {
             grid,
             start,
-            n,
-            m,
-            num_splits: 0,
+            bounds,
         }
     }
 
-    fn get_beam_nbrs(&mut self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+    /// Moves the beam at `pos` down a row, recording in `stats` whether it
+    /// split, left the bottom or lost a branch off the side.
+    fn get_beam_nbrs(
+        &self,
+        pos: (usize, usize),
+        stats: &mut SimStats,
+    ) -> Vec<(usize, usize)> {
         let (r, c) = pos;
-        if r + 1 == self.n {
+        if r + 1 == self.bounds.height() {
+            stats.beams_exited += 1;
             return vec![];
         }
         if self.grid[r + 1][c] != Node::Splitter {
             return vec![(r + 1, c)];
         }
-        self.num_splits += 1;
+        stats.splits += 1;
         let mut out = vec![];
         if c > 0 {
             out.push((r + 1, c - 1));
         }
-        if c + 1 < self.m {
+        if c + 1 < self.bounds.width() {
             out.push((r + 1, c + 1));
         }
+        stats.beams_lost += 2 - out.len();
         out
     }
 
     fn update(
-        &mut self,
-        mut beams: HashSet<(usize, usize)>,
-    ) -> HashSet<(usize, usize)> {
-        let mut new_beams = HashSet::new();
+        &self,
+        mut beams: FastSet<(usize, usize)>,
+        stats: &mut SimStats,
+    ) -> FastSet<(usize, usize)> {
+        let mut new_beams = FastSet::default();
 
         for beam in beams.drain() {
-            let next_beams = self.get_beam_nbrs(beam);
+            let next_beams = self.get_beam_nbrs(beam, stats);
             new_beams.extend(next_beams);
         }
 
         new_beams
     }
 
-    fn run1(&mut self) {
-        let mut beams = HashSet::from([self.start]);
+    fn run1(&self) -> SimStats {
+        let mut stats = SimStats::default();
+        let mut beams = FastSet::from_iter([self.start]);
+        let mut trail = FastSet::default();
         while !beams.is_empty() {
-            beams = self.update(beams);
+            crate::trace!("{} beams, {} splits", beams.len(), stats.splits);
+            if stepper::enabled() {
+                trail.extend(beams.iter().copied());
+                let frame = BeamFrame {
+                    grid: self.grid,
+                    beams: &trail,
+                };
+                stepper::frame(&format!("{} splits", stats.splits), &frame);
+            }
+            beams = self.update(beams, &mut stats);
+            stats.steps += 1;
         }
+        crate::debug!("{stats:?}");
+        stats
     }
 
-    fn build_adj(&self) -> BTreeMap<(usize, usize), HashSet<(usize, usize)>> {
+    /// For the start, each splitter, and each cell just below the bottom
+    /// row, the start and splitters whose beams reach it directly. A sink
+    /// one row further down is linked to every bottom cell.
+    fn build_adj(&self) -> BTreeMap<(usize, usize), FastSet<(usize, usize)>> {
         let mut out = BTreeMap::new();
-        let ends: HashSet<(usize, usize)> =
-            (0..self.m).map(|c| (self.n, c)).collect();
-        let mut to_add: HashSet<(usize, usize)> = ends.clone();
+        let ends: FastSet<(usize, usize)> = (0..self.bounds.width())
+            .map(|c| (self.bounds.height(), c))
+            .collect();
+        let mut to_add: FastSet<(usize, usize)> = ends.clone();
         while !to_add.is_empty() {
-            let mut add_next = HashSet::new();
+            let mut add_next = FastSet::default();
             for pos in to_add.drain() {
-                let mut pos_adj = HashSet::new();
+                let mut pos_adj = FastSet::default();
                 let (r, c) = pos;
                 for i in (0..r).rev() {
                     if matches!(self.grid[i][c], Node::Splitter) {
@@ -123,7 +192,7 @@ impl Manifold {
                             add_next.insert(new_pos);
                         }
                     }
-                    if (c + 1 < self.m)
+                    if (c + 1 < self.bounds.width())
                         && matches!(self.grid[i][c + 1], Node::Splitter)
                     {
                         let new_pos = (i, c + 1);
@@ -137,45 +206,156 @@ impl Manifold {
             }
             to_add = add_next;
         }
-        out.insert((self.n + 1, 0), ends);
+        out.insert((self.bounds.height() + 1, 0), ends);
         out
     }
 
+    /// The number of timelines: distinct routes a single beam can take from
+    /// the start out of the bottom row, choosing a side at each splitter.
+    /// Beams split off the side of the manifold are lost.
     fn compute_num_paths(&self) -> usize {
-        let mut adj = self.build_adj();
-        let mut num_paths_to: HashMap<(usize, usize), usize> = HashMap::new();
-        while let Some((pos, nbrs)) = adj.pop_first() {
-            if pos == self.start {
-                num_paths_to.insert(pos, 1);
-            } else {
-                let mut num_paths: usize = 0;
-                for nbr in nbrs {
-                    if let Some(to_add) = num_paths_to.get(&nbr) {
-                        num_paths += to_add;
+        let adj = self.build_adj();
+        crate::debug!("{} nodes in the splitter graph", adj.len());
+        let mut memo = Memo::new();
+        *memo.get(
+            &(self.bounds.height() + 1, 0),
+            |pos| {
+                if *pos == self.start {
+                    vec![]
+                } else {
+                    adj.get(pos).into_iter().flatten().copied().collect()
+                }
+            },
+            |pos, nbr_paths| {
+                if *pos == self.start {
+                    1
+                } else {
+                    nbr_paths.iter().copied().sum()
+                }
+            },
+        )
+    }
+}
+
+pub struct Day07;
+
+impl Day for Day07 {
+    type Parsed = Vec<Vec<Node>>;
+
+    fn parse(input: &str) -> Self::Parsed {
+        input
+            .lines()
+            .map(|line| line.chars().map(Node::from_char).collect())
+            .collect()
+    }
+
+    fn part1(grid: &Self::Parsed) -> Answer {
+        Manifold::from_grid(grid).run1().splits.into()
+    }
+
+    fn part2(grid: &Self::Parsed) -> Answer {
+        Manifold::from_grid(grid).compute_num_paths().into()
+    }
+}
+
+/// Row-by-row simulation over every column, tracking which columns hold a
+/// beam for part 1 and how many timelines are in each for part 2.
+impl Naive for Day07 {
+    fn naive_part1(grid: &Self::Parsed) -> Answer {
+        let (r0, c0) = Manifold::_find_start(grid);
+        let width = grid[0].len();
+        let mut beams = vec![false; width];
+        beams[c0] = true;
+        let mut splits = 0_usize;
+        for row in &grid[r0 + 1..] {
+            let mut next = vec![false; width];
+            for c in (0..width).filter(|&c| beams[c]) {
+                if row[c] == Node::Splitter {
+                    splits += 1;
+                    if c > 0 {
+                        next[c - 1] = true;
+                    }
+                    if c + 1 < width {
+                        next[c + 1] = true;
                     }
+                } else {
+                    next[c] = true;
                 }
-                num_paths_to.insert(pos, num_paths);
             }
+            beams = next;
         }
-        *num_paths_to.get(&(self.n + 1, 0)).unwrap()
+        splits.into()
     }
-}
 
-fn parse_input(lines: &mut LinesIterator) -> Vec<Vec<Node>> {
-    lines
-        .map(|line| line.unwrap().chars().map(Node::from_char).collect())
-        .collect()
+    fn naive_part2(grid: &Self::Parsed) -> Answer {
+        let (r0, c0) = Manifold::_find_start(grid);
+        let width = grid[0].len();
+        let mut timelines = vec![0_u64; width];
+        timelines[c0] = 1;
+        for row in &grid[r0 + 1..] {
+            let mut next = vec![0; width];
+            for (c, &n) in timelines.iter().enumerate() {
+                if row[c] == Node::Splitter {
+                    if c > 0 {
+                        next[c - 1] += n;
+                    }
+                    if c + 1 < width {
+                        next[c + 1] += n;
+                    }
+                } else {
+                    next[c] += n;
+                }
+            }
+            timelines = next;
+        }
+        timelines.iter().sum::<u64>().into()
+    }
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let grid = parse_input(lines);
-    let mut mf = Manifold::from_grid(grid);
-    mf.run1();
-    format!("{}", mf.num_splits)
-}
+/// Manifolds shaped like the real ones: `size` columns and `size + 1` rows,
+/// with the start centered on the top row and splitters on every other row
+/// below it. Splitters are only placed where the beam's spread could reach
+/// them, on alternating columns, each with even odds.
+impl InputGenerator for Day07 {
+    const DEFAULT_SIZE: usize = 141;
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid = parse_input(lines);
-    let mf = Manifold::from_grid(grid);
-    format!("{:?}", mf.compute_num_paths())
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.max(1);
+        let mid = width / 2;
+        let mut out = String::with_capacity((width + 1) * (width + 1));
+        for r in 0..=width {
+            for c in 0..width {
+                let offset = c.abs_diff(mid);
+                let reach = (r / 2).saturating_sub(1);
+                let node = if r == 0 && c == mid {
+                    'S'
+                } else if r > 0
+                    && r % 2 == 0
+                    && offset <= reach
+                    && (reach - offset) % 2 == 0
+                    && rng.gen_bool(0.5)
+                {
+                    '^'
+                } else {
+                    '.'
+                };
+                out.push(node);
+            }
+            out.push('\n');
+        }
+        out
+    }
 }
+
+crate::aoc_test!(
+    day07,
+    part1,
+    example = include_str!("../../inputs/examples/day07.txt"),
+    expected = "21",
+);
+crate::aoc_test!(
+    day07,
+    part2,
+    example = include_str!("../../inputs/examples/day07.txt"),
+    expected = "40",
+);
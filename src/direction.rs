@@ -0,0 +1,504 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, BitAnd, BitOr, Mul, Not, Sub};
+use std::str::FromStr;
+
+use crate::utils::Canonicalize;
+
+/// One of the eight compass directions, with north being row `-1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// The four cardinal directions, clockwise from north.
+    pub const CARDINALS: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    /// Position in [`Direction::ALL`].
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    #[must_use]
+    pub const fn from_index(i: usize) -> Self {
+        Self::ALL[i % 8]
+    }
+
+    #[must_use]
+    pub const fn is_cardinal(self) -> bool {
+        self.index().is_multiple_of(2)
+    }
+
+    /// `(d_row, d_col)` of a single step in this direction.
+    #[must_use]
+    pub const fn delta(self) -> (i64, i64) {
+        match self {
+            Self::N => (-1, 0),
+            Self::NE => (-1, 1),
+            Self::E => (0, 1),
+            Self::SE => (1, 1),
+            Self::S => (1, 0),
+            Self::SW => (1, -1),
+            Self::W => (0, -1),
+            Self::NW => (-1, -1),
+        }
+    }
+
+    /// Rotates clockwise by `eighths` eighths of a full turn.
+    #[must_use]
+    pub const fn rotate(self, eighths: usize) -> Self {
+        Self::from_index(self.index() + eighths % 8)
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError(String);
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid direction: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    /// Accepts compass names (`N`, `NE`, `north`, ...), `U`/`D`/`L`/`R`,
+    /// `up`/`down`/`left`/`right` and the arrows `^`, `v`, `<`, `>`, in any
+    /// case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" | "u" | "^" | "north" | "up" => Ok(Self::N),
+            "ne" | "northeast" => Ok(Self::NE),
+            "e" | "r" | ">" | "east" | "right" => Ok(Self::E),
+            "se" | "southeast" => Ok(Self::SE),
+            "s" | "d" | "v" | "south" | "down" => Ok(Self::S),
+            "sw" | "southwest" => Ok(Self::SW),
+            "w" | "l" | "<" | "west" | "left" => Ok(Self::W),
+            "nw" | "northwest" => Ok(Self::NW),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::N => "N",
+            Self::NE => "NE",
+            Self::E => "E",
+            Self::SE => "SE",
+            Self::S => "S",
+            Self::SW => "SW",
+            Self::W => "W",
+            Self::NW => "NW",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A set of directions stored as one bit per direction, e.g. which ways a
+/// pipe connects or which neighbors belong to the same region.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DirSet(u8);
+
+impl DirSet {
+    pub const EMPTY: Self = Self(0);
+    pub const ALL: Self = Self(u8::MAX);
+    pub const CARDINALS: Self = Self(0b0101_0101);
+
+    #[must_use]
+    pub const fn single(dir: Direction) -> Self {
+        Self(1 << dir.index())
+    }
+
+    /// The set with bit `i` standing for `Direction::from_index(i)`.
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn contains(self, dir: Direction) -> bool {
+        self.0 & (1 << dir.index()) != 0
+    }
+
+    pub const fn insert(&mut self, dir: Direction) {
+        self.0 |= 1 << dir.index();
+    }
+
+    pub const fn remove(&mut self, dir: Direction) {
+        self.0 &= !(1 << dir.index());
+    }
+
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    #[must_use]
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Every direction rotated clockwise by `eighths` eighths of a turn.
+    #[must_use]
+    pub const fn rotate(self, eighths: usize) -> Self {
+        // Bit i moves to bit i + eighths, wrapping around.
+        #[allow(clippy::cast_possible_truncation)]
+        Self(self.0.rotate_left((eighths % 8) as u32))
+    }
+
+    /// Members in [`Direction::ALL`] order, clockwise from north.
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |&d| self.contains(d))
+    }
+}
+
+impl From<Direction> for DirSet {
+    fn from(dir: Direction) -> Self {
+        Self::single(dir)
+    }
+}
+
+impl FromIterator<Direction> for DirSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, |set, d| set | d.into())
+    }
+}
+
+impl BitOr for DirSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitAnd for DirSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl Not for DirSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+/// Shown as the set of member directions, e.g. `{N, E}`.
+impl fmt::Debug for DirSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// The cardinal directions a pipe or track glyph connects to, or `None`
+/// for glyphs that are not track (including a pipe maze's `S`, whose
+/// connections have to be inferred from its neighbors).
+///
+/// Pipes `|-LJ7F` connect their two ends, `+` all four sides, and carts
+/// `<>^v` stand on straight track along their heading. The curves `/` and
+/// `\` are `None` too: which two sides they join depends on where they
+/// sit, so walks should turn on them with [`follow_track`], and
+/// [`Grid::track_connections`](crate::grid::Grid::track_connections)
+/// works their sides out from the neighboring track.
+#[must_use]
+pub fn glyph_connections(glyph: char) -> Option<DirSet> {
+    use Direction::{E, N, S, W};
+    let pair = |a, b| Some(DirSet::single(a) | DirSet::single(b));
+    match glyph {
+        '|' | '^' | 'v' => pair(N, S),
+        '-' | '<' | '>' => pair(E, W),
+        'L' => pair(N, E),
+        'J' => pair(N, W),
+        '7' => pair(S, W),
+        'F' => pair(S, E),
+        '+' => Some(DirSet::CARDINALS),
+        _ => None,
+    }
+}
+
+/// The heading after moving onto `glyph` while heading `dir`, or `None` if
+/// the glyph cannot be entered that way.
+///
+/// Straight pieces and `+` keep the heading (turning at intersections is
+/// up to the caller), pipe corners turn along the pipe, and the curves `/`
+/// and `\` turn as a mirror would.
+#[must_use]
+pub fn follow_track(glyph: char, dir: Direction) -> Option<Direction> {
+    use Direction::{E, N, S, W};
+    match glyph {
+        '/' => match dir {
+            N => Some(E),
+            E => Some(N),
+            S => Some(W),
+            W => Some(S),
+            _ => None,
+        },
+        '\\' => match dir {
+            N => Some(W),
+            W => Some(N),
+            S => Some(E),
+            E => Some(S),
+            _ => None,
+        },
+        '+' => dir.is_cardinal().then_some(dir),
+        _ => {
+            let mut exits = glyph_connections(glyph)?;
+            let entry = dir.opposite();
+            if !exits.contains(entry) {
+                return None;
+            }
+            exits.remove(entry);
+            exits.iter().next()
+        }
+    }
+}
+
+/// Non-negative `(row, col)` coordinates, as used to index a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coords(pub usize, pub usize);
+
+/// Signed `(row, col)` coordinates, for unbounded or sparse grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ICoords(pub i64, pub i64);
+
+fn offset(x: usize, d: i64) -> Option<usize> {
+    let step = usize::try_from(d.unsigned_abs()).ok()?;
+    if d < 0 {
+        x.checked_sub(step)
+    } else {
+        x.checked_add(step)
+    }
+}
+
+impl Coords {
+    /// One step in `dir`, or `None` if that would leave the non-negative
+    /// quadrant.
+    #[must_use]
+    pub fn step(self, dir: Direction) -> Option<Self> {
+        self.shift(dir.delta())
+    }
+
+    /// Moves by `(dr, dc)`, or `None` if that would leave the non-negative
+    /// quadrant.
+    #[must_use]
+    pub fn shift(self, (dr, dc): (i64, i64)) -> Option<Self> {
+        Some(Self(offset(self.0, dr)?, offset(self.1, dc)?))
+    }
+
+    #[must_use]
+    pub const fn manhattan(self, other: Self) -> usize {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+}
+
+impl ICoords {
+    #[must_use]
+    pub const fn step(self, dir: Direction) -> Self {
+        let (dr, dc) = dir.delta();
+        Self(self.0 + dr, self.1 + dc)
+    }
+
+    #[must_use]
+    pub const fn manhattan(self, other: Self) -> u64 {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+}
+
+impl From<(usize, usize)> for Coords {
+    fn from((r, c): (usize, usize)) -> Self {
+        Self(r, c)
+    }
+}
+
+impl From<(i64, i64)> for ICoords {
+    fn from((r, c): (i64, i64)) -> Self {
+        Self(r, c)
+    }
+}
+
+impl TryFrom<ICoords> for Coords {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(ICoords(r, c): ICoords) -> Result<Self, Self::Error> {
+        Ok(Self(r.try_into()?, c.try_into()?))
+    }
+}
+
+impl TryFrom<Coords> for ICoords {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(Coords(r, c): Coords) -> Result<Self, Self::Error> {
+        Ok(Self(r.try_into()?, c.try_into()?))
+    }
+}
+
+impl Add for ICoords {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for ICoords {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Mul<i64> for ICoords {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl fmt::Display for Coords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl fmt::Display for ICoords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCoordsError {
+    MissingComma,
+    Int(ParseIntError),
+}
+
+impl fmt::Display for ParseCoordsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingComma => {
+                f.write_str("expected two comma-separated values")
+            }
+            Self::Int(err) => write!(f, "invalid coordinate: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCoordsError {}
+
+impl From<ParseIntError> for ParseCoordsError {
+    fn from(err: ParseIntError) -> Self {
+        Self::Int(err)
+    }
+}
+
+fn split_pair(s: &str) -> Result<(&str, &str), ParseCoordsError> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(s);
+    let (a, b) = s.split_once(',').ok_or(ParseCoordsError::MissingComma)?;
+    Ok((a.trim(), b.trim()))
+}
+
+impl FromStr for Coords {
+    type Err = ParseCoordsError;
+
+    /// Parses `"r,c"`, optionally parenthesised and with spaces.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (r, c) = split_pair(s)?;
+        Ok(Self(r.parse()?, c.parse()?))
+    }
+}
+
+impl FromStr for ICoords {
+    type Err = ParseCoordsError;
+
+    /// Parses `"r,c"`, optionally parenthesised and with spaces.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (r, c) = split_pair(s)?;
+        Ok(Self(r.parse()?, c.parse()?))
+    }
+}
+
+impl Canonicalize for Coords {
+    fn canonicalize(&self) -> Self {
+        *self
+    }
+}
+
+impl Canonicalize for ICoords {
+    fn canonicalize(&self) -> Self {
+        *self
+    }
+}
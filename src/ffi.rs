@@ -0,0 +1,89 @@
+//! A C ABI over the solvers, behind the `ffi` feature, for calling them
+//! from other languages.
+//!
+//! Build a shared library with `cargo rustc --lib --release --features ffi
+//! --crate-type cdylib`, then e.g. from Python:
+//!
+//! ```python
+//! lib = ctypes.CDLL("target/release/libaoc25.so")
+//! buf = ctypes.create_string_buffer(4096)
+//! size = ctypes.c_size_t(len(buf))
+//! data = open("inputs/input7.txt", "rb").read()
+//! status = lib.aoc_solve(7, 1, data, len(data), buf, ctypes.byref(size))
+//! answer = buf.raw[: size.value].decode()
+//! ```
+
+use std::slice;
+
+use crate::common::{SolveError, try_solve};
+
+/// The answer was written.
+pub const AOC_OK: i32 = 0;
+/// The input was not valid UTF-8.
+pub const AOC_INVALID_INPUT: i32 = 1;
+/// The day or part is out of range, or the day has no solver.
+pub const AOC_INVALID_DAY: i32 = 2;
+/// The solver panicked.
+pub const AOC_PANICKED: i32 = 3;
+/// `out_buf` was too small; `*out_len` holds the size needed.
+pub const AOC_BUFFER_TOO_SMALL: i32 = 4;
+/// A required pointer was null.
+pub const AOC_NULL_POINTER: i32 = -1;
+
+/// Solves one part of `day` for the `len` bytes of UTF-8 at `input_ptr`.
+///
+/// On entry `*out_len` is the capacity of `out_buf`. The answer, or on
+/// failure an error message, is written there without a terminating NUL,
+/// and `*out_len` is set to its length in bytes. If it does not fit,
+/// nothing is written, `*out_len` is set to the size needed and
+/// [`AOC_BUFFER_TOO_SMALL`] is returned. Otherwise returns [`AOC_OK`] or
+/// one of the other `AOC_` error codes.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes, `out_len` must point to
+/// a writable `usize`, and `out_buf` must point to `*out_len` writable
+/// bytes. `input_ptr` may only be null if `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_buf.is_null()
+        || out_len.is_null()
+        || (input_ptr.is_null() && len > 0)
+    {
+        return AOC_NULL_POINTER;
+    }
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees `len` readable bytes.
+        unsafe { slice::from_raw_parts(input_ptr, len) }
+    };
+    let (status, text) = match str::from_utf8(bytes) {
+        Err(err) => (AOC_INVALID_INPUT, format!("input is not UTF-8: {err}")),
+        Ok(input) => match try_solve(day, part, input) {
+            Ok(answer) => (AOC_OK, answer),
+            Err(err @ SolveError::Panicked(_)) => {
+                (AOC_PANICKED, err.to_string())
+            }
+            Err(err) => (AOC_INVALID_DAY, err.to_string()),
+        },
+    };
+    // SAFETY: the caller guarantees `out_len` is valid, and `out_buf` holds
+    // `*out_len` bytes, of which only the first `text.len()` are written.
+    unsafe {
+        let capacity = *out_len;
+        *out_len = text.len();
+        if text.len() > capacity {
+            return AOC_BUFFER_TOO_SMALL;
+        }
+        out_buf.copy_from_nonoverlapping(text.as_ptr(), text.len());
+    }
+    status
+}
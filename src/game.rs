@@ -0,0 +1,171 @@
+use std::cell::RefCell;
+use std::hash::Hash;
+
+use crate::hashing::FastMap;
+use crate::utils::{Canonicalize, Memo};
+
+/// The moves available from a position in [`expectimax`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Moves<S> {
+    /// The player picks whichever successor is best for them.
+    Choice(Vec<S>),
+    /// A successor is picked at random, each with the given number of ways
+    /// (e.g. the number of dice rolls producing it).
+    Chance(Vec<(S, u64)>),
+}
+
+/// The value of `start` under optimal play by two alternating players.
+///
+/// `moves` lists the positions reachable in one move, with none meaning the
+/// game is over, and `score` values a finished position for the player to
+/// move there. Values are from the point of view of the player to move at
+/// `start`: each player maximizes their own score, which is the negation of
+/// their opponent's.
+///
+/// Positions are memoized on their canonical form, so symmetric positions
+/// are solved once. The game must not be able to revisit a position.
+///
+/// # Panics
+///
+/// Panics if a position can be revisited.
+pub fn solve<S, M, I, F>(start: &S, mut moves: M, mut score: F) -> i64
+where
+    S: Canonicalize + Clone + Eq + Hash,
+    M: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> i64,
+{
+    let mut memo = Memo::new();
+    *memo.get(
+        &start.canonicalize(),
+        |s| {
+            moves(s)
+                .into_iter()
+                .map(|t| t.canonicalize())
+                .collect::<Vec<_>>()
+        },
+        |s, values: &[&i64]| {
+            values
+                .iter()
+                .map(|&&v| -v)
+                .max()
+                .unwrap_or_else(|| score(s))
+        },
+    )
+}
+
+/// The expected score of `start` when one player maximizes against chance,
+/// e.g. choosing moves between dice rolls.
+///
+/// A position with no moves, whether an empty `Choice` or `Chance`, is
+/// finished and valued by `score`. Positions are memoized on their
+/// canonical form.
+///
+/// # Panics
+///
+/// Panics if a position can be revisited.
+// Weights are small counts of dice outcomes, far below 2^52.
+#[allow(clippy::cast_precision_loss)]
+pub fn expectimax<S, M, F>(start: &S, mut moves: M, mut score: F) -> f64
+where
+    S: Canonicalize + Clone + Eq + Hash,
+    M: FnMut(&S) -> Moves<S>,
+    F: FnMut(&S) -> f64,
+{
+    // The chance weights of each expanded position, from when `moves` was
+    // called on it until its value is combined.
+    let weights: RefCell<FastMap<S, Option<Vec<u64>>>> = RefCell::default();
+    let mut memo = Memo::new();
+    *memo.get(
+        &start.canonicalize(),
+        |s| {
+            let (next, w): (Vec<S>, _) = match moves(s) {
+                Moves::Choice(next) => (next, None),
+                Moves::Chance(next) => {
+                    let (next, w) = next.into_iter().unzip();
+                    (next, Some(w))
+                }
+            };
+            weights.borrow_mut().insert(s.clone(), w);
+            next.iter()
+                .map(Canonicalize::canonicalize)
+                .collect::<Vec<_>>()
+        },
+        |s, values: &[&f64]| {
+            let w = weights.borrow_mut().remove(s).flatten();
+            if values.is_empty() {
+                return score(s);
+            }
+            w.map_or_else(
+                || values.iter().map(|&&v| v).fold(f64::MIN, f64::max),
+                |w| {
+                    let total: u64 = w.iter().sum();
+                    let sum: f64 = values
+                        .iter()
+                        .zip(&w)
+                        .map(|(&&v, &n)| v * n as f64)
+                        .sum();
+                    sum / total as f64
+                },
+            )
+        },
+    )
+}
+
+/// Counts, for each of `N` players, the ways the game from `start` ends in
+/// their win, as in "in how many universes does each player win".
+///
+/// `moves` lists successor positions with how many ways each arises;
+/// `winner` returns the index of the player who has won a finished
+/// position, or `None` if play continues. Positions are memoized on their
+/// canonical form.
+///
+/// # Panics
+///
+/// Panics if a position can be revisited, or if the game can end without
+/// a winner.
+pub fn win_counts<const N: usize, S, M, I, W>(
+    start: &S,
+    mut moves: M,
+    mut winner: W,
+) -> [u64; N]
+where
+    S: Canonicalize + Clone + Eq + Hash,
+    M: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    W: FnMut(&S) -> Option<usize>,
+{
+    // How each expanded position resolves: its successors' weights, or the
+    // player who has won it.
+    let outcomes: RefCell<FastMap<S, Result<Vec<u64>, usize>>> =
+        RefCell::default();
+    let mut memo = Memo::new();
+    *memo.get(
+        &start.canonicalize(),
+        |s| {
+            if let Some(player) = winner(s) {
+                outcomes.borrow_mut().insert(s.clone(), Err(player));
+                return vec![];
+            }
+            let (next, w): (Vec<S>, _) = moves(s).into_iter().unzip();
+            outcomes.borrow_mut().insert(s.clone(), Ok(w));
+            next.iter().map(Canonicalize::canonicalize).collect()
+        },
+        |s, values: &[&[u64; N]]| {
+            let mut counts = [0; N];
+            let outcome = outcomes.borrow_mut().remove(s);
+            match outcome.expect("expanded position") {
+                Err(player) => counts[player] = 1,
+                Ok(w) => {
+                    assert!(!values.is_empty(), "game ended without a winner");
+                    for (value, n) in values.iter().zip(w) {
+                        for (count, v) in counts.iter_mut().zip(value.iter()) {
+                            *count += v * n;
+                        }
+                    }
+                }
+            }
+            counts
+        },
+    )
+}
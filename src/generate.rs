@@ -0,0 +1,27 @@
+//! Random puzzle inputs for `aoc25 gen`, to stress-test solvers on inputs
+//! larger than the real one and to compare two solvers on many small ones.
+//!
+//! ```text
+//! aoc25 gen 7 2000 --seed 1 > big7.txt
+//! aoc25 bench 7 --input big7.txt
+//! ```
+
+use crate::utils::Rng;
+
+/// A day that can make up its own inputs.
+pub trait InputGenerator {
+    /// The size `gen` uses when none is given, about that of a real input.
+    const DEFAULT_SIZE: usize;
+
+    /// A random input that the day's solver must accept. What `size`
+    /// measures, such as a grid's width, is up to the day, but doubling it
+    /// should make the puzzle substantially harder.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// Generates an input for `D` from `seed`, at `D`'s default size unless
+/// `size` is given. The same seed and size always give the same input.
+#[must_use]
+pub fn generate<D: InputGenerator>(size: Option<usize>, seed: u64) -> String {
+    D::generate(&mut Rng::new(seed), size.unwrap_or(D::DEFAULT_SIZE))
+}
@@ -0,0 +1,515 @@
+use crate::direction::ICoords;
+use crate::math::gcd;
+
+fn closed_edges(
+    points: &[ICoords],
+) -> impl Iterator<Item = (ICoords, ICoords)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn doubled_area(points: &[ICoords]) -> i64 {
+    closed_edges(points)
+        .map(|(ICoords(r1, c1), ICoords(r2, c2))| r1 * c2 - r2 * c1)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Area enclosed by the polygon with the given vertices, in order.
+///
+/// The polygon is closed implicitly. Half-integer areas are rounded down.
+#[must_use]
+pub fn shoelace_area(points: &[ICoords]) -> i64 {
+    doubled_area(points) / 2
+}
+
+/// Number of lattice points on the boundary of the polygon.
+///
+/// For axis-aligned (or 45 degree) edges this is the length of the perimeter
+/// in unit steps.
+///
+/// # Panics
+///
+/// Panics if an edge spans more than `i64::MAX` lattice points.
+#[must_use]
+pub fn perimeter_length(points: &[ICoords]) -> i64 {
+    closed_edges(points)
+        .map(|(ICoords(r1, c1), ICoords(r2, c2))| {
+            i64::try_from(gcd(i128::from(r2 - r1), i128::from(c2 - c1)))
+                .unwrap()
+        })
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, via Pick's theorem.
+#[must_use]
+pub fn interior_points(points: &[ICoords]) -> i64 {
+    (doubled_area(points) - perimeter_length(points)) / 2 + 1
+}
+
+/// The cells a straight line from `a` to `b` passes closest to, from `a` to
+/// `b` inclusive, by Bresenham's algorithm. Consecutive cells touch, at
+/// least diagonally.
+pub fn bresenham(a: ICoords, b: ICoords) -> impl Iterator<Item = ICoords> {
+    let (dr, dc) = (-(b.0 - a.0).abs(), (b.1 - a.1).abs());
+    let (sr, sc) = ((b.0 - a.0).signum(), (b.1 - a.1).signum());
+    let mut err = dc + dr;
+    let mut next = Some(a);
+    std::iter::from_fn(move || {
+        let cur = next?;
+        next = (cur != b).then(|| {
+            let (mut r, mut c) = (cur.0, cur.1);
+            let e2 = 2 * err;
+            if e2 >= dr {
+                err += dr;
+                c += sc;
+            }
+            if e2 <= dc {
+                err += dc;
+                r += sr;
+            }
+            ICoords(r, c)
+        });
+        Some(cur)
+    })
+}
+
+/// The lattice points exactly on the segment from `a` to `b`, from `a` to
+/// `b` inclusive. Anything at one of the points between them blocks the
+/// view from `a` to `b`.
+///
+/// # Panics
+///
+/// Panics if the coordinates are so far apart that their differences
+/// overflow.
+pub fn lattice_points(a: ICoords, b: ICoords) -> impl Iterator<Item = ICoords> {
+    let d = b - a;
+    let steps = gcd(i128::from(d.0), i128::from(d.1));
+    // The gcd divides both differences, so it fits in an i64.
+    let steps = i64::try_from(steps).expect("gcd fits in i64");
+    let unit = if steps == 0 {
+        ICoords(0, 0)
+    } else {
+        ICoords(d.0 / steps, d.1 / steps)
+    };
+    (0..=steps).map(move |i| a + unit * i)
+}
+
+/// A horizontal, vertical or 45 degree diagonal segment of lattice cells,
+/// including both endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    start: ICoords,
+    end: ICoords,
+}
+
+impl Segment {
+    /// # Panics
+    ///
+    /// Panics if the segment is neither axis-aligned nor at 45 degrees.
+    #[must_use]
+    pub const fn new(start: ICoords, end: ICoords) -> Self {
+        let (dr, dc) = ((end.0 - start.0).abs(), (end.1 - start.1).abs());
+        assert!(
+            dr == 0 || dc == 0 || dr == dc,
+            "segments must be axis-aligned or diagonal"
+        );
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub const fn start(&self) -> ICoords {
+        self.start
+    }
+
+    #[must_use]
+    pub const fn end(&self) -> ICoords {
+        self.end
+    }
+
+    #[must_use]
+    pub const fn is_horizontal(&self) -> bool {
+        self.start.0 == self.end.0
+    }
+
+    #[must_use]
+    pub const fn is_vertical(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    #[must_use]
+    pub const fn is_diagonal(&self) -> bool {
+        !self.is_horizontal() && !self.is_vertical()
+    }
+
+    /// The unit step from `start` towards `end`, or zero for a single cell.
+    const fn step(&self) -> ICoords {
+        ICoords(
+            (self.end.0 - self.start.0).signum(),
+            (self.end.1 - self.start.1).signum(),
+        )
+    }
+
+    /// Number of steps from `start` to `end`; one less than the cell count.
+    const fn steps(&self) -> i64 {
+        let (dr, dc) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        if dr.abs() > dc.abs() {
+            dr.abs()
+        } else {
+            dc.abs()
+        }
+    }
+
+    /// The cells covered, from `start` to `end`.
+    pub fn cells(&self) -> impl Iterator<Item = ICoords> + use<> {
+        let (start, step) = (self.start, self.step());
+        (0..=self.steps()).map(move |i| start + step * i)
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: ICoords) -> bool {
+        let d = p - self.start;
+        let step = self.step();
+        let i = d.0.abs().max(d.1.abs());
+        i <= self.steps() && step * i == d
+    }
+
+    /// The cells both segments cover, as a segment (a single cell where they
+    /// cross, or a run where collinear segments overlap).
+    ///
+    /// Diagonals crossing between lattice points share no cell.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if self.steps() == 0 {
+            return other.contains(self.start).then_some(*self);
+        }
+        if other.steps() == 0 {
+            return self.contains(other.start).then_some(*other);
+        }
+        let (d1, d2) = (self.step(), other.step());
+        let ICoords(er, ec) = other.start - self.start;
+        let det = d2.0 * d1.1 - d1.0 * d2.1;
+        if det == 0 {
+            // Parallel: overlap iff collinear, as a range of steps along
+            // `self`.
+            if er * d1.1 != ec * d1.0 {
+                return None;
+            }
+            let along = |p: ICoords| {
+                let ICoords(r, c) = p - self.start;
+                (r * d1.0 + c * d1.1) / (d1.0 * d1.0 + d1.1 * d1.1)
+            };
+            let (t1, t2) = (along(other.start), along(other.end));
+            let lo = t1.min(t2).max(0);
+            let hi = t1.max(t2).min(self.steps());
+            return (lo <= hi).then(|| {
+                Self::new(self.start + d1 * lo, self.start + d1 * hi)
+            });
+        }
+        let i = d2.0 * ec - er * d2.1;
+        let j = d1.0 * ec - er * d1.1;
+        if i % det != 0 || j % det != 0 {
+            return None;
+        }
+        let (i, j) = (i / det, j / det);
+        ((0..=self.steps()).contains(&i) && (0..=other.steps()).contains(&j))
+            .then(|| {
+                let p = self.start + d1 * i;
+                Self::new(p, p)
+            })
+    }
+}
+
+/// Number of cells covered by at least two of `segments`, for example where
+/// vent lines overlap.
+///
+/// Sweeps down the rows, keeping only the segments that cross the current
+/// row and merging their column spans, so the cost depends on the rows
+/// spanned rather than on the area of the bounding box.
+#[must_use]
+pub fn count_overlapping_cells(segments: &[Segment]) -> u64 {
+    let top = |s: &Segment| s.start.0.min(s.end.0);
+    let bottom = |s: &Segment| s.start.0.max(s.end.0);
+    let mut pending: Vec<&Segment> = segments.iter().collect();
+    // Sorted by descending top row, so the next to activate is last.
+    pending.sort_unstable_by_key(|s| std::cmp::Reverse(top(s)));
+    let mut active: Vec<&Segment> = vec![];
+    let mut count = 0;
+    let mut row = 0;
+    loop {
+        row = if active.is_empty() {
+            match pending.last() {
+                Some(s) => top(s),
+                None => break,
+            }
+        } else {
+            row + 1
+        };
+        while pending.last().is_some_and(|s| top(s) == row) {
+            active.extend(pending.pop());
+        }
+        let mut events: Vec<(i64, i64)> = active
+            .iter()
+            .flat_map(|s| {
+                let (lo, hi) = if s.is_horizontal() {
+                    (s.start.1.min(s.end.1), s.start.1.max(s.end.1))
+                } else {
+                    let step = s.step();
+                    let c = s.start.1 + step.1 * (row - s.start.0) * step.0;
+                    (c, c)
+                };
+                [(lo, 1), (hi + 1, -1)]
+            })
+            .collect();
+        events.sort_unstable();
+        let mut depth = 0;
+        for w in events.windows(2) {
+            depth += w[0].1;
+            if depth >= 2 {
+                // Events are sorted, so the span is non-negative.
+                count += (w[1].0 - w[0].0).unsigned_abs();
+            }
+        }
+        active.retain(|s| bottom(s) > row);
+    }
+    count
+}
+
+/// An axis-aligned rectangle of lattice cells, including its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    lo: ICoords,
+    hi: ICoords,
+}
+
+impl Rect {
+    /// The rectangle with opposite corners `a` and `b`, in either order.
+    #[must_use]
+    pub fn new(a: ICoords, b: ICoords) -> Self {
+        Self {
+            lo: ICoords(a.0.min(b.0), a.1.min(b.1)),
+            hi: ICoords(a.0.max(b.0), a.1.max(b.1)),
+        }
+    }
+
+    /// The corner with the smallest row and column.
+    #[must_use]
+    pub const fn lo(&self) -> ICoords {
+        self.lo
+    }
+
+    /// The corner with the largest row and column.
+    #[must_use]
+    pub const fn hi(&self) -> ICoords {
+        self.hi
+    }
+
+    /// Number of cells covered.
+    #[must_use]
+    pub const fn area(&self) -> i64 {
+        (self.hi.0 - self.lo.0 + 1) * (self.hi.1 - self.lo.1 + 1)
+    }
+
+    #[must_use]
+    pub const fn contains(&self, p: ICoords) -> bool {
+        self.lo.0 <= p.0
+            && p.0 <= self.hi.0
+            && self.lo.1 <= p.1
+            && p.1 <= self.hi.1
+    }
+
+    /// Whether `other` lies entirely within `self`.
+    #[must_use]
+    pub const fn contains_rect(&self, other: &Self) -> bool {
+        self.contains(other.lo) && self.contains(other.hi)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lo = ICoords(self.lo.0.max(other.lo.0), self.lo.1.max(other.lo.1));
+        let hi = ICoords(self.hi.0.min(other.hi.0), self.hi.1.min(other.hi.1));
+        (lo.0 <= hi.0 && lo.1 <= hi.1).then_some(Self { lo, hi })
+    }
+
+    /// Number of cells covered by both rectangles.
+    #[must_use]
+    pub fn overlap_area(&self, other: &Self) -> i64 {
+        self.intersection(other).map_or(0, |rect| rect.area())
+    }
+}
+
+/// An axis-aligned box of lattice cells in 3D, including its faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    lo: [i64; 3],
+    hi: [i64; 3],
+}
+
+impl Cuboid {
+    /// The cuboid with opposite corners `a` and `b`, in either order.
+    #[must_use]
+    pub fn new(a: [i64; 3], b: [i64; 3]) -> Self {
+        Self {
+            lo: std::array::from_fn(|i| a[i].min(b[i])),
+            hi: std::array::from_fn(|i| a[i].max(b[i])),
+        }
+    }
+
+    /// The corner with the smallest coordinates.
+    #[must_use]
+    pub const fn lo(&self) -> [i64; 3] {
+        self.lo
+    }
+
+    /// The corner with the largest coordinates.
+    #[must_use]
+    pub const fn hi(&self) -> [i64; 3] {
+        self.hi
+    }
+
+    /// Number of cells covered.
+    #[must_use]
+    pub fn volume(&self) -> i64 {
+        (0..3).map(|i| self.hi[i] - self.lo[i] + 1).product()
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: [i64; 3]) -> bool {
+        (0..3).all(|i| self.lo[i] <= p[i] && p[i] <= self.hi[i])
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lo = std::array::from_fn(|i| self.lo[i].max(other.lo[i]));
+        let hi = std::array::from_fn(|i| self.hi[i].min(other.hi[i]));
+        (0..3).all(|i| lo[i] <= hi[i]).then_some(Self { lo, hi })
+    }
+
+    /// The cells of `self` outside `other`, as at most six disjoint
+    /// cuboids.
+    #[must_use]
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut pieces = vec![];
+        // Peel off the slabs on either side of `cut` one axis at a time,
+        // shrinking what is left to the cut's extent along that axis.
+        let mut rest = *self;
+        for axis in 0..3 {
+            if rest.lo[axis] < cut.lo[axis] {
+                let mut below = rest;
+                below.hi[axis] = cut.lo[axis] - 1;
+                pieces.push(below);
+            }
+            if cut.hi[axis] < rest.hi[axis] {
+                let mut above = rest;
+                above.lo[axis] = cut.hi[axis] + 1;
+                pieces.push(above);
+            }
+            rest.lo[axis] = cut.lo[axis];
+            rest.hi[axis] = cut.hi[axis];
+        }
+        pieces
+    }
+}
+
+/// A set of lattice cells in 3D, stored as disjoint cuboids, e.g. the cubes
+/// left on after a sequence of on/off reboot steps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CuboidSet {
+    pieces: Vec<Cuboid>,
+}
+
+impl CuboidSet {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { pieces: vec![] }
+    }
+
+    /// The disjoint cuboids making up the set.
+    #[must_use]
+    pub fn pieces(&self) -> &[Cuboid] {
+        &self.pieces
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// Number of cells in the set.
+    #[must_use]
+    pub fn volume(&self) -> i64 {
+        self.pieces.iter().map(Cuboid::volume).sum()
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: [i64; 3]) -> bool {
+        self.pieces.iter().any(|c| c.contains(p))
+    }
+
+    /// Adds every cell of `cuboid`.
+    pub fn insert(&mut self, cuboid: Cuboid) {
+        self.remove(&cuboid);
+        self.pieces.push(cuboid);
+    }
+
+    /// Removes every cell of `cuboid`, splitting the pieces it cuts.
+    pub fn remove(&mut self, cuboid: &Cuboid) {
+        self.pieces = self
+            .pieces
+            .iter()
+            .flat_map(|piece| piece.subtract(cuboid))
+            .collect();
+    }
+
+    /// Keeps only the cells inside `cuboid`.
+    pub fn retain_within(&mut self, cuboid: &Cuboid) {
+        self.pieces = self
+            .pieces
+            .iter()
+            .filter_map(|piece| piece.intersection(cuboid))
+            .collect();
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        for &piece in &other.pieces {
+            out.insert(piece);
+        }
+        out
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let pieces = self
+            .pieces
+            .iter()
+            .flat_map(|a| other.pieces.iter().filter_map(|b| a.intersection(b)))
+            .collect();
+        Self { pieces }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        for piece in &other.pieces {
+            out.remove(piece);
+        }
+        out
+    }
+}
+
+impl FromIterator<Cuboid> for CuboidSet {
+    fn from_iter<I: IntoIterator<Item = Cuboid>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for cuboid in iter {
+            set.insert(cuboid);
+        }
+        set
+    }
+}
@@ -0,0 +1,1070 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::hash::{BuildHasher, Hash};
+
+use crate::bitset::BitSet;
+use crate::hashing::{FastMap, FastSet};
+use crate::interner::AdjList;
+use crate::search::SearchStats;
+use crate::utils::MinHeap;
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Explores the closure-defined graph from `src` breadth-first, up to
+/// `max_depth` edges away, and renders what it finds in Graphviz DOT format.
+///
+/// Vertices and edges along `highlight` (a path, in order) are drawn in red.
+pub fn to_dot_from<T, F, I, L>(
+    src: T,
+    mut get_edges: F,
+    max_depth: usize,
+    vertex_label: L,
+    highlight: &[T],
+) -> String
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    L: Fn(&T) -> String,
+{
+    let mut ids: FastMap<T, usize> = FastMap::from_iter([(src.clone(), 0)]);
+    let mut order = vec![src.clone()];
+    let mut edges = vec![];
+    let mut queue = VecDeque::from([(src, 0)]);
+    while let Some((v, depth)) = queue.pop_front() {
+        if depth == max_depth {
+            continue;
+        }
+        let v_id = ids[&v];
+        for w in get_edges(v) {
+            let w_id = *ids.entry(w.clone()).or_insert_with(|| {
+                order.push(w.clone());
+                queue.push_back((w, depth + 1));
+                order.len() - 1
+            });
+            edges.push((v_id, w_id));
+        }
+    }
+
+    let on_path: FastSet<usize> = highlight
+        .iter()
+        .filter_map(|v| ids.get(v).copied())
+        .collect();
+    let path_edges: FastSet<(usize, usize)> = highlight
+        .windows(2)
+        .filter_map(|w| Some((*ids.get(&w[0])?, *ids.get(&w[1])?)))
+        .collect();
+
+    let mut out = String::from("digraph {\n");
+    for (id, v) in order.iter().enumerate() {
+        let style = if on_path.contains(&id) {
+            ", color=red, penwidth=2"
+        } else {
+            ""
+        };
+        let label = escape_dot(&vertex_label(v));
+        writeln!(out, "    {id} [label=\"{label}\"{style}];").unwrap();
+    }
+    edges.sort_unstable();
+    for (a, b) in edges {
+        let style = if path_edges.contains(&(a, b)) {
+            " [color=red, penwidth=2]"
+        } else {
+            ""
+        };
+        writeln!(out, "    {a} -> {b}{style};").unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Builds the path ending at `tgt` by following `parent` links back to a
+/// vertex with no parent, then reversing.
+///
+/// Shared by the search functions below so path reconstruction lives in one
+/// safe place.
+pub fn build_path<T, P>(tgt: T, mut parent: P) -> Vec<T>
+where
+    T: Clone,
+    P: FnMut(&T) -> Option<T>,
+{
+    let mut path = vec![];
+    let mut cur = Some(tgt);
+    while let Some(v) = cur {
+        cur = parent(&v);
+        path.push(v);
+    }
+    path.reverse();
+    path
+}
+
+fn bfs_tree_until<T, F, I, S>(
+    src: T,
+    mut get_edges: F,
+    mut stop: S,
+) -> FastMap<T, Option<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    S: FnMut(&T) -> bool,
+{
+    let mut parents = FastMap::from_iter([(src.clone(), None)]);
+    let mut queue = VecDeque::from([src]);
+    while let Some(v) = queue.pop_front() {
+        if stop(&v) {
+            break;
+        }
+        for w in get_edges(v.clone()) {
+            if !parents.contains_key(&w) {
+                parents.insert(w.clone(), Some(v.clone()));
+                queue.push_back(w);
+            }
+        }
+    }
+    parents
+}
+
+/// Multi-source BFS: the distance from the nearest of `sources` to every
+/// reachable vertex.
+pub fn bfs_distances<T, F, I>(
+    sources: impl IntoIterator<Item = T>,
+    mut get_edges: F,
+) -> FastMap<T, usize>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut dist = FastMap::default();
+    let mut queue = VecDeque::new();
+    for src in sources {
+        if !dist.contains_key(&src) {
+            dist.insert(src.clone(), 0);
+            queue.push_back(src);
+        }
+    }
+    while let Some(v) = queue.pop_front() {
+        let d = dist[&v];
+        for w in get_edges(v) {
+            if !dist.contains_key(&w) {
+                dist.insert(w.clone(), d + 1);
+                queue.push_back(w);
+            }
+        }
+    }
+    dist
+}
+
+/// Runs a full BFS from `src` and returns the predecessor of every reachable
+/// vertex (`None` for `src` itself).
+pub fn bfs_tree<T, F, I>(src: T, get_edges: F) -> FastMap<T, Option<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    bfs_tree_until(src, get_edges, |_| false)
+}
+
+/// Reconstructs the path from the root of `tree` to `tgt`, if `tgt` was
+/// reached.
+pub fn path_to<T, S>(tgt: &T, tree: &HashMap<T, Option<T>, S>) -> Option<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    tree.contains_key(tgt)
+        .then(|| build_path(tgt.clone(), |v| tree[v].clone()))
+}
+
+/// Shortest path from `src` to `tgt` (inclusive of both), if one exists.
+pub fn shortest_path<T, F, I>(src: T, tgt: &T, get_edges: F) -> Option<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let tree = bfs_tree_until(src, get_edges, |v| v == tgt);
+    path_to(tgt, &tree)
+}
+
+/// Shortest path from any of `sources` to any vertex satisfying `is_tgt`.
+///
+/// Runs a single BFS from a virtual super-source (`None`) whose edges lead
+/// to every source, rather than one search per source.
+pub fn shortest_path_multi<T, F, I, P>(
+    sources: &[T],
+    mut is_tgt: P,
+    mut get_edges: F,
+) -> Option<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    P: FnMut(&T) -> bool,
+{
+    let mut reached = None;
+    let tree = bfs_tree_until(
+        None,
+        |v: Option<T>| -> Vec<Option<T>> {
+            v.map_or_else(
+                || sources.iter().cloned().map(Some).collect(),
+                |v| get_edges(v).into_iter().map(Some).collect(),
+            )
+        },
+        |v| {
+            let hit = v.as_ref().is_some_and(&mut is_tgt);
+            if hit {
+                reached = Some(v.clone());
+            }
+            hit
+        },
+    );
+    let path = path_to(&reached?, &tree)?;
+    Some(path.into_iter().flatten().collect())
+}
+
+struct DijkstraResult<T> {
+    dist: FastMap<T, u64>,
+    parents: FastMap<T, Option<T>>,
+    reached: Option<T>,
+}
+
+fn dijkstra_until<T, F, I, S>(
+    src: T,
+    mut get_edges: F,
+    budget: Option<u64>,
+    mut stop: S,
+) -> DijkstraResult<T>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, u64)>,
+    S: FnMut(&T) -> bool,
+{
+    let mut dist = FastMap::from_iter([(src.clone(), 0)]);
+    let mut parents = FastMap::from_iter([(src.clone(), None)]);
+    let mut heap = MinHeap::new();
+    heap.push(0, src);
+    while let Some((d, v)) = heap.pop_current(|&d, v| d <= dist[v]) {
+        if stop(&v) {
+            return DijkstraResult {
+                dist,
+                parents,
+                reached: Some(v),
+            };
+        }
+        for (w, cost) in get_edges(v.clone()) {
+            let nd = d + cost;
+            if budget.is_some_and(|b| nd > b)
+                || dist.get(&w).is_some_and(|&old| old <= nd)
+            {
+                continue;
+            }
+            dist.insert(w.clone(), nd);
+            parents.insert(w.clone(), Some(v.clone()));
+            heap.push(nd, w);
+        }
+    }
+    DijkstraResult {
+        dist,
+        parents,
+        reached: None,
+    }
+}
+
+/// Cheapest path from `src` to any vertex satisfying `is_tgt`, as
+/// `(cost, path)`.
+pub fn cheapest_to_predicate<T, F, I, P>(
+    src: T,
+    is_tgt: P,
+    get_edges: F,
+) -> Option<(u64, Vec<T>)>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, u64)>,
+    P: FnMut(&T) -> bool,
+{
+    let res = dijkstra_until(src, get_edges, None, is_tgt);
+    let tgt = res.reached?;
+    let path = build_path(tgt.clone(), |v| res.parents[v].clone());
+    Some((res.dist[&tgt], path))
+}
+
+/// Cheapest path from `src` to `tgt` over non-negative edge costs, as
+/// `(cost, path)`.
+pub fn dijkstra_fn<T, F, I>(
+    src: T,
+    tgt: &T,
+    get_edges: F,
+) -> Option<(u64, Vec<T>)>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, u64)>,
+{
+    cheapest_to_predicate(src, |v| v == tgt, get_edges)
+}
+
+/// [`dijkstra_fn`] for edge costs of only 0 or 1, such as free moves ahead
+/// and paid turns.
+///
+/// A deque replaces the heap: cost-0 edges go to the front and cost-1
+/// edges to the back, so vertices still leave it in order of cost, in
+/// O(V + E).
+///
+/// # Panics
+///
+/// Panics if an edge costs more than 1.
+pub fn zero_one_bfs<T, F, I>(
+    src: T,
+    tgt: &T,
+    mut get_edges: F,
+) -> Option<(u64, Vec<T>)>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, u64)>,
+{
+    let mut dist = FastMap::from_iter([(src.clone(), 0)]);
+    let mut parents = FastMap::from_iter([(src.clone(), None)]);
+    let mut deque = VecDeque::from([(0, src)]);
+    while let Some((d, v)) = deque.pop_front() {
+        // A vertex is queued again each time its cost improves.
+        if d > dist[&v] {
+            continue;
+        }
+        if v == *tgt {
+            let path = build_path(v, |v| parents[v].clone());
+            return Some((d, path));
+        }
+        for (w, cost) in get_edges(v.clone()) {
+            assert!(cost <= 1, "zero_one_bfs edge costs {cost}");
+            let nd = d + cost;
+            if dist.get(&w).is_some_and(|&old| old <= nd) {
+                continue;
+            }
+            dist.insert(w.clone(), nd);
+            parents.insert(w.clone(), Some(v.clone()));
+            if cost == 0 {
+                deque.push_front((nd, w));
+            } else {
+                deque.push_back((nd, w));
+            }
+        }
+    }
+    None
+}
+
+/// Every vertex reachable from `src` with total cost at most `budget`,
+/// mapped to its cheapest cost.
+pub fn reachable_within<T, F, I>(
+    src: T,
+    budget: u64,
+    get_edges: F,
+) -> FastMap<T, u64>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, u64)>,
+{
+    dijkstra_until(src, get_edges, Some(budget), |_| false).dist
+}
+
+/// Wraps `get_edges` so neighbors are always visited in sorted order, making
+/// traversals (and which of several equal-cost paths they return)
+/// deterministic.
+pub fn sorted_neighbors<T, F, I>(mut get_edges: F) -> impl FnMut(T) -> Vec<T>
+where
+    T: Ord,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    move |v| {
+        let mut nbrs: Vec<T> = get_edges(v).into_iter().collect();
+        nbrs.sort_unstable();
+        nbrs
+    }
+}
+
+/// BFS from `src` recording every predecessor lying on some shortest path,
+/// stopping once the layer containing `tgt` is complete.
+fn shortest_path_dag<T, F, I>(
+    src: T,
+    tgt: &T,
+    mut get_edges: F,
+) -> FastMap<T, (usize, Vec<T>)>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut dag = FastMap::from_iter([(src.clone(), (0, vec![]))]);
+    let mut layer = vec![src];
+    let mut depth = 0;
+    while !layer.is_empty() && !dag.contains_key(tgt) {
+        depth += 1;
+        let mut next = vec![];
+        for v in layer {
+            for w in get_edges(v.clone()) {
+                match dag.get_mut(&w) {
+                    Some((d, preds)) if *d == depth => preds.push(v.clone()),
+                    Some(_) => {}
+                    None => {
+                        dag.insert(w.clone(), (depth, vec![v.clone()]));
+                        next.push(w);
+                    }
+                }
+            }
+        }
+        layer = next;
+    }
+    dag
+}
+
+/// Every distinct shortest path from `src` to `tgt`.
+pub fn all_shortest_paths<T, F, I>(src: T, tgt: &T, get_edges: F) -> Vec<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let dag = shortest_path_dag(src, tgt, get_edges);
+    if !dag.contains_key(tgt) {
+        return vec![];
+    }
+    let mut out = vec![];
+    let mut stack = vec![vec![tgt.clone()]];
+    while let Some(rev_path) = stack.pop() {
+        let preds = rev_path.last().map_or(&[][..], |v| &dag[v].1);
+        if preds.is_empty() {
+            out.push(rev_path.into_iter().rev().collect());
+            continue;
+        }
+        for p in preds {
+            let mut extended = rev_path.clone();
+            extended.push(p.clone());
+            stack.push(extended);
+        }
+    }
+    out
+}
+
+/// Every vertex lying on at least one shortest path from `src` to `tgt`,
+/// without enumerating the (possibly exponentially many) paths.
+pub fn vertices_on_shortest_paths<T, F, I>(
+    src: T,
+    tgt: &T,
+    get_edges: F,
+) -> FastSet<T>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let dag = shortest_path_dag(src, tgt, get_edges);
+    let mut seen = FastSet::default();
+    if !dag.contains_key(tgt) {
+        return seen;
+    }
+    let mut stack = vec![tgt.clone()];
+    while let Some(v) = stack.pop() {
+        if seen.insert(v.clone()) {
+            stack.extend(dag[&v].1.iter().cloned());
+        }
+    }
+    seen
+}
+
+/// Every simple path from `src` to `tgt` using at most `max_len` edges.
+///
+/// The cap keeps the search finite on dense graphs; the number of paths can
+/// still grow exponentially with it. Parallel edges count once, so each
+/// sequence of vertices is returned only once.
+pub fn all_simple_paths<T, F, I>(
+    src: T,
+    tgt: &T,
+    mut get_edges: F,
+    max_len: usize,
+) -> Vec<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut out = vec![];
+    let mut path = vec![src.clone()];
+    let mut on_path = FastSet::from_iter([src.clone()]);
+    let mut stack = vec![distinct(get_edges(src))];
+    if path[0] == *tgt {
+        return vec![path];
+    }
+    while let Some(frontier) = stack.last_mut() {
+        let Some(w) = frontier.pop() else {
+            stack.pop();
+            if let Some(v) = path.pop() {
+                on_path.remove(&v);
+            }
+            continue;
+        };
+        if on_path.contains(&w) {
+            continue;
+        }
+        if w == *tgt {
+            if path.len() > max_len {
+                continue;
+            }
+            let mut found = path.clone();
+            found.push(w);
+            out.push(found);
+        } else if path.len() < max_len {
+            on_path.insert(w.clone());
+            path.push(w.clone());
+            stack.push(distinct(get_edges(w)));
+        }
+    }
+    out
+}
+
+/// The items of `iter` with repeats dropped, in order of first appearance.
+fn distinct<T, I>(iter: I) -> Vec<T>
+where
+    T: Clone + Eq + Hash,
+    I: IntoIterator<Item = T>,
+{
+    let mut seen = FastSet::default();
+    iter.into_iter()
+        .filter(|w| seen.insert(w.clone()))
+        .collect()
+}
+
+/// The search behind [`dfs_depth_limited`], also returning whether some
+/// vertex was left unexpanded at the limit, so a deeper search could find
+/// more.
+fn dfs_limited<T, F, I, P>(
+    src: T,
+    get_edges: &mut F,
+    limit: usize,
+    is_goal: &mut P,
+    stats: &mut SearchStats,
+) -> (Option<Vec<T>>, bool)
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    P: FnMut(&T) -> bool,
+{
+    if is_goal(&src) {
+        return (Some(vec![src]), false);
+    }
+    if limit == 0 {
+        return (None, true);
+    }
+    let mut cut_off = false;
+    let mut on_path = FastSet::from_iter([src.clone()]);
+    stats.expanded += 1;
+    let mut stack = vec![get_edges(src.clone()).into_iter()];
+    let mut path = vec![src];
+    stats.max_frontier = stats.max_frontier.max(1);
+    while let Some(edges) = stack.last_mut() {
+        let Some(w) = edges.next() else {
+            stack.pop();
+            on_path.remove(&path.pop().unwrap());
+            continue;
+        };
+        stats.generated += 1;
+        if on_path.contains(&w) {
+            continue;
+        }
+        if is_goal(&w) {
+            path.push(w);
+            return (Some(path), cut_off);
+        }
+        if path.len() == limit {
+            cut_off = true;
+            continue;
+        }
+        on_path.insert(w.clone());
+        stats.expanded += 1;
+        stack.push(get_edges(w.clone()).into_iter());
+        path.push(w);
+        stats.max_frontier = stats.max_frontier.max(stack.len());
+    }
+    (None, cut_off)
+}
+
+/// A path from `src` to a vertex satisfying `is_goal` using at most `limit`
+/// edges, found depth-first, with counts of the work done.
+///
+/// Memory grows only with `limit`, however wide the graph. Vertices
+/// already on the current path are skipped, so the path is simple, but
+/// others are re-explored whenever reached again: the search trades time
+/// for memory. `max_frontier` in the stats is the deepest the path got.
+pub fn dfs_depth_limited<T, F, I, P>(
+    src: T,
+    mut get_edges: F,
+    limit: usize,
+    mut is_goal: P,
+) -> (Option<Vec<T>>, SearchStats)
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    P: FnMut(&T) -> bool,
+{
+    let mut stats = SearchStats::default();
+    let (path, _) =
+        dfs_limited(src, &mut get_edges, limit, &mut is_goal, &mut stats);
+    (path, stats)
+}
+
+/// Iterative deepening: [`dfs_depth_limited`] with limits 0, 1, 2, ... up
+/// to `max_depth`, so the path found uses as few edges as possible, as
+/// with BFS, in memory proportional to its length.
+///
+/// The stats add up every pass. The search ends early, without a path, if
+/// a pass explored everything reachable without reaching its limit.
+pub fn iddfs<T, F, I, P>(
+    src: T,
+    mut get_edges: F,
+    max_depth: usize,
+    mut is_goal: P,
+) -> (Option<Vec<T>>, SearchStats)
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    P: FnMut(&T) -> bool,
+{
+    let mut stats = SearchStats::default();
+    for limit in 0..max_depth {
+        let (path, cut_off) = dfs_limited(
+            src.clone(),
+            &mut get_edges,
+            limit,
+            &mut is_goal,
+            &mut stats,
+        );
+        if path.is_some() || !cut_off {
+            return (path, stats);
+        }
+    }
+    let (path, _) =
+        dfs_limited(src, &mut get_edges, max_depth, &mut is_goal, &mut stats);
+    (path, stats)
+}
+
+/// Like [`shortest_path`], but tracks visited vertices in a [`BitSet`] via
+/// `to_index` instead of hashing them. Worthwhile when there are millions
+/// of small states that map densely onto `0..n`.
+pub fn shortest_path_indexed<T, F, I, X>(
+    src: T,
+    tgt: &T,
+    mut get_edges: F,
+    to_index: X,
+) -> Option<Vec<T>>
+where
+    T: Clone + Eq,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    X: Fn(&T) -> usize,
+{
+    let mut visited = BitSet::new();
+    visited.insert(to_index(&src));
+    // BFS queue that is never popped, so parents can be stored as positions.
+    let mut discovered: Vec<(T, Option<usize>)> = vec![(src, None)];
+    let mut head = 0;
+    while head < discovered.len() {
+        let v = discovered[head].0.clone();
+        if v == *tgt {
+            let path = build_path(head, |&i| discovered[i].1);
+            return Some(
+                path.into_iter().map(|i| discovered[i].0.clone()).collect(),
+            );
+        }
+        for w in get_edges(v) {
+            if visited.insert(to_index(&w)) {
+                discovered.push((w, Some(head)));
+            }
+        }
+        head += 1;
+    }
+    None
+}
+
+/// Number of vertices reachable from `src` (including `src` itself) that
+/// satisfy `is_tgt`, tracking visited vertices in a [`BitSet`] via
+/// `to_index`.
+pub fn num_reachable_targets_indexed<T, F, I, X, P>(
+    src: T,
+    mut get_edges: F,
+    to_index: X,
+    mut is_tgt: P,
+) -> usize
+where
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    X: Fn(&T) -> usize,
+    P: FnMut(&T) -> bool,
+{
+    let mut visited = BitSet::new();
+    visited.insert(to_index(&src));
+    let mut queue = VecDeque::from([src]);
+    let mut count = 0;
+    while let Some(v) = queue.pop_front() {
+        if is_tgt(&v) {
+            count += 1;
+        }
+        for w in get_edges(v) {
+            if visited.insert(to_index(&w)) {
+                queue.push_back(w);
+            }
+        }
+    }
+    count
+}
+
+/// The transpose of `adj`: an edge `b -> a` with the same weight for every
+/// edge `a -> b`. Vertices only listed as targets get their own lists, and
+/// each list is in increasing order of source.
+#[must_use]
+pub fn reversed<W: Clone>(adj: &AdjList<W>) -> AdjList<W> {
+    let n = adj
+        .iter()
+        .flatten()
+        .map(|&(b, _)| b + 1)
+        .fold(adj.len(), usize::max);
+    let mut out: AdjList<W> = vec![vec![]; n];
+    for (a, edges) in adj.iter().enumerate() {
+        for (b, w) in edges {
+            out[*b].push((a, w.clone()));
+        }
+    }
+    out
+}
+
+/// The predecessors of every vertex of the closure-defined graph, found by
+/// expanding each vertex of `nodes` once. Vertices with no predecessors
+/// are absent.
+pub fn reverse_index<T, F, I>(
+    nodes: impl IntoIterator<Item = T>,
+    mut get_edges: F,
+) -> FastMap<T, Vec<T>>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut preds: FastMap<T, Vec<T>> = FastMap::default();
+    for v in nodes {
+        for w in get_edges(v.clone()) {
+            preds.entry(w).or_default().push(v.clone());
+        }
+    }
+    preds
+}
+
+/// Every vertex that can reach `tgt`, mapped to its distance to it:
+/// a BFS backwards from the goal over [`reverse_index`].
+///
+/// Only the edges of `nodes` are known, so `nodes` must include every
+/// vertex that might lie on a path to `tgt`. For adjacency lists,
+/// `bfs_distances([tgt], |v| rev[v].iter().map(|&(u, _)| u))` over
+/// [`reversed`] does the same without hashing.
+pub fn reachable_from_reverse<T, F, I>(
+    tgt: T,
+    nodes: impl IntoIterator<Item = T>,
+    get_edges: F,
+) -> FastMap<T, usize>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let preds = reverse_index(nodes, get_edges);
+    bfs_distances([tgt], |v| preds.get(&v).into_iter().flatten().cloned())
+}
+
+/// The strongly connected components of the graph on `0..n`, each listed
+/// once, in reverse topological order: no edge leaves a component for a
+/// later one.
+///
+/// Uses Tarjan's algorithm with an explicit stack, so deep graphs do not
+/// overflow. For adjacency lists, pass `|v| adj[v].iter().map(|&(w, _)| w)`.
+pub fn strongly_connected_components<F, I>(
+    n: usize,
+    mut get_edges: F,
+) -> Vec<Vec<usize>>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    const UNSEEN: usize = usize::MAX;
+    let mut index = vec![UNSEEN; n];
+    let mut low = vec![0; n];
+    let mut on_stack = BitSet::with_len(n);
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_index = 0;
+    for root in 0..n {
+        if index[root] != UNSEEN {
+            continue;
+        }
+        // DFS frames: a vertex and its remaining out-neighbors.
+        let mut frames = vec![(root, get_edges(root).into_iter())];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack.insert(root);
+        while let Some((v, edges)) = frames.last_mut() {
+            let v = *v;
+            if let Some(w) = edges.next() {
+                if index[w] == UNSEEN {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack.insert(w);
+                    frames.push((w, get_edges(w).into_iter()));
+                } else if on_stack.contains(w) {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                let mut component = vec![];
+                while let Some(w) = stack.pop() {
+                    on_stack.remove(w);
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// A closure-defined graph made concrete by [`materialize`], for the
+/// algorithms that need every vertex numbered up front, such as
+/// [`strongly_connected_components`].
+///
+/// Vertices are numbered `0..len()` in the order found, the source first.
+#[derive(Debug, Clone)]
+pub struct Materialized<T, W> {
+    pub nodes: Vec<T>,
+    pub ids: FastMap<T, usize>,
+    /// The edges of each vertex by id, in the order `get_edges` gave them.
+    pub adj: AdjList<W>,
+}
+
+impl<T: Eq + Hash, W> Materialized<T, W> {
+    #[must_use]
+    pub fn id(&self, v: &T) -> Option<usize> {
+        self.ids.get(v).copied()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `id` is not a vertex.
+    #[must_use]
+    pub fn node(&self, id: usize) -> &T {
+        &self.nodes[id]
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// [`materialize`] found more than `max_nodes` vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeBudgetExceeded {
+    pub max_nodes: usize,
+}
+
+impl fmt::Display for NodeBudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "graph has more than {} vertices", self.max_nodes)
+    }
+}
+
+impl std::error::Error for NodeBudgetExceeded {}
+
+/// Explores the closure-defined graph from `src` breadth-first and builds
+/// its adjacency lists over dense ids.
+///
+/// Edges carry a weight, which can be `()` for an unweighted graph:
+///
+/// ```ignore
+/// let g = materialize(start, |v| nbrs(v).map(|w| (w, ())), 100_000)?;
+/// let sccs = strongly_connected_components(g.len(), |v| {
+///     g.adj[v].iter().map(|&(w, ())| w)
+/// });
+/// ```
+///
+/// # Errors
+///
+/// Stops as soon as a vertex beyond the first `max_nodes` is found, since
+/// an implicit graph may be far larger than intended, or infinite.
+pub fn materialize<T, W, F, I>(
+    src: T,
+    mut get_edges: F,
+    max_nodes: usize,
+) -> Result<Materialized<T, W>, NodeBudgetExceeded>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, W)>,
+{
+    let exceeded = NodeBudgetExceeded { max_nodes };
+    if max_nodes == 0 {
+        return Err(exceeded);
+    }
+    let mut graph = Materialized {
+        nodes: vec![src.clone()],
+        ids: FastMap::from_iter([(src, 0)]),
+        adj: vec![],
+    };
+    // The nodes not yet expanded are exactly those past the adjacency
+    // lists, so the node list doubles as the BFS queue.
+    while graph.adj.len() < graph.nodes.len() {
+        let v = graph.nodes[graph.adj.len()].clone();
+        let mut edges = vec![];
+        for (w, weight) in get_edges(v) {
+            let id = if let Some(&id) = graph.ids.get(&w) {
+                id
+            } else {
+                if graph.nodes.len() == max_nodes {
+                    return Err(exceeded);
+                }
+                let id = graph.nodes.len();
+                graph.ids.insert(w.clone(), id);
+                graph.nodes.push(w);
+                id
+            };
+            edges.push((id, weight));
+        }
+        graph.adj.push(edges);
+    }
+    Ok(graph)
+}
+
+/// Memoizes an expensive `get_edges`, so that vertices revisited within a
+/// search, or across several searches, are expanded only once.
+///
+/// `E` is the edge item: `T` for unweighted searches or `(T, u64)` for
+/// weighted ones. Pass [`edges`](Self::edges) wherever a search takes
+/// `get_edges`:
+///
+/// ```ignore
+/// let mut cached = CachedEdges::new(|v| expensive_neighbors(v));
+/// let a = shortest_path(src, &tgt, cached.edges());
+/// let b = bfs_distances([src], cached.edges());
+/// eprintln!("hit rate {:.1}%", 100.0 * cached.stats().hit_rate());
+/// ```
+pub struct CachedEdges<T, E, F> {
+    get_edges: F,
+    cache: FastMap<T, Vec<E>>,
+    stats: CacheStats,
+}
+
+/// Lookups served from a [`CachedEdges`] cache versus computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// The fraction of lookups that were hits, or 0 before any lookup.
+    // Lookup counts stay far below 2^52.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl<T, E, F> CachedEdges<T, E, F>
+where
+    T: Clone + Eq + Hash,
+    E: Clone,
+{
+    pub fn new(get_edges: F) -> Self {
+        Self {
+            get_edges,
+            cache: FastMap::default(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// The edges of `v`, computed on the first request only.
+    pub fn get<I>(&mut self, v: T) -> Vec<E>
+    where
+        F: FnMut(T) -> I,
+        I: IntoIterator<Item = E>,
+    {
+        if let Some(edges) = self.cache.get(&v) {
+            self.stats.hits += 1;
+            return edges.clone();
+        }
+        self.stats.misses += 1;
+        let edges: Vec<E> = (self.get_edges)(v.clone()).into_iter().collect();
+        self.cache.insert(v, edges.clone());
+        edges
+    }
+
+    /// A `get_edges` closure reading through the cache.
+    pub fn edges<I>(&mut self) -> impl FnMut(T) -> Vec<E> + '_
+    where
+        F: FnMut(T) -> I,
+        I: IntoIterator<Item = E>,
+    {
+        move |v| self.get(v)
+    }
+
+    #[must_use]
+    pub const fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Number of vertices whose edges are cached.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Drops every cached edge list and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = CacheStats::default();
+    }
+}
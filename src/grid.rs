@@ -0,0 +1,1088 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::BuildHasher;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::direction::{Coords, DirSet, Direction, ICoords, glyph_connections};
+use crate::geometry::lattice_points;
+use crate::graph::{bfs_distances, cheapest_to_predicate};
+use crate::hashing::FastSet;
+use crate::neighborhood::Neighborhood;
+use crate::utils::Canonicalize;
+
+/// A dense, rectangular, row-major grid indexed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+/// A rectangle of coordinates, from `min` up to but excluding `max` on each
+/// axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bounds {
+    pub min: Coords,
+    pub max: Coords,
+}
+
+impl Bounds {
+    /// The coordinates of a `height` by `width` grid.
+    #[must_use]
+    pub const fn new(height: usize, width: usize) -> Self {
+        Self {
+            min: Coords(0, 0),
+            max: Coords(height, width),
+        }
+    }
+
+    /// The rectangle with opposite corners `a` and `b`, inclusive.
+    #[must_use]
+    pub fn from_corners(a: Coords, b: Coords) -> Self {
+        Self {
+            min: Coords(a.0.min(b.0), a.1.min(b.1)),
+            max: Coords(a.0.max(b.0) + 1, a.1.max(b.1) + 1),
+        }
+    }
+
+    /// The smallest rectangle containing every point, or `None` if there
+    /// are none.
+    pub fn enclosing(points: impl IntoIterator<Item = Coords>) -> Option<Self> {
+        points.into_iter().fold(None, |acc: Option<Self>, p| {
+            Some(acc.map_or_else(
+                || Self::from_corners(p, p),
+                |b| Self {
+                    min: Coords(b.min.0.min(p.0), b.min.1.min(p.1)),
+                    max: Coords(b.max.0.max(p.0 + 1), b.max.1.max(p.1 + 1)),
+                },
+            ))
+        })
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.max.0.saturating_sub(self.min.0)
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.max.1.saturating_sub(self.min.1)
+    }
+
+    /// Number of coordinates inside.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.height() * self.width()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub const fn contains(&self, Coords(r, c): Coords) -> bool {
+        self.min.0 <= r && r < self.max.0 && self.min.1 <= c && c < self.max.1
+    }
+
+    /// The nearest coordinates inside to `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds are empty.
+    #[must_use]
+    pub fn clamp(&self, Coords(r, c): Coords) -> Coords {
+        assert!(!self.is_empty(), "cannot clamp to empty bounds");
+        Coords(
+            r.clamp(self.min.0, self.max.0 - 1),
+            c.clamp(self.min.1, self.max.1 - 1),
+        )
+    }
+
+    /// Every coordinate inside, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = Coords> + use<> {
+        let Self { min, max } = *self;
+        (min.0..max.0)
+            .flat_map(move |r| (min.1..max.1).map(move |c| Coords(r, c)))
+    }
+
+    /// The coordinates on the border, each once, clockwise from `min`.
+    pub fn edge_iter(&self) -> impl Iterator<Item = Coords> + use<> {
+        let (top, left) = (self.min.0, self.min.1);
+        // With empty bounds every range below is empty too.
+        let (bottom, right) = if self.is_empty() {
+            (top, left)
+        } else {
+            (self.max.0 - 1, self.max.1 - 1)
+        };
+        let cols = if self.is_empty() {
+            left..left
+        } else {
+            left..right + 1
+        };
+        let across = cols.map(move |c| Coords(top, c));
+        let down = (top + 1..=bottom).map(move |r| Coords(r, right));
+        let back = (left..right)
+            .rev()
+            .filter(move |_| bottom > top)
+            .map(move |c| Coords(bottom, c));
+        let up = (top + 1..bottom)
+            .rev()
+            .filter(move |_| right > left)
+            .map(move |r| Coords(r, left));
+        across.chain(down).chain(back).chain(up)
+    }
+}
+
+impl<T> Grid<T> {
+    #[must_use]
+    pub fn new(height: usize, width: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; height * width],
+            height,
+            width,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    #[must_use]
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "ragged grid rows"
+        );
+        Self {
+            cells: rows.into_iter().flatten().collect(),
+            height,
+            width,
+        }
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn bounds(&self) -> Bounds {
+        Bounds::new(self.height, self.width)
+    }
+
+    #[must_use]
+    pub const fn in_bounds(&self, Coords(r, c): Coords) -> bool {
+        r < self.height && c < self.width
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Coords) -> Option<&T> {
+        if self.in_bounds(pos) {
+            Some(&self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Coords) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coords, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Coords(i / width, i % width), cell))
+    }
+
+    /// Finds the coordinates of the first cell (in reading order) matching
+    /// `pred`.
+    pub fn position<F>(&self, mut pred: F) -> Option<Coords>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find_map(|(pos, cell)| pred(cell).then_some(pos))
+    }
+
+    #[must_use]
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    /// The grid mirrored across its main diagonal.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|c| (0..self.height).map(move |r| (r, c)))
+            .map(|(r, c)| self.cells[r * self.width + c].clone())
+            .collect();
+        Self {
+            cells,
+            height: self.width,
+            width: self.height,
+        }
+    }
+
+    /// The grid with each row reversed.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut flipped = self.clone();
+        for row in flipped.cells.chunks_exact_mut(self.width.max(1)) {
+            row.reverse();
+        }
+        flipped
+    }
+
+    /// All eight rotations and reflections of the grid, starting with the
+    /// grid itself (some may coincide).
+    #[must_use]
+    pub fn symmetries(&self) -> Vec<Self>
+    where
+        T: Clone,
+    {
+        let mut out = Vec::with_capacity(8);
+        let mut cur = self.clone();
+        for _ in 0..4 {
+            out.push(cur.clone());
+            out.push(cur.flip_horizontal());
+            // Transposing then flipping rotates a quarter turn clockwise.
+            cur = cur.transpose().flip_horizontal();
+        }
+        out
+    }
+
+    /// In-bounds neighbors of `pos` under `nbhd`.
+    pub fn neighbors(
+        &self,
+        pos: Coords,
+        nbhd: Neighborhood,
+    ) -> impl Iterator<Item = Coords> + use<T> {
+        let (height, width) = (self.height, self.width);
+        nbhd.of(pos)
+            .filter(move |&Coords(r, c)| r < height && c < width)
+    }
+
+    /// The directions, of all eight, in which `pos` has an in-bounds
+    /// neighbor satisfying `pred`.
+    pub fn neighbor_dirs<P>(&self, pos: Coords, mut pred: P) -> DirSet
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Direction::ALL
+            .into_iter()
+            .filter(|&dir| {
+                pos.step(dir)
+                    .and_then(|nbr| self.get(nbr))
+                    .is_some_and(&mut pred)
+            })
+            .collect()
+    }
+
+    /// Precomputes, for every cell, the directions of its in-bounds
+    /// neighbors for which `filter(cell, neighbor)` holds.
+    #[must_use]
+    pub fn neighbor_table<F>(&self, filter: F) -> NbrTable
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let dirs = self
+            .iter()
+            .map(|(pos, here)| {
+                self.neighbor_dirs(pos, |there| filter(here, there))
+            })
+            .collect();
+        NbrTable {
+            dirs: Grid {
+                cells: dirs,
+                height: self.height,
+                width: self.width,
+            },
+        }
+    }
+
+    /// In-bounds neighbors of `pos` in the four cardinal directions.
+    pub fn cardinal_neighbors(
+        &self,
+        pos: Coords,
+    ) -> impl Iterator<Item = Coords> + use<T> {
+        self.neighbors(pos, Neighborhood::VonNeumann)
+    }
+}
+
+/// The cells met walking from `from` in `dir`, excluding `from` itself.
+/// Ends only where a coordinate would go negative; use [`Grid::ray`] to
+/// stay on a grid.
+pub fn ray(from: Coords, dir: Direction) -> impl Iterator<Item = Coords> {
+    std::iter::successors(from.step(dir), move |pos| pos.step(dir))
+}
+
+impl<T> Grid<T> {
+    /// The in-bounds cells met walking from `from` in `dir`, excluding
+    /// `from` itself.
+    pub fn ray(
+        &self,
+        from: Coords,
+        dir: Direction,
+    ) -> impl Iterator<Item = Coords> + use<T> {
+        let bounds = self.bounds();
+        ray(from, dir).take_while(move |&pos| bounds.contains(pos))
+    }
+
+    /// The first cell walking from `from` in `dir` that satisfies
+    /// `is_blocker`, such as the first seat visible in that direction.
+    pub fn first_hit<P>(
+        &self,
+        from: Coords,
+        dir: Direction,
+        mut is_blocker: P,
+    ) -> Option<Coords>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.ray(from, dir).find(|&pos| is_blocker(&self[pos]))
+    }
+
+    /// Whether nothing satisfying `is_blocker` sits exactly on the line
+    /// between `a` and `b`, at any angle. The endpoints themselves are not
+    /// checked.
+    pub fn line_of_sight<P>(
+        &self,
+        a: Coords,
+        b: Coords,
+        mut is_blocker: P,
+    ) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (Ok(ia), Ok(ib)) = (ICoords::try_from(a), ICoords::try_from(b))
+        else {
+            return false;
+        };
+        lattice_points(ia, ib)
+            .filter(|&p| p != ia && p != ib)
+            .filter_map(|p| Coords::try_from(p).ok())
+            .all(|pos| !self.get(pos).is_some_and(&mut is_blocker))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Multi-source BFS distance (in cardinal steps) from the nearest of
+    /// `sources` to every cell, moving only through cells where `passable`
+    /// holds. Unreachable cells are `None`.
+    pub fn distance_field<P>(
+        &self,
+        sources: &[Coords],
+        passable: P,
+    ) -> Grid<Option<usize>>
+    where
+        P: Fn(&T) -> bool,
+    {
+        let dist = bfs_distances(sources.iter().copied(), |pos| {
+            self.cardinal_neighbors(pos)
+                .filter(|&nbr| passable(&self[nbr]))
+        });
+        let mut field = Grid::new(self.height, self.width, None);
+        for (pos, d) in dist {
+            field[pos] = Some(d);
+        }
+        field
+    }
+}
+
+/// Which of each cell's eight neighbors pass a filter, from
+/// [`Grid::neighbor_table`]. Cells outside the grid have none.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NbrTable {
+    dirs: Grid<DirSet>,
+}
+
+impl NbrTable {
+    /// The directions of the accepted neighbors of `pos`.
+    #[must_use]
+    pub fn dirs(&self, pos: Coords) -> DirSet {
+        self.dirs.get(pos).copied().unwrap_or(DirSet::EMPTY)
+    }
+
+    /// The neighbor of `pos` in `dir`, if it was accepted.
+    #[must_use]
+    pub fn get(&self, pos: Coords, dir: Direction) -> Option<Coords> {
+        self.dirs(pos)
+            .contains(dir)
+            .then(|| pos.step(dir))
+            .flatten()
+    }
+
+    /// The accepted neighbors of `pos`, clockwise from north.
+    pub fn neighbors(&self, pos: Coords) -> impl Iterator<Item = Coords> {
+        self.dirs(pos).iter().filter_map(move |dir| pos.step(dir))
+    }
+
+    /// Number of accepted neighbors of `pos` in the cardinal directions.
+    #[must_use]
+    pub fn cardinal_count(&self, pos: Coords) -> usize {
+        (self.dirs(pos) & DirSet::CARDINALS).len()
+    }
+
+    /// The cardinal directions in which `pos` has no accepted neighbor,
+    /// i.e. the sides of `pos` on the edge of its region.
+    #[must_use]
+    pub fn missing_cardinals(&self, pos: Coords) -> DirSet {
+        DirSet::CARDINALS.difference(self.dirs(pos))
+    }
+}
+
+/// A portal that also moves between recursion levels, as in donut mazes
+/// where inner portals lead one level deeper and outer ones one level out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LevelPortal {
+    pub to: Coords,
+    pub cost: u64,
+    /// Added to the depth when the portal is used.
+    pub depth_change: i64,
+}
+
+impl<T> Grid<T> {
+    /// The neighbor one step from `pos` in `dir`, if the two cells connect:
+    /// `connections` of `pos` includes `dir` and that of the neighbor
+    /// includes the opposite direction.
+    pub fn connected_neighbor_by<F>(
+        &self,
+        pos: Coords,
+        dir: Direction,
+        connections: F,
+    ) -> Option<Coords>
+    where
+        F: Fn(&T) -> Option<DirSet>,
+    {
+        let nbr = pos.step(dir)?;
+        let here = connections(self.get(pos)?)?;
+        let there = connections(self.get(nbr)?)?;
+        (here.contains(dir) && there.contains(dir.opposite())).then_some(nbr)
+    }
+}
+
+impl Grid<char> {
+    /// The cardinal directions the track glyph at `pos` connects to, as
+    /// [`glyph_connections`] gives them, except that the curves `/` and `\`
+    /// are resolved from their neighbors.
+    ///
+    /// A `/` joins either south and east or north and west, and a `\`
+    /// south and west or north and east: whichever pair has a non-curve
+    /// neighbor connecting back is taken. A curve touching only other
+    /// curves is `None`.
+    #[must_use]
+    pub fn track_connections(&self, pos: Coords) -> Option<DirSet> {
+        use Direction::{E, N, S, W};
+        let glyph = *self.get(pos)?;
+        let pairs = match glyph {
+            '/' => [[S, E], [N, W]],
+            '\\' => [[S, W], [N, E]],
+            _ => return glyph_connections(glyph),
+        };
+        let connects_back = |dir: Direction| {
+            pos.step(dir)
+                .and_then(|nbr| self.get(nbr))
+                .and_then(|&c| glyph_connections(c))
+                .is_some_and(|set| set.contains(dir.opposite()))
+        };
+        pairs
+            .into_iter()
+            .find(|pair| pair.iter().any(|&dir| connects_back(dir)))
+            .map(|pair| pair.into_iter().collect())
+    }
+
+    /// The neighbor one step from `pos` in `dir`, if the two cells connect
+    /// by the standard pipe and track glyphs of
+    /// [`track_connections`](Self::track_connections).
+    #[must_use]
+    pub fn connected_neighbor(
+        &self,
+        pos: Coords,
+        dir: Direction,
+    ) -> Option<Coords> {
+        let nbr = pos.step(dir)?;
+        let here = self.track_connections(pos)?;
+        let there = self.track_connections(nbr)?;
+        (here.contains(dir) && there.contains(dir.opposite())).then_some(nbr)
+    }
+
+    /// The cardinal directions whose neighbor connects back to `pos`,
+    /// whatever `pos` holds. This recovers the pipe hidden under a pipe
+    /// maze's `S`.
+    #[must_use]
+    pub fn connections_into(&self, pos: Coords) -> DirSet {
+        Direction::CARDINALS
+            .into_iter()
+            .filter(|&dir| {
+                pos.step(dir)
+                    .and_then(|nbr| self.track_connections(nbr))
+                    .is_some_and(|set| set.contains(dir.opposite()))
+            })
+            .collect()
+    }
+}
+
+/// A position and recursion depth, for
+/// [`recursive_portal_edges`](Grid::recursive_portal_edges).
+pub type LevelState = (Coords, usize);
+
+impl<T> Grid<T> {
+    /// A weighted `get_edges` for the closure-based searches in `graph`
+    /// (e.g. [`dijkstra_fn`](crate::graph::dijkstra_fn)): a step costing 1
+    /// to each passable cardinal neighbor, plus the portal edges leaving
+    /// the cell.
+    ///
+    /// Portal destinations are taken as given, whether or not they are
+    /// passable.
+    pub fn portal_edges<'a, P, S>(
+        &'a self,
+        passable: P,
+        portals: &'a HashMap<Coords, Vec<(Coords, u64)>, S>,
+    ) -> impl Fn(Coords) -> Vec<(Coords, u64)> + 'a
+    where
+        P: Fn(&T) -> bool + 'a,
+        S: BuildHasher,
+    {
+        move |pos| {
+            let mut out: Vec<(Coords, u64)> = self
+                .cardinal_neighbors(pos)
+                .filter(|&nbr| passable(&self[nbr]))
+                .map(|nbr| (nbr, 1))
+                .collect();
+            if let Some(jumps) = portals.get(&pos) {
+                out.extend_from_slice(jumps);
+            }
+            out
+        }
+    }
+
+    /// Like [`portal_edges`](Self::portal_edges), over `(pos, depth)`
+    /// states: steps keep the depth, and each portal changes it by its
+    /// `depth_change`.
+    ///
+    /// Portals that would lead above depth 0 or deeper than `max_depth` are
+    /// closed. The cap keeps the state space finite, so a search for an
+    /// unreachable goal still ends.
+    pub fn recursive_portal_edges<'a, P, S>(
+        &'a self,
+        passable: P,
+        portals: &'a HashMap<Coords, Vec<LevelPortal>, S>,
+        max_depth: usize,
+    ) -> impl Fn(LevelState) -> Vec<(LevelState, u64)> + 'a
+    where
+        P: Fn(&T) -> bool + 'a,
+        S: BuildHasher,
+    {
+        move |(pos, depth)| {
+            let mut out: Vec<(LevelState, u64)> = self
+                .cardinal_neighbors(pos)
+                .filter(|&nbr| passable(&self[nbr]))
+                .map(|nbr| ((nbr, depth), 1))
+                .collect();
+            for portal in portals.get(&pos).into_iter().flatten() {
+                let next = depth.checked_add_signed(
+                    isize::try_from(portal.depth_change).unwrap_or(isize::MAX),
+                );
+                if let Some(next) = next.filter(|&d| d <= max_depth) {
+                    out.push(((portal.to, next), portal.cost));
+                }
+            }
+            out
+        }
+    }
+}
+
+/// A search state for direction-aware grid pathfinding: where we are,
+/// which way we last moved, and how many consecutive steps we have taken
+/// that way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectedState {
+    pub pos: Coords,
+    pub dir: Direction,
+    pub run: usize,
+}
+
+/// Cheapest path over `grid` from `start` (initially facing any of
+/// `start_dirs`, with a run of zero) to a state satisfying `is_goal`, as
+/// `(cost, states)`.
+///
+/// From each state the walker may continue straight or turn 90 degrees,
+/// never reverse. `allowed(state, dir)` decides whether moving in `dir`
+/// is permitted (e.g. run-length limits), and `cost(pos, cell)` gives the
+/// price of entering a cell, or `None` if it is impassable.
+///
+/// ```ignore
+/// // Ultra crucible: 4..=10 steps before turning or stopping.
+/// grid_dijkstra_directed(
+///     &grid,
+///     Coords(0, 0),
+///     &[Direction::E, Direction::S],
+///     |s, d| if d == s.dir { s.run < 10 } else { s.run == 0 || s.run >= 4 },
+///     |_, &heat| Some(heat),
+///     |s| s.pos == end && s.run >= 4,
+/// )
+/// ```
+pub fn grid_dijkstra_directed<T, A, C, G>(
+    grid: &Grid<T>,
+    start: Coords,
+    start_dirs: &[Direction],
+    allowed: A,
+    cost: C,
+    mut is_goal: G,
+) -> Option<(u64, Vec<DirectedState>)>
+where
+    A: Fn(&DirectedState, Direction) -> bool,
+    C: Fn(Coords, &T) -> Option<u64>,
+    G: FnMut(&DirectedState) -> bool,
+{
+    // `None` is a virtual source with free edges to every starting heading.
+    let moves = |state: Option<DirectedState>| -> Vec<_> {
+        let Some(s) = state else {
+            return start_dirs
+                .iter()
+                .map(|&dir| {
+                    (
+                        Some(DirectedState {
+                            pos: start,
+                            dir,
+                            run: 0,
+                        }),
+                        0,
+                    )
+                })
+                .collect();
+        };
+        [s.dir, s.dir.turn_left(), s.dir.turn_right()]
+            .into_iter()
+            .filter(|&dir| allowed(&s, dir))
+            .filter_map(|dir| {
+                let pos = s.pos.step(dir)?;
+                let price = cost(pos, grid.get(pos)?)?;
+                let run = if dir == s.dir { s.run + 1 } else { 1 };
+                Some((Some(DirectedState { pos, dir, run }), price))
+            })
+            .collect()
+    };
+    let (total, path) = cheapest_to_predicate(
+        None,
+        |s| s.as_ref().is_some_and(&mut is_goal),
+        moves,
+    )?;
+    Some((total, path.into_iter().flatten().collect()))
+}
+
+/// The fewest cardinal steps from `start` that visit every one of `pois`.
+///
+/// Moves only through cells where `passable` holds, and returns `(steps,
+/// order)` with the points in the order visited, or `None` if some point
+/// cannot be reached.
+///
+/// Computes the distances between every pair of points with a BFS from
+/// each, then runs Dijkstra over (current point, visited set) states, so
+/// it suits up to a couple of dozen points.
+///
+/// # Panics
+///
+/// Panics if there are more than 63 points.
+pub fn collect_all_shortest<T, P>(
+    grid: &Grid<T>,
+    start: Coords,
+    pois: &[Coords],
+    passable: P,
+) -> Option<(u64, Vec<Coords>)>
+where
+    P: Fn(&T) -> bool,
+{
+    assert!(pois.len() < 64, "at most 63 points of interest");
+    let all: u64 = (1 << pois.len()) - 1;
+    // dist[i][j] from point i to POI j, where point 0 is `start` and point
+    // j + 1 is POI j.
+    let dist: Vec<Vec<Option<usize>>> = std::iter::once(start)
+        .chain(pois.iter().copied())
+        .map(|from| {
+            let field = grid.distance_field(&[from], &passable);
+            pois.iter().map(|&poi| field[poi]).collect()
+        })
+        .collect();
+    let moves = |(at, visited): (usize, u64)| {
+        let dist = &dist[at];
+        (0..pois.len())
+            .filter(move |&j| visited & (1 << j) == 0)
+            .filter_map(move |j| {
+                let d = u64::try_from(dist[j]?).ok()?;
+                Some(((j + 1, visited | (1 << j)), d))
+            })
+    };
+    let (steps, path) =
+        cheapest_to_predicate((0, 0), |&(_, visited)| visited == all, moves)?;
+    let order = path.iter().skip(1).map(|&(at, _)| pois[at - 1]).collect();
+    Some((steps, order))
+}
+
+/// The fewest minutes to get from `start` to `goal` on a map that changes
+/// over time and repeats every `period` minutes, such as a valley of
+/// blizzards.
+///
+/// Each minute the walker takes one cardinal step or waits in place, and
+/// may only end the minute on a cell where `passable_at(cell, t)` holds.
+/// Time is folded for it, so `t` is always in `0..period`: precompute the
+/// map for each phase (the period is usually the [`lcm`](crate::math::lcm)
+/// of the movers' cycle lengths) and index it by `t`. `passable_at` must
+/// also reject cells outside the map.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+pub fn time_expanded_shortest_path<P>(
+    start: Coords,
+    goal: Coords,
+    period: usize,
+    passable_at: P,
+) -> Option<usize>
+where
+    P: FnMut(Coords, usize) -> bool,
+{
+    time_expanded_shortest_path_from(start, goal, 0, period, passable_at)
+}
+
+/// [`time_expanded_shortest_path`] leaving `start` at minute `depart`,
+/// for trips that continue an earlier one. Returns the minute of arrival
+/// rather than the duration.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+pub fn time_expanded_shortest_path_from<P>(
+    start: Coords,
+    goal: Coords,
+    depart: usize,
+    period: usize,
+    mut passable_at: P,
+) -> Option<usize>
+where
+    P: FnMut(Coords, usize) -> bool,
+{
+    assert!(period > 0, "the period must be positive");
+    // Two visits to a cell at the same phase have the same future, so
+    // states are deduplicated on the folded time.
+    let mut seen = FastSet::from_iter([(start, depart % period)]);
+    let mut queue = VecDeque::from([(start, depart)]);
+    while let Some((pos, t)) = queue.pop_front() {
+        if pos == goal {
+            return Some(t);
+        }
+        let phase = (t + 1) % period;
+        let moves = Direction::CARDINALS.iter().filter_map(|&d| pos.step(d));
+        for next in std::iter::once(pos).chain(moves) {
+            if passable_at(next, phase) && seen.insert((next, phase)) {
+                queue.push_back((next, t + 1));
+            }
+        }
+    }
+    None
+}
+
+/// A summed-area table answering rectangle-sum queries in constant time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrefixSums2D {
+    /// `sums[(r, c)]` is the sum of all cells above and left of `(r, c)`,
+    /// exclusive, so it has one more row and column than the source grid.
+    sums: Grid<i64>,
+}
+
+impl PrefixSums2D {
+    /// Sum of the cells in the rectangle with corners `lo` and `hi`,
+    /// inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hi` is outside the source grid.
+    #[must_use]
+    pub fn rect_sum(&self, lo: Coords, hi: Coords) -> i64 {
+        if lo.0 > hi.0 || lo.1 > hi.1 {
+            return 0;
+        }
+        let (r0, c0, r1, c1) = (lo.0, lo.1, hi.0 + 1, hi.1 + 1);
+        self.sums[Coords(r1, c1)]
+            - self.sums[Coords(r0, c1)]
+            - self.sums[Coords(r1, c0)]
+            + self.sums[Coords(r0, c0)]
+    }
+}
+
+impl Grid<i64> {
+    #[must_use]
+    pub fn prefix_sums(&self) -> PrefixSums2D {
+        let mut sums = Self::new(self.height + 1, self.width + 1, 0);
+        for (Coords(r, c), &v) in self.iter() {
+            sums[Coords(r + 1, c + 1)] =
+                v + sums[Coords(r, c + 1)] + sums[Coords(r + 1, c)]
+                    - sums[Coords(r, c)];
+        }
+        PrefixSums2D { sums }
+    }
+}
+
+/// The least of the grid's eight symmetries, ordered by dimensions and then
+/// cells, so rotated or reflected copies canonicalize identically.
+impl<T: Clone + Ord> Canonicalize for Grid<T> {
+    fn canonicalize(&self) -> Self {
+        self.symmetries()
+            .into_iter()
+            .min_by(|a, b| {
+                (a.height, a.width, &a.cells)
+                    .cmp(&(b.height, b.width, &b.cells))
+            })
+            .expect("a grid has eight symmetries")
+    }
+}
+
+impl<T> Index<Coords> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Coords) -> &T {
+        self.get(pos).expect("grid index out of bounds")
+    }
+}
+
+impl<T> IndexMut<Coords> for Grid<T> {
+    fn index_mut(&mut self, pos: Coords) -> &mut T {
+        self.get_mut(pos).expect("grid index out of bounds")
+    }
+}
+
+/// Cell types with a standard one-character spelling, so a grid of them can
+/// be parsed with [`str::parse`] or [`GridParser::parse`].
+pub trait FromCell: Sized {
+    fn from_cell(c: char) -> Option<Self>;
+}
+
+impl FromCell for char {
+    fn from_cell(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// `#` is `true` and `.` is `false`.
+impl FromCell for bool {
+    fn from_cell(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A single decimal digit.
+impl FromCell for u8 {
+    fn from_cell(c: char) -> Option<Self> {
+        c.to_digit(10).and_then(|d| Self::try_from(d).ok())
+    }
+}
+
+/// How [`GridParser`] handles lines of different lengths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Ragged<T> {
+    /// Every line must be as long as the first.
+    Reject,
+    /// Short lines are extended to the longest with this value, as for
+    /// inputs whose trailing spaces were trimmed.
+    Fill(T),
+    /// Every line is cut to the shortest.
+    Truncate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    /// A character the cell mapping rejected. Positions count from 0.
+    BadCell { row: usize, col: usize, c: char },
+    /// A line whose length differs from the grid's width.
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadCell { row, col, c } => write!(
+                f,
+                "unexpected {c:?} at line {}, column {}",
+                row + 1,
+                col + 1
+            ),
+            Self::Ragged { row, len, width } => {
+                write!(f, "line {} has {len} cells, expected {width}", row + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Options for reading a block of text into a [`Grid`], one cell per
+/// character.
+///
+/// ```ignore
+/// // Walls all round, so neighbor lookups never leave the grid.
+/// let grid = GridParser::new()
+///     .border(Tile::Wall)
+///     .ragged(Ragged::Fill(Tile::Wall))
+///     .parse_with(input, Tile::from_char)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridParser<T> {
+    border: Option<T>,
+    ragged: Ragged<T>,
+}
+
+impl<T> Default for GridParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> GridParser<T> {
+    /// No border, and ragged lines are rejected.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            border: None,
+            ragged: Ragged::Reject,
+        }
+    }
+
+    /// Surrounds the grid with a one-cell border of `sentinel`. Parsed
+    /// cells then sit one row and column further in, at `(row + 1, col +
+    /// 1)`.
+    #[must_use]
+    pub fn border(mut self, sentinel: T) -> Self {
+        self.border = Some(sentinel);
+        self
+    }
+
+    #[must_use]
+    pub fn ragged(mut self, policy: Ragged<T>) -> Self {
+        self.ragged = policy;
+        self
+    }
+
+    /// Parses each line of `input` into a row, mapping each character with
+    /// `cell`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first character `cell` rejects, or with
+    /// [`Ragged::Reject`] the first line whose length differs from the
+    /// first line's.
+    pub fn parse_with<F>(
+        &self,
+        input: &str,
+        mut cell: F,
+    ) -> Result<Grid<T>, GridParseError>
+    where
+        T: Clone,
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines: Vec<&str> = input.lines().collect();
+        let lens = lines.iter().map(|line| line.chars().count());
+        let width = match self.ragged {
+            Ragged::Reject => lens.clone().next().unwrap_or(0),
+            Ragged::Fill(_) => lens.clone().max().unwrap_or(0),
+            Ragged::Truncate => lens.clone().min().unwrap_or(0),
+        };
+        if matches!(self.ragged, Ragged::Reject)
+            && let Some((row, len)) =
+                lens.enumerate().find(|&(_, len)| len != width)
+        {
+            return Err(GridParseError::Ragged { row, len, width });
+        }
+
+        let pad = usize::from(self.border.is_some());
+        let mut rows = Vec::with_capacity(lines.len() + 2 * pad);
+        let border_row = || self.border.iter().cloned().cycle();
+        if pad == 1 {
+            rows.push(border_row().take(width + 2).collect());
+        }
+        for (r, line) in lines.iter().enumerate() {
+            let mut row = Vec::with_capacity(width + 2 * pad);
+            row.extend(border_row().take(pad));
+            for (c, ch) in line.chars().take(width).enumerate() {
+                let value = cell(ch).ok_or(GridParseError::BadCell {
+                    row: r,
+                    col: c,
+                    c: ch,
+                })?;
+                row.push(value);
+            }
+            if let Ragged::Fill(fill) = &self.ragged {
+                row.resize(width + pad, fill.clone());
+            }
+            row.extend(border_row().take(pad));
+            rows.push(row);
+        }
+        if pad == 1 {
+            rows.push(border_row().take(width + 2).collect());
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    /// [`parse_with`](Self::parse_with) using the cell type's own
+    /// spelling.
+    ///
+    /// # Errors
+    ///
+    /// As for [`parse_with`](Self::parse_with).
+    pub fn parse(&self, input: &str) -> Result<Grid<T>, GridParseError>
+    where
+        T: Clone + FromCell,
+    {
+        self.parse_with(input, T::from_cell)
+    }
+}
+
+impl<T: Clone + FromCell> FromStr for Grid<T> {
+    type Err = GridParseError;
+
+    /// Parses a rectangular block of cells with no border.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GridParser::new().parse(s)
+    }
+}
@@ -0,0 +1,373 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write as _;
+use std::ops::Range;
+
+use crate::utils::par_find_first;
+
+/// Runs `rounds` rounds of the knot-tying process on the list `0..size`,
+/// keeping the position and skip size between rounds.
+///
+/// # Panics
+///
+/// Panics if `size` exceeds 256 or a length exceeds `size`.
+#[must_use]
+pub fn knot_rounds(lengths: &[usize], rounds: usize, size: usize) -> Vec<u8> {
+    let mut list: Vec<u8> = (0..size)
+        .map(|i| u8::try_from(i).expect("knot lists hold at most 256 items"))
+        .collect();
+    let (mut pos, mut skip) = (0, 0);
+    for _ in 0..rounds {
+        for &len in lengths {
+            assert!(len <= size, "knot length {len} exceeds list size");
+            // Rotating the reversed span to the front avoids wrapping.
+            list.rotate_left(pos);
+            list[..len].reverse();
+            list.rotate_right(pos);
+            pos = (pos + len + skip) % size;
+            skip += 1;
+        }
+    }
+    list
+}
+
+/// The 16-byte dense knot hash of `input`.
+#[must_use]
+pub fn knot_hash(input: &str) -> [u8; 16] {
+    let lengths: Vec<usize> = input
+        .bytes()
+        .chain([17, 31, 73, 47, 23])
+        .map(usize::from)
+        .collect();
+    let sparse = knot_rounds(&lengths, 64, 256);
+    let mut dense = [0; 16];
+    for (out, block) in dense.iter_mut().zip(sparse.chunks(16)) {
+        *out = block.iter().fold(0, |acc, b| acc ^ b);
+    }
+    dense
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(2 * bytes.len()), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+/// The knot hash of `input` as 32 lowercase hex digits.
+#[must_use]
+pub fn knot_hash_hex(input: &str) -> String {
+    to_hex(&knot_hash(input))
+}
+
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, //
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, //
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, //
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_CONSTANTS: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee, //
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501, //
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be, //
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821, //
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa, //
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8, //
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed, //
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a, //
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c, //
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70, //
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05, //
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665, //
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039, //
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1, //
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1, //
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+/// The MD5 digest of `bytes`.
+#[must_use]
+pub fn md5(bytes: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] =
+        [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    let bit_len = (bytes.len() as u64).wrapping_mul(8);
+    message.extend(bit_len.to_le_bytes());
+    for block in message.chunks_exact(64) {
+        let words: Vec<u32> = block
+            .chunks_exact(4)
+            .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (mix, idx) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(mix)
+                .wrapping_add(MD5_CONSTANTS[i])
+                .wrapping_add(words[idx])
+                .rotate_left(MD5_SHIFTS[i]);
+            (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+        }
+        for (s, x) in state.iter_mut().zip([a, b, c, d]) {
+            *s = s.wrapping_add(x);
+        }
+    }
+    let mut digest = [0; 16];
+    for (out, s) in digest.chunks_exact_mut(4).zip(state) {
+        out.copy_from_slice(&s.to_le_bytes());
+    }
+    digest
+}
+
+/// The MD5 digest of `bytes` as 32 lowercase hex digits.
+#[must_use]
+pub fn md5_hex(bytes: &[u8]) -> String {
+    to_hex(&md5(bytes))
+}
+
+/// Finds the smallest nonce `n >= start` such that the MD5 digest of
+/// `prefix` followed by the decimal digits of `n` satisfies `pred`.
+///
+/// For example `|d| d[0] == 0 && d[1] == 0 && d[2] >> 4 == 0` asks for five
+/// leading zero hex digits.
+///
+/// Nonces are hashed in parallel with [`par_find_first`], so the result is
+/// the same as a sequential search. Returns the nonce with its digest, or
+/// `None` if no nonce up to `u64::MAX` matches.
+pub fn search_nonce<P>(
+    prefix: &str,
+    start: u64,
+    pred: P,
+) -> Option<(u64, [u8; 16])>
+where
+    P: Fn(&[u8; 16]) -> bool + Sync,
+{
+    let digest = |n: u64| {
+        let mut buf = prefix.as_bytes().to_vec();
+        let _ = write!(buf, "{n}");
+        md5(&buf)
+    };
+    par_find_first(start..u64::MAX, |n| pred(&digest(n)))
+        .map(|n| (n, digest(n)))
+}
+
+const MOD: u64 = (1 << 61) - 1;
+const BASE: u64 = 1_000_003;
+
+// The remainder is below 2^61, so narrowing back to u64 is lossless.
+#[allow(clippy::cast_possible_truncation)]
+const fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MOD as u128) as u64
+}
+
+/// Polynomial hashes of every prefix of a byte string, answering
+/// substring-hash queries in constant time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RollingHash {
+    prefix: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl RollingHash {
+    #[must_use]
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut prefix = vec![0];
+        let mut powers = vec![1];
+        for &b in bytes {
+            let last = prefix[prefix.len() - 1];
+            prefix.push((mul_mod(last, BASE) + u64::from(b) + 1) % MOD);
+            powers.push(mul_mod(powers[powers.len() - 1], BASE));
+        }
+        Self { prefix, powers }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of `bytes[range]`. Equal substrings always hash equal; distinct
+    /// ones collide with negligible probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    #[must_use]
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        let shifted =
+            mul_mod(self.prefix[range.start], self.powers[range.len()]);
+        (self.prefix[range.end] + MOD - shifted) % MOD
+    }
+
+    /// Whether `bytes[a]` and `bytes[b]` are (almost certainly) equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range is out of bounds.
+    #[must_use]
+    pub fn same(&self, a: Range<usize>, b: Range<usize>) -> bool {
+        a.len() == b.len() && self.hash(a) == self.hash(b)
+    }
+
+    /// Hashes of every window of length `k`, in order.
+    pub fn windows(&self, k: usize) -> impl Iterator<Item = u64> + '_ {
+        (0..=self.len().saturating_sub(k))
+            .filter(move |&i| i + k <= self.len())
+            .map(move |i| self.hash(i..i + k))
+    }
+}
+
+const FX_MULTIPLIER: u64 = 0x517c_c1b7_2722_0a95;
+
+/// The word-at-a-time multiplicative hash used by rustc. Much faster than
+/// `SipHash` on the small integer and tuple keys typical here, but with no
+/// resistance to adversarial inputs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    const fn add(&mut self, word: u64) {
+        self.hash =
+            (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_MULTIPLIER);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.add(n.into());
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.add(n.into());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(n.into());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        // usize is at most 64 bits on every supported target.
+        self.add(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds [`FxHasher`]s starting from a fixed seed.
+///
+/// The default seed is 0, so unlike `RandomState` iteration order is the
+/// same on every run. Use [`FxBuildHasher::with_seed`] to perturb it, e.g.
+/// to check that an answer does not depend on iteration order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FxBuildHasher {
+    seed: u64,
+}
+
+impl FxBuildHasher {
+    #[must_use]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for FxBuildHasher {
+    type Hasher = FxHasher;
+
+    fn build_hasher(&self) -> FxHasher {
+        FxHasher { hash: self.seed }
+    }
+}
+
+/// A `HashMap` with the fast, deterministic [`FxBuildHasher`]. Construct
+/// with `FastMap::default()`, or `from_iter` in place of `HashMap::from`.
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` with the fast, deterministic [`FxBuildHasher`].
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
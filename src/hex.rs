@@ -0,0 +1,210 @@
+//! Axial hex coordinates for a flat-topped hex grid.
+//!
+//! `q` increases to the south-east and `r` to the south, so the implicit
+//! third cube coordinate is `s = -q - r`. Stored in a [`Grid`] with rows as
+//! `r` and columns as `q`, neighbors match [`Neighborhood::Hex`].
+//!
+//! [`Grid`]: crate::grid::Grid
+//! [`Neighborhood::Hex`]: crate::neighborhood::Neighborhood::Hex
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+/// One of the six directions between flat-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexDir {
+    /// All six directions, clockwise from north.
+    pub const ALL: [Self; 6] =
+        [Self::N, Self::NE, Self::SE, Self::S, Self::SW, Self::NW];
+
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Direction `i` (mod 6) in [`HexDir::ALL`].
+    #[must_use]
+    pub const fn from_index(i: usize) -> Self {
+        Self::ALL[i % 6]
+    }
+
+    /// `(dq, dr)` for one step in this direction.
+    #[must_use]
+    pub const fn delta(self) -> (i64, i64) {
+        match self {
+            Self::N => (0, -1),
+            Self::NE => (1, -1),
+            Self::SE => (1, 0),
+            Self::S => (0, 1),
+            Self::SW => (-1, 1),
+            Self::NW => (-1, 0),
+        }
+    }
+
+    /// Rotates clockwise by `sixths` sixths of a turn.
+    #[must_use]
+    pub const fn rotate(self, sixths: usize) -> Self {
+        Self::from_index(self.index() + sixths % 6)
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        self.rotate(3)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHexDirError(String);
+
+impl fmt::Display for ParseHexDirError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid hex direction: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseHexDirError {}
+
+impl FromStr for HexDir {
+    type Err = ParseHexDirError;
+
+    /// Accepts `n`, `ne`, `se`, `s`, `sw`, `nw` in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" => Ok(Self::N),
+            "ne" => Ok(Self::NE),
+            "se" => Ok(Self::SE),
+            "s" => Ok(Self::S),
+            "sw" => Ok(Self::SW),
+            "nw" => Ok(Self::NW),
+            _ => Err(ParseHexDirError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for HexDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::N => "n",
+            Self::NE => "ne",
+            Self::SE => "se",
+            Self::S => "s",
+            Self::SW => "sw",
+            Self::NW => "nw",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Parses a comma-separated list of moves such as `"ne,ne,s,sw"`.
+///
+/// # Errors
+///
+/// Returns the first token that is not a hex direction.
+pub fn parse_moves(line: &str) -> Result<Vec<HexDir>, ParseHexDirError> {
+    line.split(',')
+        .filter(|tok| !tok.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Axial `(q, r)` coordinates of a hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HexCoords {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl HexCoords {
+    pub const ORIGIN: Self = Self { q: 0, r: 0 };
+
+    #[must_use]
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// The third cube coordinate, `-q - r`.
+    #[must_use]
+    pub const fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    #[must_use]
+    pub const fn step(self, dir: HexDir) -> Self {
+        let (dq, dr) = dir.delta();
+        Self::new(self.q + dq, self.r + dr)
+    }
+
+    /// Number of steps between two hexes.
+    #[must_use]
+    pub const fn distance(self, other: Self) -> u64 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.unsigned_abs() + dr.unsigned_abs() + (dq + dr).unsigned_abs()) / 2
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        HexDir::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// The hexes exactly `radius` steps away, clockwise from the northern
+    /// corner. A radius of zero yields just `self`.
+    pub fn ring(self, radius: u32) -> impl Iterator<Item = Self> {
+        let corner = HexDir::N.delta();
+        let start = self + Self::new(corner.0, corner.1) * i64::from(radius);
+        let sides = if radius == 0 { 1 } else { 6 };
+        (0..sides)
+            .flat_map(move |side| {
+                let dir = HexDir::from_index(side + 2);
+                (0..radius.max(1)).map(move |_| dir)
+            })
+            .scan(start, |pos, dir| {
+                let here = *pos;
+                *pos = pos.step(dir);
+                Some(here)
+            })
+    }
+
+    /// Every hex within `radius` steps, ring by ring outwards from `self`.
+    pub fn spiral(self, radius: u32) -> impl Iterator<Item = Self> {
+        (0..=radius).flat_map(move |k| self.ring(k))
+    }
+}
+
+impl Add for HexCoords {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for HexCoords {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<i64> for HexCoords {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self {
+        Self::new(self.q * k, self.r * k)
+    }
+}
+
+impl fmt::Display for HexCoords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::rc::Rc;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::bitset::BitSet;
+
+/// Maps string labels to dense `usize` ids, allocating each distinct label
+/// once.
+#[derive(Debug, Clone, Default)]
+pub struct StringInterner {
+    ids: HashMap<Rc<str>, usize>,
+    labels: Vec<Rc<str>>,
+}
+
+impl StringInterner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `label`, assigning the next free one if it is new.
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        let label: Rc<str> = Rc::from(label);
+        self.ids.insert(Rc::clone(&label), id);
+        self.labels.push(label);
+        id
+    }
+
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `id` was not returned by this interner.
+    #[must_use]
+    pub fn label(&self, id: usize) -> &str {
+        &self.labels[id]
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+/// Weighted adjacency lists over interned ids.
+pub type AdjList<W> = Vec<Vec<(usize, W)>>;
+
+/// Builds weighted adjacency lists over interned ids from `(from, to,
+/// weight)` triples in a single pass.
+///
+/// The result plugs straight into the closure-based searches in `graph`:
+/// `|v| adj[v].iter().copied()`.
+pub fn intern_edge_list<'a, W, I>(edges: I) -> (StringInterner, AdjList<W>)
+where
+    I: IntoIterator<Item = (&'a str, &'a str, W)>,
+{
+    let mut interner = StringInterner::new();
+    let mut adj: AdjList<W> = vec![];
+    for (from, to, weight) in edges {
+        let a = interner.intern(from);
+        let b = interner.intern(to);
+        adj.resize_with(interner.len(), Vec::new);
+        adj[a].push((b, weight));
+    }
+    (interner, adj)
+}
+
+/// Serialized as the list of labels in id order.
+impl Serialize for StringInterner {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.labels.iter().map(|l| &**l))
+    }
+}
+
+impl<'de> Deserialize<'de> for StringInterner {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let labels = Vec::<String>::deserialize(deserializer)?;
+        let mut interner = Self::new();
+        for label in &labels {
+            interner.intern(label);
+        }
+        if interner.len() == labels.len() {
+            Ok(interner)
+        } else {
+            Err(serde::de::Error::custom("duplicate interner label"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEdgeListError {
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for ParseEdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected `a -> b : w`, got {:?}",
+            self.line + 1,
+            self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseEdgeListError {}
+
+/// Reads a plain-text edge list with one `from -> to : weight` edge per
+/// line, as written by [`write_edge_list`]. Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns the first line that is not an edge or whose weight does not
+/// parse.
+pub fn parse_edge_list<W: FromStr>(
+    text: &str,
+) -> Result<(StringInterner, AdjList<W>), ParseEdgeListError> {
+    let mut edges = vec![];
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let edge = line.split_once("->").and_then(|(from, rest)| {
+            let (to, weight) = rest.rsplit_once(':')?;
+            Some((from.trim(), to.trim(), weight.trim().parse().ok()?))
+        });
+        edges.push(edge.ok_or_else(|| ParseEdgeListError {
+            line: i,
+            text: line.to_string(),
+        })?);
+    }
+    Ok(intern_edge_list(edges))
+}
+
+/// Writes adjacency lists over interned ids as a `from -> to : weight` edge
+/// list, one edge per line in id order.
+///
+/// # Panics
+///
+/// Panics if `adj` refers to an id not in `interner`.
+#[must_use]
+pub fn write_edge_list<W: fmt::Display>(
+    interner: &StringInterner,
+    adj: &[Vec<(usize, W)>],
+) -> String {
+    let mut out = String::new();
+    for (from, edges) in adj.iter().enumerate() {
+        for (to, weight) in edges {
+            let (from, to) = (interner.label(from), interner.label(*to));
+            let _ = writeln!(out, "{from} -> {to} : {weight}");
+        }
+    }
+    out
+}
+
+/// Adjacency lists shown with interned labels in place of ids, one vertex
+/// per line as `label: nbr (weight), nbr (weight)`. Made by
+/// [`debug_with_labels`].
+///
+/// An id with no label is shown as `#id`.
+pub struct Labeled<'a, W> {
+    interner: &'a StringInterner,
+    adj: &'a [Vec<(usize, W)>],
+    removed: Option<&'a BitSet>,
+}
+
+impl<'a, W> Labeled<'a, W> {
+    /// Like [`debug_with_labels`], but skipping the vertices in `removed`.
+    pub(crate) const fn new(
+        interner: &'a StringInterner,
+        adj: &'a [Vec<(usize, W)>],
+        removed: Option<&'a BitSet>,
+    ) -> Self {
+        Self {
+            interner,
+            adj,
+            removed,
+        }
+    }
+}
+
+/// Displays `adj` using the labels in `interner`, so a parsed puzzle graph
+/// can be printed legibly.
+#[must_use]
+pub const fn debug_with_labels<'a, W>(
+    interner: &'a StringInterner,
+    adj: &'a [Vec<(usize, W)>],
+) -> Labeled<'a, W> {
+    Labeled::new(interner, adj, None)
+}
+
+impl<W: fmt::Display> fmt::Display for Labeled<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = |v: usize| -> String {
+            self.interner
+                .labels
+                .get(v)
+                .map_or_else(|| format!("#{v}"), ToString::to_string)
+        };
+        write_adjacency(f, self.adj, self.removed, label)
+    }
+}
+
+impl<W: fmt::Display> fmt::Debug for Labeled<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Writes adjacency lists one vertex per line, naming vertices with
+/// `label` and skipping those in `removed`.
+pub(crate) fn write_adjacency<W, L>(
+    f: &mut fmt::Formatter,
+    adj: &[Vec<(usize, W)>],
+    removed: Option<&BitSet>,
+    label: impl Fn(usize) -> L,
+) -> fmt::Result
+where
+    W: fmt::Display,
+    L: fmt::Display,
+{
+    for (v, edges) in adj.iter().enumerate() {
+        if removed.is_some_and(|removed| removed.contains(v)) {
+            continue;
+        }
+        write!(f, "{}:", label(v))?;
+        for (i, (to, weight)) in edges.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{} ({weight})", label(*to))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
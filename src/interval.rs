@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+/// A closed range of integers `lo..=hi`, never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub lo: i64,
+    pub hi: i64,
+}
+
+impl Interval {
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    #[must_use]
+    pub const fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo <= hi, "empty interval");
+        Self { lo, hi }
+    }
+
+    /// Like [`Interval::new`], but returns `None` for an empty range.
+    #[must_use]
+    pub const fn try_new(lo: i64, hi: i64) -> Option<Self> {
+        if lo <= hi {
+            Some(Self { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    /// Number of integers in the interval.
+    #[must_use]
+    pub const fn size(&self) -> i64 {
+        self.hi - self.lo + 1
+    }
+
+    #[must_use]
+    pub const fn contains(&self, x: i64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    #[must_use]
+    pub const fn contains_interval(&self, other: &Self) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::try_new(self.lo.max(other.lo), self.hi.min(other.hi))
+    }
+
+    /// The union of two intervals, if it is itself an interval (i.e. they
+    /// overlap or are adjacent).
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.hi.saturating_add(1) < other.lo
+            || other.hi.saturating_add(1) < self.lo
+        {
+            return None;
+        }
+        Some(Self::new(self.lo.min(other.lo), self.hi.max(other.hi)))
+    }
+
+    /// The parts of `self` not covered by `other`, in increasing order.
+    #[must_use]
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.intersection(other).is_none() {
+            return vec![*self];
+        }
+        let mut out = vec![];
+        if self.lo < other.lo {
+            out.push(Self::new(self.lo, other.lo - 1));
+        }
+        if other.hi < self.hi {
+            out.push(Self::new(other.hi + 1, self.hi));
+        }
+        out
+    }
+
+    /// Splits into the parts strictly below `at` and from `at` upwards.
+    #[must_use]
+    pub fn split_at(&self, at: i64) -> (Option<Self>, Option<Self>) {
+        let below = at
+            .checked_sub(1)
+            .and_then(|last| Self::try_new(self.lo, self.hi.min(last)));
+        (below, Self::try_new(self.lo.max(at), self.hi))
+    }
+}
+
+/// A set of integers stored as disjoint, non-adjacent, sorted intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: BTreeMap<i64, i64>,
+}
+
+impl IntervalSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let mut merged = interval;
+        let mut to_remove = vec![];
+        let start = merged.lo.saturating_sub(1);
+        if let Some((&lo, &hi)) = self.ranges.range(..start).next_back()
+            && hi >= start
+        {
+            merged = Interval::new(merged.lo.min(lo), merged.hi.max(hi));
+            to_remove.push(lo);
+        }
+        for (&lo, &hi) in self.ranges.range(start..=merged.hi.saturating_add(1))
+        {
+            merged = Interval::new(merged.lo.min(lo), merged.hi.max(hi));
+            to_remove.push(lo);
+        }
+        for lo in to_remove {
+            self.ranges.remove(&lo);
+        }
+        self.ranges.insert(merged.lo, merged.hi);
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        let overlapping: Vec<Interval> = self
+            .iter()
+            .filter(|iv| iv.intersection(&interval).is_some())
+            .collect();
+        for iv in overlapping {
+            self.ranges.remove(&iv.lo);
+            for rest in iv.subtract(&interval) {
+                self.ranges.insert(rest.lo, rest.hi);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, x: i64) -> bool {
+        self.ranges
+            .range(..=x)
+            .next_back()
+            .is_some_and(|(_, &hi)| x <= hi)
+    }
+
+    /// Total number of integers in the set.
+    #[must_use]
+    pub fn total_len(&self) -> i64 {
+        self.iter().map(|iv| iv.size()).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.ranges.iter().map(|(&lo, &hi)| Interval::new(lo, hi))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
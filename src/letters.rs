@@ -0,0 +1,192 @@
+use std::fmt;
+
+use crate::direction::Coords;
+use crate::grid::Grid;
+
+/// The 6-row font, with each glyph trimmed to its lit columns.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 10-row font.
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n\
+         ######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n\
+         #.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n\
+         #..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n\
+         #....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n\
+         ....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n\
+         ##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n\
+         #.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n\
+         #..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n\
+         ..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n\
+         ..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// After trimming blank rows, the text is neither 6 nor 10 rows tall.
+    UnsupportedHeight(usize),
+    /// The glyph starting at `column` is not a known letter.
+    UnknownGlyph { column: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedHeight(h) => {
+                write!(f, "letters must be 6 or 10 rows tall, not {h}")
+            }
+            Self::UnknownGlyph { column, glyph } => {
+                write!(f, "unknown glyph at column {column}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// The letters of the font `height` rows tall, as `(letter, art)` pairs
+/// with rows of `#` and `.` separated by newlines, or `None` if there is no
+/// font of that height.
+#[must_use]
+pub const fn font(height: usize) -> Option<&'static [(char, &'static str)]> {
+    match height {
+        6 => Some(&SMALL_FONT),
+        10 => Some(&LARGE_FONT),
+        _ => None,
+    }
+}
+
+/// Reads the capital letters drawn in `grid` in the Advent of Code fonts,
+/// 6 or 10 rows tall. Blank rows around the text are ignored, and letters
+/// are split on blank columns.
+///
+/// # Errors
+///
+/// Returns an error if the text has another height or a glyph is not a
+/// known letter; the error shows the glyph so it can be added.
+pub fn read_letters(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let lit_row = |r| (0..grid.width()).any(|c| grid[Coords(r, c)]);
+    let Some(top) = (0..grid.height()).find(|&r| lit_row(r)) else {
+        return Ok(String::new());
+    };
+    let bottom = (0..grid.height())
+        .rev()
+        .find(|&r| lit_row(r))
+        .unwrap_or(top);
+    let height = bottom - top + 1;
+    let font = font(height).ok_or(OcrError::UnsupportedHeight(height))?;
+    let lit_col = |c| (top..=bottom).any(|r| grid[Coords(r, c)]);
+    let mut out = String::new();
+    let mut c = 0;
+    while c < grid.width() {
+        if !lit_col(c) {
+            c += 1;
+            continue;
+        }
+        let start = c;
+        while c < grid.width() && lit_col(c) {
+            c += 1;
+        }
+        let glyph = (top..=bottom)
+            .map(|r| {
+                (start..c)
+                    .map(|c| if grid[Coords(r, c)] { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let letter = font
+            .iter()
+            .find(|(_, art)| *art == glyph)
+            .map(|&(letter, _)| letter)
+            .ok_or(OcrError::UnknownGlyph {
+                column: start,
+                glyph,
+            })?;
+        out.push(letter);
+    }
+    Ok(out)
+}
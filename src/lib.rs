@@ -1,4 +1,47 @@
+pub mod agents;
+pub mod automaton;
+pub mod bitgrid;
+pub mod bitset;
+pub mod cache;
+pub mod checkpoint;
+pub mod cli;
 pub mod common; // or any modules you want to expose
+pub mod config;
+pub mod counter;
 pub mod days;
+pub mod direction;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod game;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod hashing;
+pub mod hex;
+pub mod interner;
+pub mod interval;
+pub mod letters;
+pub mod math;
+pub mod matrix;
+pub mod neighborhood;
+pub mod parse;
+pub mod profiling;
+pub mod region;
+pub mod scan;
+pub mod search;
+pub mod simulate;
+pub mod solver;
+pub mod stepper;
+pub mod timings;
+pub mod turtle;
+pub mod twosat;
+pub mod undirected;
+pub mod uptree;
+pub mod utils;
+pub mod viz;
+pub mod vm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use common::run_w_args; // expose function(s) used in tests
@@ -1,7 +1,8 @@
-use aoc25::run_w_args;
+use aoc25::common::run_cli;
 use std::env;
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     let args = env::args().collect::<Vec<_>>();
-    println!("{}", run_w_args(&args));
+    run_cli(&args)
 }
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+/// Greatest common divisor, always non-negative.
+#[must_use]
+pub const fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Least common multiple, or `None` if the result overflows.
+#[must_use]
+pub const fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    match (a / gcd(a, b)).checked_mul(b) {
+        Some(l) => Some(l.abs()),
+        None => None,
+    }
+}
+
+/// Least common multiple of every value in `values`.
+#[must_use]
+pub fn lcm_all<I>(values: I) -> Option<i128>
+where
+    I: IntoIterator<Item = i128>,
+{
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a * x + b * y == g`.
+#[must_use]
+pub const fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut rem) = (a, b);
+    let (mut old_s, mut coef_s) = (1, 0);
+    let (mut old_t, mut coef_t) = (0, 1);
+    while rem != 0 {
+        let q = old_r / rem;
+        (old_r, rem) = (rem, old_r - q * rem);
+        (old_s, coef_s) = (coef_s, old_s - q * coef_s);
+        (old_t, coef_t) = (coef_t, old_t - q * coef_t);
+    }
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Inverse of `a` modulo `m`, if `m` is positive and `a` and `m` are
+/// coprime.
+#[must_use]
+pub const fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    if g == 1 { Some(x.rem_euclid(m)) } else { None }
+}
+
+/// `a + b mod m` for `a` and `b` already in `0..m`, without overflowing
+/// even when `m` is close to `i128::MAX`.
+pub(crate) const fn mod_add(a: i128, b: i128, m: i128) -> i128 {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `a * b mod m`, in `0..m`, without overflowing for any positive `m`.
+///
+/// # Panics
+///
+/// Panics if `m <= 0`.
+#[must_use]
+pub const fn mod_mul(a: i128, b: i128, m: i128) -> i128 {
+    assert!(m > 0, "modulus must be positive");
+    let mut a = a.rem_euclid(m);
+    let mut b = b.rem_euclid(m);
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+    let mut out = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            out = mod_add(out, a, m);
+        }
+        a = mod_add(a, a, m);
+        b >>= 1;
+    }
+    out
+}
+
+/// `base^exp mod m` by repeated squaring.
+///
+/// # Panics
+///
+/// Panics if `m <= 0`.
+#[must_use]
+pub const fn mod_pow(base: i128, mut exp: u128, m: i128) -> i128 {
+    assert!(m > 0, "modulus must be positive");
+    if m == 1 {
+        return 0;
+    }
+    let mut base = base.rem_euclid(m);
+    let mut out = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = mod_mul(out, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+    out
+}
+
+/// Solves the system `x = r_i (mod m_i)` for every `(r_i, m_i)` pair.
+///
+/// The moduli do not need to be pairwise coprime. Returns `(x, lcm)` with
+/// `0 <= x < lcm`, or `None` if the system is inconsistent, a modulus is
+/// not positive, or the combined modulus overflows.
+#[must_use]
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut sol: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(residue, n) in congruences {
+        if n <= 0 {
+            return None;
+        }
+        let (g, inv, _) = egcd(modulus, n);
+        let diff = residue.rem_euclid(n) - sol;
+        if diff % g != 0 {
+            return None;
+        }
+        let n_g = n / g;
+        let k = mod_mul(diff / g, inv, n_g);
+        let new_modulus = modulus.checked_mul(n_g)?;
+        sol = mod_add(sol, mod_mul(modulus, k, new_modulus), new_modulus);
+        modulus = new_modulus;
+    }
+    Some((sol, modulus))
+}
+
+/// An integer modulo `M`, always stored reduced to `0..M`.
+///
+/// `M` must be nonzero, which is checked at compile time:
+///
+/// ```compile_fail
+/// let zero = aoc25::math::ModInt::<0>::new(1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    /// Referenced wherever a value is created, so `ModInt<0>` is a
+    /// compile error rather than a division by zero at runtime.
+    const NONZERO_MODULUS: () =
+        assert!(M > 0, "ModInt modulus must be nonzero");
+
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        let () = Self::NONZERO_MODULUS;
+        Self(value % M)
+    }
+
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn pow(self, mut exp: u64) -> Self {
+        let mut base = self.0;
+        let mut out = 1 % M;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                out = mul_mod_u64(out, base, M);
+            }
+            base = mul_mod_u64(base, base, M);
+            exp >>= 1;
+        }
+        Self(out)
+    }
+
+    /// Multiplicative inverse, if `self` is coprime to `M`.
+    #[must_use]
+    pub fn inv(self) -> Option<Self> {
+        let inv = mod_inverse(i128::from(self.0), i128::from(M))?;
+        u64::try_from(inv).ok().map(Self)
+    }
+}
+
+// The remainder is below `m`, so narrowing back to u64 is lossless.
+#[allow(clippy::cast_possible_truncation)]
+const fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> Default for ModInt<M> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        let () = Self::NONZERO_MODULUS;
+        let m = i128::from(M);
+        let r = u64::try_from(i128::from(value).rem_euclid(m));
+        Self(r.expect("remainder is below M"))
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (sum, overflow) = self.0.overflowing_add(rhs.0);
+        if overflow || sum >= M {
+            Self(sum.wrapping_sub(M))
+        } else {
+            Self(sum)
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        if self.0 >= rhs.0 {
+            Self(self.0 - rhs.0)
+        } else {
+            Self(M - (rhs.0 - self.0))
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul_mod_u64(self.0, rhs.0, M))
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` has no inverse modulo `M`.
+    fn div(self, rhs: Self) -> Self {
+        let inv = rhs.inv().expect("divisor is not invertible modulo M");
+        Mul::mul(self, inv)
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(0) - self
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), Add::add)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), Mul::mul)
+    }
+}
@@ -0,0 +1,186 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use crate::math::{mod_add, mod_mul};
+
+/// A dense row-major matrix of `i128`s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<i128>,
+}
+
+impl Matrix {
+    #[must_use]
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0; rows * cols],
+        }
+    }
+
+    #[must_use]
+    pub fn identity(n: usize) -> Self {
+        let mut out = Self::zeros(n, n);
+        for i in 0..n {
+            out[(i, i)] = 1;
+        }
+        out
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    #[must_use]
+    pub fn from_rows(rows: Vec<Vec<i128>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|r| r.len() == cols), "ragged matrix rows");
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    #[must_use]
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn mul_with(&self, rhs: &Self, modulus: Option<i128>) -> Self {
+        assert_eq!(self.cols, rhs.rows, "matrix dimension mismatch");
+        let mut out = Self::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                if a == 0 {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    let (cur, b) = (out[(i, j)], rhs[(k, j)]);
+                    out[(i, j)] = modulus.map_or_else(
+                        || cur + a * b,
+                        |m| mod_add(cur, mod_mul(a, b, m), m),
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    /// Product with every entry reduced modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions do not match.
+    #[must_use]
+    pub fn mul_mod(&self, rhs: &Self, modulus: i128) -> Self {
+        self.mul_with(rhs, Some(modulus))
+    }
+
+    fn pow_with(&self, mut exp: u64, modulus: Option<i128>) -> Self {
+        assert_eq!(self.rows, self.cols, "only square matrices have powers");
+        let mut base = self.clone();
+        let mut out = Self::identity(self.rows);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                out = out.mul_with(&base, modulus);
+            }
+            base = base.mul_with(&base, modulus);
+            exp >>= 1;
+        }
+        out
+    }
+
+    /// `self^exp` by repeated squaring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn pow(&self, exp: u64) -> Self {
+        self.pow_with(exp, None)
+    }
+
+    /// `self^exp` with every entry reduced modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn pow_mod(&self, exp: u64, modulus: i128) -> Self {
+        self.pow_with(exp, Some(modulus))
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = i128;
+
+    fn index(&self, (r, c): (usize, usize)) -> &i128 {
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut i128 {
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, rhs: Self) -> Matrix {
+        self.mul_with(rhs, None)
+    }
+}
+
+/// The `n`th term (0-indexed) of the linear recurrence
+/// `a[i] = coeffs[0] * a[i - 1] + ... + coeffs[k - 1] * a[i - k]`, modulo
+/// `modulus`, given the first `k` terms in `initial`.
+///
+/// # Panics
+///
+/// Panics if `coeffs` and `initial` have different lengths, or if
+/// `modulus` is not positive.
+#[must_use]
+pub fn nth_term(
+    coeffs: &[i128],
+    initial: &[i128],
+    n: u64,
+    modulus: i128,
+) -> i128 {
+    let k = coeffs.len();
+    assert_eq!(k, initial.len(), "need one initial term per coefficient");
+    if k == 0 {
+        return 0;
+    }
+    if let Some(&term) = usize::try_from(n).ok().and_then(|i| initial.get(i)) {
+        return term.rem_euclid(modulus);
+    }
+    // Companion matrix mapping [a[i-1], ..., a[i-k]] to [a[i], ..., a[i-k+1]].
+    let mut step = Matrix::zeros(k, k);
+    for (j, &c) in coeffs.iter().enumerate() {
+        step[(0, j)] = c.rem_euclid(modulus);
+    }
+    for i in 1..k {
+        step[(i, i - 1)] = 1;
+    }
+    let state = Matrix::from_rows(
+        initial
+            .iter()
+            .rev()
+            .map(|&a| vec![a.rem_euclid(modulus)])
+            .collect(),
+    );
+    let shifted = step
+        .pow_mod(n - (k as u64 - 1), modulus)
+        .mul_mod(&state, modulus);
+    shifted[(0, 0)]
+}
@@ -0,0 +1,59 @@
+use crate::direction::{Coords, ICoords};
+
+/// Which cells count as adjacent to a given cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// The four orthogonal neighbors.
+    VonNeumann,
+    /// All eight surrounding cells.
+    Moore,
+    /// The six neighbors of a hex grid stored in axial layout, with rows as
+    /// `r` and columns as `q`.
+    Hex,
+    /// The eight cells a chess knight can jump to.
+    Knight,
+}
+
+impl Neighborhood {
+    /// `(dr, dc)` offsets of the neighbors, clockwise from north where that
+    /// makes sense.
+    #[must_use]
+    pub const fn offsets(self) -> &'static [(i64, i64)] {
+        match self {
+            Self::VonNeumann => &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            Self::Moore => &[
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+            ],
+            Self::Hex => &[(-1, 0), (-1, 1), (0, 1), (1, 0), (1, -1), (0, -1)],
+            Self::Knight => &[
+                (-2, 1),
+                (-1, 2),
+                (1, 2),
+                (2, 1),
+                (2, -1),
+                (1, -2),
+                (-1, -2),
+                (-2, -1),
+            ],
+        }
+    }
+
+    /// Neighbors of `pos` that stay in the non-negative quadrant.
+    pub fn of(self, pos: Coords) -> impl Iterator<Item = Coords> {
+        self.offsets().iter().filter_map(move |&d| pos.shift(d))
+    }
+
+    /// Neighbors of `pos` on an unbounded plane.
+    pub fn of_signed(self, pos: ICoords) -> impl Iterator<Item = ICoords> {
+        self.offsets()
+            .iter()
+            .map(move |&(dr, dc)| ICoords(pos.0 + dr, pos.1 + dc))
+    }
+}
@@ -0,0 +1,472 @@
+//! A tiny parser-combinator layer for structured puzzle lines.
+//!
+//! ```ignore
+//! // "Game 3: 1 red, 5 blue; 2 green"
+//! let cube = pair(preceded(ws, uint::<u32>), preceded(ws, word));
+//! let game = pair(
+//!     delimited(tag("Game "), uint::<u32>, tag(":")),
+//!     sep_by(sep_by(cube, tag(",")), tag(";")),
+//! );
+//! let (id, rounds) = parse_all(game, line)?;
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The unparsed remainder of a line, remembering where it started so errors
+/// can point at the right column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Input<'a> {
+    #[must_use]
+    pub const fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    #[must_use]
+    pub fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    #[must_use]
+    pub const fn pos(&self) -> usize {
+        self.pos
+    }
+
+    const fn advance(self, n: usize) -> Self {
+        Self {
+            src: self.src,
+            pos: self.pos + n,
+        }
+    }
+
+    fn error(self, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            src: self.src.to_string(),
+            pos: self.pos,
+            expected: expected.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    src: String,
+    pub pos: usize,
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let col = self.src[..self.pos].chars().count();
+        writeln!(f, "expected {} at column {}", self.expected, col + 1)?;
+        writeln!(f, "  {}", self.src)?;
+        write!(f, "  {}^", " ".repeat(col))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type PResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// Runs `parser` on `line`, requiring it to consume everything.
+///
+/// # Errors
+///
+/// Returns the parser's error, or an error at the first unconsumed
+/// character.
+pub fn parse_all<'a, T, P>(parser: P, line: &'a str) -> Result<T, ParseError>
+where
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    let (out, rest) = parser(Input::new(line))?;
+    if rest.rest().is_empty() {
+        Ok(out)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+/// Matches the literal `t`.
+pub fn tag<'a>(t: &'static str) -> impl Fn(Input<'a>) -> PResult<'a, &'a str> {
+    move |input| {
+        if input.rest().starts_with(t) {
+            Ok((&input.rest()[..t.len()], input.advance(t.len())))
+        } else {
+            Err(input.error(format!("{t:?}")))
+        }
+    }
+}
+
+fn take_while(
+    input: Input<'_>,
+    pred: impl Fn(char) -> bool,
+) -> (&str, Input<'_>) {
+    let rest = input.rest();
+    let n = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+    (&rest[..n], input.advance(n))
+}
+
+/// Skips any amount of whitespace (including none).
+///
+/// # Errors
+///
+/// Never fails; returns `Result` to compose with other parsers.
+pub fn ws(input: Input<'_>) -> PResult<'_, ()> {
+    Ok(((), take_while(input, char::is_whitespace).1))
+}
+
+/// One or more alphabetic characters.
+///
+/// # Errors
+///
+/// Fails if the input does not start with a letter.
+pub fn word(input: Input<'_>) -> PResult<'_, &str> {
+    match take_while(input, char::is_alphabetic) {
+        ("", _) => Err(input.error("a word")),
+        (w, rest) => Ok((w, rest)),
+    }
+}
+
+/// An unsigned integer.
+///
+/// # Errors
+///
+/// Fails if there are no digits or the value does not fit in `T`.
+pub fn uint<T: FromStr>(input: Input<'_>) -> PResult<'_, T> {
+    let (digits, rest) = take_while(input, |c| c.is_ascii_digit());
+    digits
+        .parse()
+        .map(|n| (n, rest))
+        .map_err(|_| input.error("an unsigned integer"))
+}
+
+/// A signed integer with an optional leading `-` or `+`.
+///
+/// # Errors
+///
+/// Fails if there are no digits or the value does not fit in `T`.
+pub fn int<T: FromStr>(input: Input<'_>) -> PResult<'_, T> {
+    let sign = usize::from(input.rest().starts_with(['-', '+']));
+    let (digits, rest) =
+        take_while(input.advance(sign), |c| c.is_ascii_digit());
+    let text = &input.rest()[..sign + digits.len()];
+    if digits.is_empty() {
+        return Err(input.error("an integer"));
+    }
+    text.parse()
+        .map(|n| (n, rest))
+        .map_err(|_| input.error("an integer"))
+}
+
+pub fn map<'a, A, B, P, F>(p: P, f: F) -> impl Fn(Input<'a>) -> PResult<'a, B>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    F: Fn(A) -> B,
+{
+    move |input| p(input).map(|(a, rest)| (f(a), rest))
+}
+
+pub fn pair<'a, A, B, P, Q>(
+    p: P,
+    q: Q,
+) -> impl Fn(Input<'a>) -> PResult<'a, (A, B)>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    Q: Fn(Input<'a>) -> PResult<'a, B>,
+{
+    move |input| {
+        let (a, input) = p(input)?;
+        let (b, input) = q(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+/// Runs `first` then `p`, keeping only the result of `p`.
+pub fn preceded<'a, A, B, P, Q>(
+    first: P,
+    p: Q,
+) -> impl Fn(Input<'a>) -> PResult<'a, B>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    Q: Fn(Input<'a>) -> PResult<'a, B>,
+{
+    move |input| {
+        let (_, input) = first(input)?;
+        p(input)
+    }
+}
+
+/// Runs `p` then `last`, keeping only the result of `p`.
+pub fn terminated<'a, A, B, P, Q>(
+    p: P,
+    last: Q,
+) -> impl Fn(Input<'a>) -> PResult<'a, A>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    Q: Fn(Input<'a>) -> PResult<'a, B>,
+{
+    move |input| {
+        let (a, input) = p(input)?;
+        let (_, input) = last(input)?;
+        Ok((a, input))
+    }
+}
+
+/// Runs `open`, `p`, `close` in order, keeping only the result of `p`.
+pub fn delimited<'a, A, B, C, O, P, Q>(
+    open: O,
+    p: P,
+    close: Q,
+) -> impl Fn(Input<'a>) -> PResult<'a, B>
+where
+    O: Fn(Input<'a>) -> PResult<'a, A>,
+    P: Fn(Input<'a>) -> PResult<'a, B>,
+    Q: Fn(Input<'a>) -> PResult<'a, C>,
+{
+    move |input| {
+        let (_, input) = open(input)?;
+        let (b, input) = p(input)?;
+        let (_, input) = close(input)?;
+        Ok((b, input))
+    }
+}
+
+/// Tries `p`, then `q` on the same input. On double failure, reports
+/// whichever error got further.
+pub fn alt<'a, A, P, Q>(p: P, q: Q) -> impl Fn(Input<'a>) -> PResult<'a, A>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    Q: Fn(Input<'a>) -> PResult<'a, A>,
+{
+    move |input| match p(input) {
+        Ok(out) => Ok(out),
+        Err(e1) => q(input).map_err(|e2| match e1.pos.cmp(&e2.pos) {
+            std::cmp::Ordering::Less => e2,
+            std::cmp::Ordering::Greater => e1,
+            std::cmp::Ordering::Equal => ParseError {
+                expected: format!("{} or {}", e1.expected, e2.expected),
+                ..e1
+            },
+        }),
+    }
+}
+
+/// Zero or more `p`s separated by `sep`.
+///
+/// A `p` that fails after consuming input is an error rather than the end
+/// of the list. The list also ends when a separator and item together
+/// consume nothing, as in [`many`].
+pub fn sep_by<'a, A, B, P, S>(
+    p: P,
+    sep: S,
+) -> impl Fn(Input<'a>) -> PResult<'a, Vec<A>>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    S: Fn(Input<'a>) -> PResult<'a, B>,
+{
+    move |input| {
+        let mut out = vec![];
+        let (first, mut input) = match p(input) {
+            Ok(first) => first,
+            Err(err) if err.pos == input.pos() => return Ok((out, input)),
+            Err(err) => return Err(err),
+        };
+        out.push(first);
+        while let Ok((_, after_sep)) = sep(input) {
+            let (next, after) = p(after_sep)?;
+            if after.pos() == input.pos() {
+                break;
+            }
+            out.push(next);
+            input = after;
+        }
+        Ok((out, input))
+    }
+}
+
+/// Zero or more consecutive `p`s.
+pub fn many<'a, A, P>(p: P) -> impl Fn(Input<'a>) -> PResult<'a, Vec<A>>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+{
+    move |mut input| {
+        let mut out = vec![];
+        while let Ok((a, rest)) = p(input) {
+            if rest.pos() == input.pos() {
+                break;
+            }
+            out.push(a);
+            input = rest;
+        }
+        Ok((out, input))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+/// Precedence and associativity of the binary operators an expression may
+/// use. Higher precedence binds tighter. Known operators are `+`, `-`, `*`,
+/// `/`, `%` and `^` (power).
+///
+/// ```ignore
+/// // Addition binds tighter than multiplication.
+/// let table = OpTable::new()
+///     .with('+', 2, Assoc::Left)
+///     .with('*', 1, Assoc::Left);
+/// assert_eq!(eval_expr("2 * 3 + (4 * 5)", &table)?, 46);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpTable {
+    ops: Vec<(char, u8, Assoc)>,
+}
+
+impl OpTable {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    /// The usual arithmetic rules: `^` over `* / %` over `+ -`.
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::new()
+            .with('+', 1, Assoc::Left)
+            .with('-', 1, Assoc::Left)
+            .with('*', 2, Assoc::Left)
+            .with('/', 2, Assoc::Left)
+            .with('%', 2, Assoc::Left)
+            .with('^', 3, Assoc::Right)
+    }
+
+    /// `+ - * /` evaluated strictly left to right.
+    #[must_use]
+    pub fn flat() -> Self {
+        Self::new()
+            .with('+', 1, Assoc::Left)
+            .with('-', 1, Assoc::Left)
+            .with('*', 1, Assoc::Left)
+            .with('/', 1, Assoc::Left)
+    }
+
+    /// Adds or replaces the rule for `op`.
+    #[must_use]
+    pub fn with(mut self, op: char, prec: u8, assoc: Assoc) -> Self {
+        self.ops.retain(|&(c, _, _)| c != op);
+        self.ops.push((op, prec, assoc));
+        self
+    }
+
+    fn get(&self, op: char) -> Option<(u8, Assoc)> {
+        self.ops
+            .iter()
+            .find(|&&(c, _, _)| c == op)
+            .map(|&(_, prec, assoc)| (prec, assoc))
+    }
+}
+
+fn apply_op(op: char, a: i128, b: i128) -> Option<i128> {
+    match op {
+        '+' => a.checked_add(b),
+        '-' => a.checked_sub(b),
+        '*' => a.checked_mul(b),
+        '/' => a.checked_div(b),
+        '%' => a.checked_rem(b),
+        '^' => a.checked_pow(u32::try_from(b).ok()?),
+        _ => None,
+    }
+}
+
+/// Evaluates an infix expression of non-negative integer literals, binary
+/// operators from `table` and parentheses, using the shunting-yard
+/// algorithm.
+///
+/// # Errors
+///
+/// Fails on malformed input, unbalanced parentheses, division by zero or
+/// overflow, pointing at the offending character.
+pub fn eval_expr(expr: &str, table: &OpTable) -> Result<i128, ParseError> {
+    let error = |pos, expected: &str| Input { src: expr, pos }.error(expected);
+    let mut vals: Vec<i128> = vec![];
+    // Pending operators and open parentheses, with their byte positions.
+    let mut ops: Vec<(char, usize)> = vec![];
+    let reduce = |vals: &mut Vec<i128>, op: char, pos: usize| {
+        let (b, a) = (vals.pop(), vals.pop());
+        let out = a.zip(b).and_then(|(a, b)| apply_op(op, a, b));
+        vals.push(out.ok_or_else(|| error(pos, "an in-range operation"))?);
+        Ok(())
+    };
+    let mut expect_operand = true;
+    let mut input = Input::new(expr);
+    while let Some(ch) = input.rest().chars().next() {
+        let pos = input.pos();
+        if ch.is_whitespace() {
+            input = input.advance(ch.len_utf8());
+        } else if ch.is_ascii_digit() {
+            if !expect_operand {
+                return Err(error(pos, "an operator"));
+            }
+            let (n, rest) = uint::<i128>(input)?;
+            vals.push(n);
+            input = rest;
+            expect_operand = false;
+        } else if ch == '(' {
+            if !expect_operand {
+                return Err(error(pos, "an operator"));
+            }
+            ops.push((ch, pos));
+            input = input.advance(1);
+        } else if ch == ')' {
+            if expect_operand {
+                return Err(error(pos, "a number"));
+            }
+            loop {
+                match ops.pop() {
+                    Some(('(', _)) => break,
+                    Some((op, at)) => reduce(&mut vals, op, at)?,
+                    None => return Err(error(pos, "a matching '('")),
+                }
+            }
+            input = input.advance(1);
+        } else if let Some((prec, assoc)) = table.get(ch) {
+            if expect_operand {
+                return Err(error(pos, "a number"));
+            }
+            while let Some(&(top, at)) = ops.last() {
+                let Some((top_prec, _)) = table.get(top) else {
+                    break;
+                };
+                if top_prec < prec
+                    || (top_prec == prec && assoc == Assoc::Right)
+                {
+                    break;
+                }
+                ops.pop();
+                reduce(&mut vals, top, at)?;
+            }
+            ops.push((ch, pos));
+            input = input.advance(ch.len_utf8());
+            expect_operand = true;
+        } else {
+            return Err(error(pos, "a number, operator or parenthesis"));
+        }
+    }
+    if expect_operand {
+        return Err(error(expr.len(), "a number"));
+    }
+    while let Some((op, at)) = ops.pop() {
+        if op == '(' {
+            return Err(error(at, "a matching ')'"));
+        }
+        reduce(&mut vals, op, at)?;
+    }
+    vals.pop().ok_or_else(|| error(0, "an expression"))
+}
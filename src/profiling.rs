@@ -0,0 +1,140 @@
+use std::fmt;
+
+/// Heap usage while running a closure, from [`measure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Allocations and reallocations made.
+    pub allocations: usize,
+    /// The most heap in use at once, above what was in use beforehand.
+    pub peak_bytes: usize,
+}
+
+impl fmt::Display for AllocStats {
+    // Byte counts only need one decimal place.
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.peak_bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(
+            f,
+            "peak {size:.1} {}, {} allocations",
+            UNITS[unit], self.allocations
+        )
+    }
+}
+
+/// Runs `f`, returning its heap usage if the `profiling` feature is on.
+///
+/// Allocations on every thread count, so measurements overlap if two are
+/// taken at once.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "profiling")]
+    {
+        counting::reset();
+        let out = f();
+        (out, Some(counting::stats()))
+    }
+    #[cfg(not(feature = "profiling"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "profiling")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    use super::AllocStats;
+
+    /// The system allocator, counting allocations and live bytes.
+    struct Counting;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    /// `CURRENT` when the measurement started.
+    static BASELINE: AtomicUsize = AtomicUsize::new(0);
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    fn grow(bytes: usize) {
+        let now = CURRENT.fetch_add(bytes, Relaxed) + bytes;
+        PEAK.fetch_max(now, Relaxed);
+    }
+
+    fn shrink(bytes: usize) {
+        CURRENT.fetch_sub(bytes, Relaxed);
+    }
+
+    // SAFETY: every call is forwarded to `System` unchanged; the counters
+    // are only bookkeeping.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: the caller upholds `alloc`'s contract.
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                ALLOCATIONS.fetch_add(1, Relaxed);
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: the caller upholds `alloc_zeroed`'s contract.
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                ALLOCATIONS.fetch_add(1, Relaxed);
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            // SAFETY: the caller upholds `dealloc`'s contract.
+            unsafe { System.dealloc(ptr, layout) };
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(
+            &self,
+            ptr: *mut u8,
+            layout: Layout,
+            new_size: usize,
+        ) -> *mut u8 {
+            // SAFETY: the caller upholds `realloc`'s contract.
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                ALLOCATIONS.fetch_add(1, Relaxed);
+                if new_size > layout.size() {
+                    grow(new_size - layout.size());
+                } else {
+                    shrink(layout.size() - new_size);
+                }
+            }
+            new
+        }
+    }
+
+    pub fn reset() {
+        let now = CURRENT.load(Relaxed);
+        BASELINE.store(now, Relaxed);
+        PEAK.store(now, Relaxed);
+        ALLOCATIONS.store(0, Relaxed);
+    }
+
+    pub fn stats() -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed),
+            peak_bytes: PEAK
+                .load(Relaxed)
+                .saturating_sub(BASELINE.load(Relaxed)),
+        }
+    }
+}
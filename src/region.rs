@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::direction::{Coords, Direction};
+use crate::graph::bfs_distances;
+use crate::grid::Grid;
+
+/// A set of grid cells, usually a 4-connected component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Coords>,
+}
+
+/// Splits `cells` into 4-connected components, ordered by their first cell
+/// in reading order.
+fn components(cells: &HashSet<Coords>) -> Vec<Region> {
+    let mut sorted: Vec<Coords> = cells.iter().copied().collect();
+    sorted.sort_unstable();
+    let mut seen = HashSet::new();
+    let mut out = vec![];
+    for seed in sorted {
+        if seen.contains(&seed) {
+            continue;
+        }
+        let component: HashSet<Coords> =
+            bfs_distances([seed], |pos: Coords| {
+                Direction::CARDINALS
+                    .into_iter()
+                    .filter_map(move |dir| pos.step(dir))
+                    .filter(move |nbr| cells.contains(nbr))
+            })
+            .into_keys()
+            .collect();
+        seen.extend(component.iter().copied());
+        out.push(Region { cells: component });
+    }
+    out
+}
+
+impl Region {
+    #[must_use]
+    pub const fn from_cells(cells: HashSet<Coords>) -> Self {
+        Self { cells }
+    }
+
+    #[must_use]
+    pub const fn cells(&self) -> &HashSet<Coords> {
+        &self.cells
+    }
+
+    #[must_use]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Coords) -> bool {
+        self.cells.contains(&pos)
+    }
+
+    /// Number of cell edges on the boundary of the region.
+    #[must_use]
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|&pos| {
+                Direction::CARDINALS.into_iter().map(move |d| pos.step(d))
+            })
+            .filter(|nbr| nbr.is_none_or(|nbr| !self.contains(nbr)))
+            .count()
+    }
+
+    /// Smallest and largest corners of the region, or `None` if it is empty.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Coords, Coords)> {
+        self.cells.iter().fold(None, |acc, &Coords(r, c)| {
+            Some(acc.map_or((Coords(r, c), Coords(r, c)), |(lo, hi)| {
+                (
+                    Coords(lo.0.min(r), lo.1.min(c)),
+                    Coords(hi.0.max(r), hi.1.max(c)),
+                )
+            }))
+        })
+    }
+
+    /// Connected groups of cells not in the region that are completely
+    /// enclosed by it (using 4-connectivity for the outside).
+    #[must_use]
+    pub fn holes(&self) -> Vec<Self> {
+        let Some((lo, hi)) = self.bounding_box() else {
+            return vec![];
+        };
+        let outside: HashSet<Coords> = (lo.0..=hi.0)
+            .flat_map(|r| (lo.1..=hi.1).map(move |c| Coords(r, c)))
+            .filter(|pos| !self.contains(*pos))
+            .collect();
+        components(&outside)
+            .into_iter()
+            .filter(|comp| {
+                comp.cells.iter().all(|&Coords(r, c)| {
+                    r != lo.0 && r != hi.0 && c != lo.1 && c != hi.1
+                })
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn contains_holes(&self) -> bool {
+        !self.holes().is_empty()
+    }
+
+    /// Mean `(row, col)` of the cells, or `None` if the region is empty.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        if self.is_empty() {
+            return None;
+        }
+        let n = self.cells.len() as f64;
+        let (sum_r, sum_c) = self
+            .cells
+            .iter()
+            .fold((0.0, 0.0), |(sr, sc), &Coords(r, c)| {
+                (sr + r as f64, sc + c as f64)
+            });
+        Some((sum_r / n, sum_c / n))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into 4-connected regions of cells related by `same`,
+    /// ordered by their first cell in reading order.
+    pub fn regions<F>(&self, same: F) -> Vec<Region>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let same = &same;
+        let mut seen = HashSet::new();
+        let mut out = vec![];
+        for (seed, _) in self.iter() {
+            if seen.contains(&seed) {
+                continue;
+            }
+            let cells: HashSet<Coords> = bfs_distances([seed], |pos| {
+                self.cardinal_neighbors(pos)
+                    .filter(move |&nbr| same(&self[pos], &self[nbr]))
+            })
+            .into_keys()
+            .collect();
+            seen.extend(cells.iter().copied());
+            out.push(Region { cells });
+        }
+        out
+    }
+}
+
+/// Which regions border which: entry `i` lists `(j, border)` for every
+/// region `j` sharing `border` cell edges with `regions[i]`, sorted by `j`.
+///
+/// Region ids are indices into `regions`, so the result plugs into the
+/// closure-based searches in `graph` as `|i| adj[i].iter().copied()`.
+#[must_use]
+pub fn region_adjacency(regions: &[Region]) -> Vec<Vec<(usize, u64)>> {
+    let owner: HashMap<Coords, usize> = regions
+        .iter()
+        .enumerate()
+        .flat_map(|(id, region)| region.cells.iter().map(move |&c| (c, id)))
+        .collect();
+    let mut adj: Vec<BTreeMap<usize, u64>> =
+        vec![BTreeMap::new(); regions.len()];
+    for (&pos, &id) in &owner {
+        for dir in Direction::CARDINALS {
+            let Some(other) = pos.step(dir).and_then(|nbr| owner.get(&nbr))
+            else {
+                continue;
+            };
+            if *other != id {
+                *adj[id].entry(*other).or_default() += 1;
+            }
+        }
+    }
+    adj.into_iter().map(|m| m.into_iter().collect()).collect()
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// The literal text of the template was not found in the input.
+    Mismatch { expected: String, at: usize },
+    /// A field was captured but could not be parsed as its type.
+    Parse {
+        index: usize,
+        field: String,
+        ty: &'static str,
+    },
+    /// The template's `{}` count does not match the number of types.
+    Arity,
+    /// Two placeholders in the template have no literal text between them,
+    /// so there is no telling where the first field ends.
+    AdjacentPlaceholders,
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Mismatch { expected, at } if expected.is_empty() => {
+                write!(f, "unexpected trailing text at byte {at}")
+            }
+            Self::Mismatch { expected, at } => {
+                write!(f, "expected {expected:?} at byte {at}")
+            }
+            Self::Parse { index, field, ty } => {
+                write!(f, "field {index} ({field:?}) is not a valid {ty}")
+            }
+            Self::Arity => {
+                f.write_str("placeholder count does not match types")
+            }
+            Self::AdjacentPlaceholders => {
+                f.write_str("template has `{}{}` with no text between")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Matches `line` against `template`, returning the text captured by each
+/// `{}` placeholder.
+///
+/// Each placeholder captures up to the first occurrence of the literal text
+/// following it (or the rest of the line, for a trailing placeholder), so
+/// placeholders must be separated by some literal text.
+///
+/// # Errors
+///
+/// Returns [`ScanError::AdjacentPlaceholders`] if the template has `{}{}`,
+/// or [`ScanError::Mismatch`] if some literal part is missing or text is
+/// left over.
+pub fn scan_fields<'a>(
+    line: &'a str,
+    template: &str,
+) -> Result<Vec<&'a str>, ScanError> {
+    let parts: Vec<&str> = template.split("{}").collect();
+    if parts.len() > 2 && parts[1..parts.len() - 1].contains(&"") {
+        return Err(ScanError::AdjacentPlaceholders);
+    }
+    let mut literals = parts.into_iter();
+    let first = literals.next().unwrap_or_default();
+    let mut rest =
+        line.strip_prefix(first)
+            .ok_or_else(|| ScanError::Mismatch {
+                expected: first.to_string(),
+                at: 0,
+            })?;
+    let mut fields = vec![];
+    for lit in literals {
+        let end = if lit.is_empty() {
+            rest.len()
+        } else {
+            rest.find(lit).ok_or_else(|| ScanError::Mismatch {
+                expected: lit.to_string(),
+                at: line.len() - rest.len(),
+            })?
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + lit.len()..];
+    }
+    if rest.is_empty() {
+        Ok(fields)
+    } else {
+        Err(ScanError::Mismatch {
+            expected: String::new(),
+            at: line.len() - rest.len(),
+        })
+    }
+}
+
+/// Parses the next captured field as a `T`. Used by [`scan!`].
+///
+/// # Errors
+///
+/// Returns [`ScanError::Arity`] if there is no next field, or
+/// [`ScanError::Parse`] if it does not parse.
+pub fn parse_field<T: FromStr>(
+    field: Option<(usize, &str)>,
+) -> Result<T, ScanError> {
+    let (index, field) = field.ok_or(ScanError::Arity)?;
+    field.parse().map_err(|_| ScanError::Parse {
+        index,
+        field: field.to_string(),
+        ty: std::any::type_name::<T>(),
+    })
+}
+
+/// sscanf-style matching of a line against a template with `{}`
+/// placeholders, each parsed as the corresponding type.
+///
+/// ```ignore
+/// let (lo, hi, c, pw) = scan!(line, "{}-{} {}: {}", u32, u32, char, String)?;
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $template:expr, $($t:ty),+ $(,)?) => {
+        $crate::scan::scan_fields($line, $template).and_then(|fields| {
+            let mut fields = fields.into_iter().enumerate();
+            let out = ($($crate::scan::parse_field::<$t>(fields.next())?,)+);
+            if fields.next().is_some() {
+                return Err($crate::scan::ScanError::Arity);
+            }
+            Ok(out)
+        })
+    };
+}
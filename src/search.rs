@@ -0,0 +1,151 @@
+use std::hash::Hash;
+
+use crate::hashing::{FastMap, FastSet};
+use crate::utils::{Canonicalize, MinHeap};
+
+/// An optimization problem for [`best_first`]: find the goal state of least
+/// cost. To maximize a score instead, use its negation as the cost.
+pub trait Problem {
+    type State: Clone;
+
+    /// Groups states that [`Problem::dominates`] can compare, e.g. the time
+    /// remaining and robot counts. Problems without dominance use `()`.
+    type Key: Eq + Hash;
+
+    fn successors(&mut self, state: &Self::State) -> Vec<Self::State>;
+
+    /// Goal states are leaves: they are scored but never expanded.
+    fn is_goal(&mut self, state: &Self::State) -> bool;
+
+    /// The cost of a goal state.
+    fn cost(&mut self, state: &Self::State) -> i64;
+
+    /// An optimistic estimate: no goal reachable from `state` costs less.
+    /// The tighter it is, the more the search can prune.
+    fn lower_bound(&mut self, state: &Self::State) -> i64;
+
+    /// The group a state is checked for dominance in, or `None` to skip the
+    /// check for it.
+    fn dominance_key(&mut self, _state: &Self::State) -> Option<Self::Key> {
+        None
+    }
+
+    /// Whether every goal reachable from `b` is matched or beaten by one
+    /// reachable from `a`, so `b` need not be explored. Only called on
+    /// states with the same dominance key.
+    fn dominates(&mut self, _a: &Self::State, _b: &Self::State) -> bool {
+        false
+    }
+}
+
+/// Counters describing how much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SearchStats {
+    pub expanded: usize,
+    pub generated: usize,
+    pub pruned_by_bound: usize,
+    pub pruned_by_dominance: usize,
+    pub pruned_by_transposition: usize,
+    pub max_frontier: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    /// The cheapest goal found with its cost, if any goal is reachable.
+    pub best: Option<(i64, S)>,
+    pub stats: SearchStats,
+}
+
+/// Branch-and-bound best-first search from `start`.
+///
+/// States are expanded in order of their lower bound. Once a goal has been
+/// found, any state whose bound cannot beat it is pruned, and the search
+/// stops as soon as the frontier holds nothing better. States dominated by
+/// one already generated are dropped.
+pub fn best_first<P: Problem>(
+    problem: &mut P,
+    start: P::State,
+) -> SearchResult<P::State> {
+    search(problem, start, |_| false)
+}
+
+/// [`best_first`] with a transposition table of canonical states.
+///
+/// A state whose canonical form has already been generated is dropped, so
+/// states reached along different paths, or equal up to symmetry, are only
+/// explored once.
+///
+/// Only sound when the cost of a goal depends on the state alone, not on
+/// the path taken to reach it.
+pub fn best_first_dedup<P>(
+    problem: &mut P,
+    start: P::State,
+) -> SearchResult<P::State>
+where
+    P: Problem,
+    P::State: Canonicalize + Eq + Hash,
+{
+    let mut seen = FastSet::default();
+    search(problem, start, |state| !seen.insert(state.canonicalize()))
+}
+
+/// The search behind [`best_first`], dropping states for which
+/// `is_transposition` holds.
+fn search<P, D>(
+    problem: &mut P,
+    start: P::State,
+    mut is_transposition: D,
+) -> SearchResult<P::State>
+where
+    P: Problem,
+    D: FnMut(&P::State) -> bool,
+{
+    let mut stats = SearchStats::default();
+    let mut best: Option<(i64, P::State)> = None;
+    let mut fronts: FastMap<P::Key, Vec<P::State>> = FastMap::default();
+    let mut frontier = MinHeap::new();
+    let bound = problem.lower_bound(&start);
+    is_transposition(&start);
+    if let Some(key) = problem.dominance_key(&start) {
+        fronts.insert(key, vec![start.clone()]);
+    }
+    frontier.push(bound, start);
+    while let Some((bound, state)) = frontier.pop() {
+        if best.as_ref().is_some_and(|&(cost, _)| bound >= cost) {
+            stats.pruned_by_bound += 1 + frontier.len();
+            break;
+        }
+        if problem.is_goal(&state) {
+            let cost = problem.cost(&state);
+            if best.as_ref().is_none_or(|&(best, _)| cost < best) {
+                best = Some((cost, state));
+            }
+            continue;
+        }
+        stats.expanded += 1;
+        for next in problem.successors(&state) {
+            stats.generated += 1;
+            let bound = problem.lower_bound(&next);
+            if best.as_ref().is_some_and(|&(cost, _)| bound >= cost) {
+                stats.pruned_by_bound += 1;
+                continue;
+            }
+            if is_transposition(&next) {
+                stats.pruned_by_transposition += 1;
+                continue;
+            }
+            if let Some(key) = problem.dominance_key(&next) {
+                let front = fronts.entry(key).or_default();
+                if front.iter().any(|s| problem.dominates(s, &next)) {
+                    stats.pruned_by_dominance += 1;
+                    continue;
+                }
+                front.retain(|s| !problem.dominates(&next, s));
+                front.push(next.clone());
+            }
+            frontier.push(bound, next);
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    SearchResult { best, stats }
+}
@@ -0,0 +1,92 @@
+//! Running a step function until its state stops changing, for "iterate
+//! until nothing moves" puzzles.
+//!
+//! ```ignore
+//! let (seats, rounds) = fixed_point(seats, |s| next_round(s), 1000)?;
+//! ```
+
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::hashing::FxBuildHasher;
+
+/// The state reached when `max_iters` steps ran out before it settled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotConverged<T> {
+    pub state: T,
+    pub iterations: usize,
+}
+
+impl<T> fmt::Display for NotConverged<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no fixed point after {} iterations", self.iterations)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for NotConverged<T> {}
+
+/// Applies `step` until it returns a state equal to its input, returning
+/// that state and the number of steps that changed it.
+///
+/// # Errors
+///
+/// Returns the latest state if it still changed on each of `max_iters`
+/// steps.
+pub fn fixed_point<T, F>(
+    initial: T,
+    mut step: F,
+    max_iters: usize,
+) -> Result<(T, usize), NotConverged<T>>
+where
+    T: PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut state = initial;
+    for iterations in 0..max_iters {
+        let next = step(&state);
+        if next == state {
+            return Ok((state, iterations));
+        }
+        state = next;
+    }
+    Err(NotConverged {
+        state,
+        iterations: max_iters,
+    })
+}
+
+/// [`fixed_point`] for a `step` that updates the state in place, such as
+/// [`Grid::step_automaton`](crate::grid::Grid::step_automaton).
+///
+/// Only the hash of the previous state is kept, so no copies are made; a
+/// hash collision would end the run one step early.
+///
+/// # Errors
+///
+/// Returns the latest state if it still changed on each of `max_iters`
+/// steps.
+pub fn fixed_point_by_hash<T, F>(
+    initial: T,
+    mut step: F,
+    max_iters: usize,
+) -> Result<(T, usize), NotConverged<T>>
+where
+    T: Hash,
+    F: FnMut(&mut T),
+{
+    let hasher = FxBuildHasher::default();
+    let mut state = initial;
+    let mut hash = hasher.hash_one(&state);
+    for iterations in 0..max_iters {
+        step(&mut state);
+        let next = hasher.hash_one(&state);
+        if next == hash {
+            return Ok((state, iterations));
+        }
+        hash = next;
+    }
+    Err(NotConverged {
+        state,
+        iterations: max_iters,
+    })
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::cache;
+use crate::generate::{self, InputGenerator};
+
+/// A puzzle answer, either numeric or textual.
+///
+/// Numeric answers compare equal by value whatever their width, and any
+/// answer compares equal to a string with the same `Display` output, so an
+/// answer can be checked directly against the expected text.
+///
+/// A `String` answer may span several lines, e.g. a rendered grid of
+/// letters; trailing newlines are dropped when it is displayed.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    String(String),
+}
+
+impl Answer {
+    /// The value of a numeric answer.
+    #[must_use]
+    pub fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Self::I64(n) => Some(n.into()),
+            Self::U64(n) => Some(n.into()),
+            Self::I128(n) => Some(n),
+            Self::String(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::I64(n) => n.fmt(f),
+            Self::U64(n) => n.fmt(f),
+            Self::I128(n) => n.fmt(f),
+            Self::String(s) => f.write_str(s.trim_end_matches('\n')),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Self::String(s) => s.trim_end_matches('\n') == *other,
+            _ => self.to_string().as_str() == *other,
+        }
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_i128() == Some(i128::from(*other))
+    }
+}
+
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        self.as_i128() == Some(i128::from(*other))
+    }
+}
+
+impl PartialEq<i128> for Answer {
+    fn eq(&self, other: &i128) -> bool {
+        self.as_i128() == Some(*other)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::I64(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::U64(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        // usize is at most 64 bits on every supported target.
+        Self::U64(n as u64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Self::I128(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+/// One day's puzzle: the input is parsed once into `Parsed`, which both
+/// parts then borrow.
+///
+/// `Parsed` must be serializable so that `--cache` can reuse it across runs.
+pub trait Day {
+    type Parsed: Serialize + DeserializeOwned;
+
+    fn parse(input: &str) -> Self::Parsed;
+
+    fn part1(parsed: &Self::Parsed) -> Answer;
+
+    fn part2(parsed: &Self::Parsed) -> Answer;
+}
+
+/// Parses `input` (through the parse cache) and solves the given part.
+///
+/// # Panics
+///
+/// Panics if `part` is not 1 or 2.
+pub fn solve<D: Day>(input: &str, part: u8) -> Answer {
+    let parsed = cache::cached(input, D::parse);
+    match part {
+        1 => D::part1(&parsed),
+        2 => D::part2(&parsed),
+        _ => panic!("part must be 1 or 2, got {part}"),
+    }
+}
+
+/// Slow but obviously correct versions of a day's parts, for checking a
+/// clever [`Day`] implementation against.
+///
+/// `--verify-against-naive` runs both on the input, and
+/// `tests/differential.rs` runs both on generated inputs.
+///
+/// Days register these with [`Solver::with_naive`].
+pub trait Naive: Day {
+    fn naive_part1(parsed: &Self::Parsed) -> Answer;
+
+    fn naive_part2(parsed: &Self::Parsed) -> Answer;
+}
+
+/// Parses `input` and solves the given part with the naive solver. The
+/// parse cache is bypassed, so the two solvers share nothing but the
+/// parser.
+///
+/// # Panics
+///
+/// Panics if `part` is not 1 or 2.
+#[must_use]
+pub fn solve_naive<D: Naive>(input: &str, part: u8) -> Answer {
+    let parsed = D::parse(input);
+    match part {
+        1 => D::naive_part1(&parsed),
+        2 => D::naive_part2(&parsed),
+        _ => panic!("part must be 1 or 2, got {part}"),
+    }
+}
+
+/// One day's entry in [`SOLVERS`](crate::days::SOLVERS), through which the
+/// runner finds everything the day provides.
+///
+/// ```ignore
+/// Solver::new::<day07::Day07>(7)
+///     .with_naive::<day07::Day07>()
+///     .with_generator::<day07::Day07>()
+///     .with_example(1, day07::part1::EXAMPLE, day07::part1::EXPECTED),
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    /// [`solve`] for the day.
+    pub solve: fn(&str, u8) -> Answer,
+    /// [`solve_naive`] for the day, if it implements [`Naive`].
+    pub naive: Option<fn(&str, u8) -> Answer>,
+    /// [`generate::generate`] for the day, if it implements
+    /// [`InputGenerator`].
+    pub generate: Option<fn(Option<usize>, u64) -> String>,
+    /// The example input and expected answer for each part, for
+    /// `--example`.
+    pub examples: [Option<(&'static str, &'static str)>; 2],
+}
+
+impl Solver {
+    #[must_use]
+    pub const fn new<D: Day>(day: u8) -> Self {
+        Self {
+            day,
+            solve: solve::<D>,
+            naive: None,
+            generate: None,
+            examples: [None, None],
+        }
+    }
+
+    #[must_use]
+    pub const fn with_naive<D: Naive>(mut self) -> Self {
+        self.naive = Some(solve_naive::<D>);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_generator<D: InputGenerator>(mut self) -> Self {
+        self.generate = Some(generate::generate::<D>);
+        self
+    }
+
+    /// Registers the example for `part`, usually the constants defined by
+    /// [`aoc_test!`](crate::aoc_test).
+    ///
+    /// # Panics
+    ///
+    /// Panics, at compile time in a `const`, if `part` is not 1 or 2.
+    #[must_use]
+    pub const fn with_example(
+        mut self,
+        part: u8,
+        example: &'static str,
+        expected: &'static str,
+    ) -> Self {
+        assert!(part == 1 || part == 2, "part must be 1 or 2");
+        self.examples[part as usize - 1] = Some((example, expected));
+        self
+    }
+}
+
+/// Embeds a puzzle example next to a day's solution and generates a test
+/// checking it.
+///
+/// ```ignore
+/// aoc_test!(
+///     day12,
+///     part1,
+///     example = "AAAA\nBBCD\nBBCC\nEEEC",
+///     expected = "140",
+/// );
+/// aoc_test!(
+///     day12,
+///     part2,
+///     example = include_str!("../../inputs/examples/day12.txt"),
+///     expected = "1206",
+/// );
+/// ```
+///
+/// Each use defines a module named after the part, holding the `EXAMPLE`
+/// and `EXPECTED` constants and an `example` test that solves the example
+/// through [`solve_day`](crate::common::solve_day), so the day must be
+/// registered in [`SOLVERS`](crate::days::SOLVERS). Registering the
+/// constants with [`Solver::with_example`] also makes them available to
+/// `--example`.
+#[macro_export]
+macro_rules! aoc_test {
+    (
+        $day:ident,
+        $part:ident,
+        example = $example:expr,
+        expected = $expected:expr $(,)?
+    ) => {
+        pub mod $part {
+            pub const EXAMPLE: &str = $example;
+            pub const EXPECTED: &str = $expected;
+
+            #[test]
+            fn example() {
+                let day: u8 = stringify!($day)
+                    .trim_start_matches("day")
+                    .parse()
+                    .expect("day name should be `dayNN`");
+                let part: u8 = stringify!($part)
+                    .trim_start_matches("part")
+                    .parse()
+                    .expect("part name should be `part1` or `part2`");
+                let answer = $crate::common::solve_day(day, part, EXAMPLE)
+                    .expect("day should be registered in SOLVERS");
+                assert_eq!(answer, EXPECTED);
+            }
+        }
+    };
+}
@@ -0,0 +1,147 @@
+//! Frame-by-frame display of a solver's state for `--step`, and recording
+//! of the frames as images for `--record`.
+//!
+//! Solvers call [`frame`] wherever the state is worth seeing; without
+//! either flag the call does nothing, so it can stay in committed code.
+//!
+//! ```ignore
+//! for round in 1.. {
+//!     grid.step_automaton(rule);
+//!     stepper::frame(&format!("round {round}"), &grid);
+//! }
+//! ```
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::viz::{self, Visualizable};
+
+/// Pixels per cell side in recorded frames.
+const RECORD_SCALE: usize = 4;
+
+/// How `--step` advances between frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepMode {
+    /// Frames are not shown.
+    Off,
+    /// Wait for Enter after each frame. Entering `q` stops showing frames
+    /// and lets the solver run on.
+    Enter,
+    /// Pause for this long after each frame.
+    Delay(Duration),
+}
+
+struct State {
+    mode: StepMode,
+    /// Where frames are saved as `frame-NNNNN.ppm`, with `--record`.
+    record: Option<PathBuf>,
+    frames: usize,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    mode: StepMode::Off,
+    record: None,
+    frames: 0,
+});
+
+/// Called by the runner before dispatching to a solver. Frames from an
+/// earlier recording in `record` are deleted.
+///
+/// # Errors
+///
+/// Returns an error if the recording directory cannot be emptied or
+/// created.
+///
+/// # Panics
+///
+/// Panics if the stepper lock is poisoned.
+pub fn configure(mode: StepMode, record: Option<PathBuf>) -> io::Result<()> {
+    if let Some(dir) = &record {
+        match fs::remove_dir_all(dir) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(err);
+            }
+            _ => {}
+        }
+        fs::create_dir_all(dir)?;
+    }
+    *STATE.lock().unwrap() = State {
+        mode,
+        record,
+        frames: 0,
+    };
+    Ok(())
+}
+
+/// Whether frames are being shown or recorded, for solvers that want to
+/// skip work that only feeds them.
+///
+/// # Panics
+///
+/// Panics if the stepper lock is poisoned.
+#[must_use]
+pub fn enabled() -> bool {
+    let state = STATE.lock().unwrap();
+    state.mode != StepMode::Off || state.record.is_some()
+}
+
+/// Saves a frame as the next image of the recording.
+fn record(dir: &Path, number: usize, frame: &Grid<char>) {
+    let path = dir.join(format!("frame-{number:05}.ppm"));
+    if let Err(err) = fs::write(&path, viz::to_ppm(frame, RECORD_SCALE)) {
+        eprintln!("{}: {err}; recording stopped", path.display());
+        STATE.lock().unwrap().record = None;
+    }
+}
+
+/// Clears the terminal and draws `state` under `label`, then waits as the
+/// step mode says, and with `--record` saves it as an image. Does nothing
+/// without either flag.
+///
+/// # Panics
+///
+/// Panics if the stepper lock is poisoned.
+pub fn frame<V: Visualizable + ?Sized>(label: &str, state: &V) {
+    let (mode, dir, number) = {
+        let mut guard = STATE.lock().unwrap();
+        guard.frames += 1;
+        (guard.mode, guard.record.clone(), guard.frames)
+    };
+    if mode == StepMode::Off && dir.is_none() {
+        return;
+    }
+    let grid = state.frame();
+    if let Some(dir) = dir {
+        record(&dir, number, &grid);
+    }
+    if mode == StepMode::Off {
+        return;
+    }
+    let text = grid.render(|&c| c);
+    let mut out = io::stdout().lock();
+    // Frames are best effort: a closed stdout should not stop the solve.
+    let _ = write!(out, "\x1b[2J\x1b[H{text}\nFrame {number}: {label}");
+    match mode {
+        StepMode::Off => {}
+        StepMode::Enter => {
+            let _ = write!(out, "  (Enter for next, q to run on) ");
+            let _ = out.flush();
+            let mut reply = String::new();
+            let read = io::stdin().lock().read_line(&mut reply);
+            // Stop stepping at end of input too, rather than spinning.
+            if matches!(read, Ok(0) | Err(_)) || reply.trim() == "q" {
+                STATE.lock().unwrap().mode = StepMode::Off;
+            }
+        }
+        StepMode::Delay(delay) => {
+            let _ = writeln!(out);
+            let _ = out.flush();
+            thread::sleep(delay);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Where every timed run is appended, one JSON object per line.
+pub const TIMINGS_PATH: &str = "./target/aoc-timings.jsonl";
+
+/// How many of the most recent runs the report's trend line shows.
+const TREND_LEN: usize = 30;
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// A single solve from `run` or `all` with `--time`.
+    Run,
+    /// The median of a `bench`.
+    Bench,
+}
+
+/// One timed solve of a part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub elapsed_ms: f64,
+    pub source: Source,
+}
+
+impl Timing {
+    /// A timing taken now.
+    #[must_use]
+    pub fn now(day: u8, part: u8, elapsed: Duration, source: Source) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            timestamp,
+            day,
+            part,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            source,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.elapsed_ms / 1000.0)
+    }
+}
+
+/// Appends `timing` to [`TIMINGS_PATH`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn record(timing: &Timing) -> io::Result<()> {
+    let path = Path::new(TIMINGS_PATH);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(timing).map_err(io::Error::other)?;
+    writeln!(file, "{line}")
+}
+
+/// Every recorded timing, oldest first. Lines that do not parse (e.g. one
+/// cut short by an interrupted write) are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn load() -> io::Result<Vec<Timing>> {
+    match fs::read_to_string(TIMINGS_PATH) {
+        Ok(text) => Ok(text
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err),
+    }
+}
+
+/// A sparkline of `times`, scaled between their minimum and maximum.
+fn trend(times: &[f64]) -> String {
+    let lo = times.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = times.iter().copied().fold(0.0, f64::max);
+    times
+        .iter()
+        .map(|&t| {
+            if hi <= lo {
+                return BARS[0];
+            }
+            // The ratio is in [0, 1], so the index is in range.
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let level = ((t - lo) / (hi - lo) * (BARS.len() - 1) as f64).round()
+                as usize;
+            BARS[level]
+        })
+        .collect()
+}
+
+/// Summarizes `timings` per day and part, optionally only for `day`.
+///
+/// Each part gets the number of runs, the best and latest times, and a
+/// trend over the most recent runs. A part is flagged when its latest time
+/// is more than `threshold` percent slower than its best. Returns the
+/// report and whether no part was flagged.
+#[must_use]
+pub fn report(
+    timings: &[Timing],
+    day: Option<u8>,
+    threshold: u32,
+) -> (String, bool) {
+    let mut by_part: BTreeMap<(u8, u8), Vec<&Timing>> = BTreeMap::new();
+    for timing in timings {
+        if day.is_none_or(|day| day == timing.day) {
+            by_part
+                .entry((timing.day, timing.part))
+                .or_default()
+                .push(timing);
+        }
+    }
+    if by_part.is_empty() {
+        return (format!("No timings recorded in {TIMINGS_PATH}.\n"), true);
+    }
+
+    let limit = 1.0 + f64::from(threshold) / 100.0;
+    let mut out = String::new();
+    let mut ok = true;
+    for ((day, part), runs) in by_part {
+        let Some(&latest) = runs.last() else {
+            continue;
+        };
+        let best = runs.iter().fold(latest, |best, &t| {
+            if t.elapsed_ms < best.elapsed_ms {
+                t
+            } else {
+                best
+            }
+        });
+        let change = (latest.elapsed_ms / best.elapsed_ms - 1.0) * 100.0;
+        let recent: Vec<f64> = runs
+            .iter()
+            .skip(runs.len().saturating_sub(TREND_LEN))
+            .map(|t| t.elapsed_ms)
+            .collect();
+        let _ = write!(
+            out,
+            "Day {day:2} part {part}: {:3} runs, best {:>10.1?}, latest \
+             {:>10.1?} ({change:+.1}%)  {}",
+            runs.len(),
+            best.elapsed(),
+            latest.elapsed(),
+            trend(&recent),
+        );
+        if latest.elapsed_ms > best.elapsed_ms * limit {
+            ok = false;
+            let _ = write!(out, "  SLOWER than best by over {threshold}%");
+        }
+        out.push('\n');
+    }
+    (out, ok)
+}
@@ -0,0 +1,289 @@
+//! A walker following turn/forward instructions over a configurable
+//! topology, for "follow these directions on the map" puzzles.
+//!
+//! ```ignore
+//! let tape = parse_tape("10R5L5R10L4R5L5")?;
+//! let mut turtle = Turtle::new(start, Direction::E);
+//! turtle.run(&tape, &mut Torus::new(&grid, |&c| c == '.', |&c| c == ' '));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+
+use crate::direction::{Coords, Direction};
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instr {
+    TurnLeft,
+    TurnRight,
+    /// Move up to this many steps, stopping early if blocked.
+    Forward(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTapeError {
+    pub at: usize,
+}
+
+impl fmt::Display for ParseTapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unexpected character at byte {}", self.at)
+    }
+}
+
+impl std::error::Error for ParseTapeError {}
+
+/// Parses a tape of `L`/`R` turns and step counts, such as `"10R5L5"` or
+/// `"R5, L5, R5"`. Commas and whitespace are ignored.
+///
+/// # Errors
+///
+/// Returns the position of any other character, or of a step count that
+/// overflows.
+pub fn parse_tape(s: &str) -> Result<Vec<Instr>, ParseTapeError> {
+    let mut tape = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((at, ch)) = chars.next() {
+        match ch {
+            'L' | 'l' => tape.push(Instr::TurnLeft),
+            'R' | 'r' => tape.push(Instr::TurnRight),
+            ',' => {}
+            _ if ch.is_whitespace() => {}
+            _ if ch.is_ascii_digit() => {
+                let mut end = at + 1;
+                while let Some(&(i, d)) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let n =
+                    s[at..end].parse().map_err(|_| ParseTapeError { at })?;
+                tape.push(Instr::Forward(n));
+            }
+            _ => return Err(ParseTapeError { at }),
+        }
+    }
+    Ok(tape)
+}
+
+/// How the turtle moves one step: the state after stepping forward from
+/// `(pos, heading)`, or `None` if the move is blocked.
+///
+/// Closures implement this, so one-off rules like cube wrapping can be
+/// written inline.
+pub trait Topology {
+    fn step(
+        &mut self,
+        pos: Coords,
+        heading: Direction,
+    ) -> Option<(Coords, Direction)>;
+}
+
+impl<F> Topology for F
+where
+    F: FnMut(Coords, Direction) -> Option<(Coords, Direction)>,
+{
+    fn step(
+        &mut self,
+        pos: Coords,
+        heading: Direction,
+    ) -> Option<(Coords, Direction)> {
+        self(pos, heading)
+    }
+}
+
+/// A grid whose edges are walls: steps off the grid or onto a cell that is
+/// not `open` are blocked.
+pub struct Bounded<'a, T, O> {
+    grid: &'a Grid<T>,
+    open: O,
+}
+
+impl<'a, T, O: Fn(&T) -> bool> Bounded<'a, T, O> {
+    pub const fn new(grid: &'a Grid<T>, open: O) -> Self {
+        Self { grid, open }
+    }
+}
+
+impl<T, O: Fn(&T) -> bool> Topology for Bounded<'_, T, O> {
+    fn step(
+        &mut self,
+        pos: Coords,
+        heading: Direction,
+    ) -> Option<(Coords, Direction)> {
+        let next = pos.step(heading)?;
+        self.grid
+            .get(next)
+            .is_some_and(|cell| (self.open)(cell))
+            .then_some((next, heading))
+    }
+}
+
+/// A grid that wraps around: stepping off the edge, or into a `void` cell,
+/// continues from the far side of the same row or column. Steps onto cells
+/// that are neither `open` nor `void` are blocked.
+pub struct Torus<'a, T, O, V> {
+    grid: &'a Grid<T>,
+    open: O,
+    void: V,
+}
+
+impl<'a, T, O, V> Torus<'a, T, O, V>
+where
+    O: Fn(&T) -> bool,
+    V: Fn(&T) -> bool,
+{
+    pub const fn new(grid: &'a Grid<T>, open: O, void: V) -> Self {
+        Self { grid, open, void }
+    }
+
+    fn on_map(&self, pos: Option<Coords>) -> Option<Coords> {
+        pos.filter(|&p| self.grid.get(p).is_some_and(|c| !(self.void)(c)))
+    }
+}
+
+impl<T, O, V> Topology for Torus<'_, T, O, V>
+where
+    O: Fn(&T) -> bool,
+    V: Fn(&T) -> bool,
+{
+    fn step(
+        &mut self,
+        pos: Coords,
+        heading: Direction,
+    ) -> Option<(Coords, Direction)> {
+        let next = self.on_map(pos.step(heading)).unwrap_or_else(|| {
+            let back = heading.opposite();
+            let mut far = pos;
+            while let Some(p) = self.on_map(far.step(back)) {
+                far = p;
+            }
+            far
+        });
+        (self.open)(&self.grid[next]).then_some((next, heading))
+    }
+}
+
+/// Wraps another topology with portals: stepping forward from a key
+/// `(pos, heading)` lands on the mapped state instead, as long as that
+/// state's cell is accepted by `open`.
+pub struct WithPortals<'a, P, S, O> {
+    inner: P,
+    portals: &'a HashMap<(Coords, Direction), (Coords, Direction), S>,
+    open: O,
+}
+
+impl<'a, P, S, O> WithPortals<'a, P, S, O>
+where
+    P: Topology,
+    S: BuildHasher,
+    O: Fn(Coords) -> bool,
+{
+    pub const fn new(
+        inner: P,
+        portals: &'a HashMap<(Coords, Direction), (Coords, Direction), S>,
+        open: O,
+    ) -> Self {
+        Self {
+            inner,
+            portals,
+            open,
+        }
+    }
+}
+
+impl<P, S, O> Topology for WithPortals<'_, P, S, O>
+where
+    P: Topology,
+    S: BuildHasher,
+    O: Fn(Coords) -> bool,
+{
+    fn step(
+        &mut self,
+        pos: Coords,
+        heading: Direction,
+    ) -> Option<(Coords, Direction)> {
+        match self.portals.get(&(pos, heading)) {
+            Some(&(dest, facing)) => {
+                (self.open)(dest).then_some((dest, facing))
+            }
+            None => self.inner.step(pos, heading),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Turtle {
+    pub pos: Coords,
+    pub heading: Direction,
+}
+
+impl Turtle {
+    #[must_use]
+    pub const fn new(pos: Coords, heading: Direction) -> Self {
+        Self { pos, heading }
+    }
+
+    /// Carries out one instruction, returning the number of steps actually
+    /// taken.
+    pub fn execute<P: Topology>(
+        &mut self,
+        instr: Instr,
+        topo: &mut P,
+    ) -> usize {
+        match instr {
+            Instr::TurnLeft => {
+                self.heading = self.heading.turn_left();
+                0
+            }
+            Instr::TurnRight => {
+                self.heading = self.heading.turn_right();
+                0
+            }
+            Instr::Forward(n) => {
+                for taken in 0..n {
+                    let Some((pos, heading)) =
+                        topo.step(self.pos, self.heading)
+                    else {
+                        return taken;
+                    };
+                    self.pos = pos;
+                    self.heading = heading;
+                }
+                n
+            }
+        }
+    }
+
+    /// Runs the whole tape.
+    pub fn run<P: Topology>(&mut self, tape: &[Instr], topo: &mut P) {
+        for &instr in tape {
+            self.execute(instr, topo);
+        }
+    }
+
+    /// Runs the whole tape, returning every state visited, starting with the
+    /// current one.
+    pub fn trace<P: Topology>(
+        &mut self,
+        tape: &[Instr],
+        topo: &mut P,
+    ) -> Vec<Self> {
+        let mut trail = vec![*self];
+        let mut one_step = |pos, heading| {
+            let next = topo.step(pos, heading);
+            if let Some((pos, heading)) = next {
+                trail.push(Self { pos, heading });
+            }
+            next
+        };
+        for &instr in tape {
+            self.execute(instr, &mut one_step);
+        }
+        trail
+    }
+}
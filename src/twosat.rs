@@ -0,0 +1,147 @@
+use std::ops::Not;
+
+use crate::graph::strongly_connected_components;
+
+/// A variable or its negation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lit {
+    pub var: usize,
+    /// Whether the literal holds when `var` is true.
+    pub positive: bool,
+}
+
+impl Lit {
+    #[must_use]
+    pub const fn pos(var: usize) -> Self {
+        Self {
+            var,
+            positive: true,
+        }
+    }
+
+    #[must_use]
+    pub const fn neg(var: usize) -> Self {
+        Self {
+            var,
+            positive: false,
+        }
+    }
+
+    /// The literal whose variable must take `value` for it to hold.
+    #[must_use]
+    pub const fn new(var: usize, value: bool) -> Self {
+        Self {
+            var,
+            positive: value,
+        }
+    }
+
+    /// The literal's vertex in the implication graph.
+    const fn node(self) -> usize {
+        2 * self.var + if self.positive { 0 } else { 1 }
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self {
+            var: self.var,
+            positive: !self.positive,
+        }
+    }
+}
+
+/// A 2-SAT instance: a conjunction of clauses of at most two literals.
+///
+/// Clauses are stored as the implication graph, with an edge `¬a → b` and
+/// `¬b → a` for each clause `a ∨ b`. [`solve`](Self::solve) finds its
+/// strongly connected components; the instance is satisfiable unless some
+/// variable shares a component with its negation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TwoSat {
+    implications: Vec<Vec<usize>>,
+}
+
+impl TwoSat {
+    /// An instance over the variables `0..num_vars` with no clauses.
+    #[must_use]
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            implications: vec![vec![]; 2 * num_vars],
+        }
+    }
+
+    #[must_use]
+    pub const fn num_vars(&self) -> usize {
+        self.implications.len() / 2
+    }
+
+    /// Adds a fresh variable, returning it.
+    pub fn add_var(&mut self) -> usize {
+        self.implications.extend([vec![], vec![]]);
+        self.num_vars() - 1
+    }
+
+    /// Requires `a ∨ b`. Variables beyond [`num_vars`](Self::num_vars) are
+    /// added as needed.
+    pub fn add_clause(&mut self, a: Lit, b: Lit) {
+        let needed = 2 * (a.var.max(b.var) + 1);
+        if self.implications.len() < needed {
+            self.implications.resize_with(needed, Vec::new);
+        }
+        self.implications[(!a).node()].push(b.node());
+        self.implications[(!b).node()].push(a.node());
+    }
+
+    /// Requires `a → b`.
+    pub fn implies(&mut self, a: Lit, b: Lit) {
+        self.add_clause(!a, b);
+    }
+
+    /// Requires `a` to hold.
+    pub fn force(&mut self, a: Lit) {
+        self.add_clause(a, a);
+    }
+
+    /// Requires exactly one of `a` and `b` to hold.
+    pub fn exactly_one(&mut self, a: Lit, b: Lit) {
+        self.add_clause(a, b);
+        self.add_clause(!a, !b);
+    }
+
+    /// Requires at most one of `lits` to hold, with a clause per pair.
+    pub fn at_most_one(&mut self, lits: &[Lit]) {
+        for (i, &a) in lits.iter().enumerate() {
+            for &b in &lits[i + 1..] {
+                self.add_clause(!a, !b);
+            }
+        }
+    }
+
+    /// A satisfying assignment, indexed by variable, or `None` if there is
+    /// none.
+    #[must_use]
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let components =
+            strongly_connected_components(self.implications.len(), |v| {
+                self.implications[v].iter().copied()
+            });
+        let mut component = vec![0; self.implications.len()];
+        for (i, members) in components.iter().enumerate() {
+            for &v in members {
+                component[v] = i;
+            }
+        }
+        // Components come in reverse topological order, so a literal in an
+        // earlier component cannot imply its negation.
+        (0..self.num_vars())
+            .map(|var| {
+                let t = component[Lit::pos(var).node()];
+                let f = component[Lit::neg(var).node()];
+                (t != f).then_some(t < f)
+            })
+            .collect()
+    }
+}
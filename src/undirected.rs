@@ -0,0 +1,435 @@
+use std::fmt;
+use std::ops::Add;
+
+use crate::bitset::BitSet;
+use crate::interner::{self, AdjList, Labeled, StringInterner};
+
+/// An undirected weighted graph over dense `usize` ids, stored as adjacency
+/// lists that are kept symmetric: every edge is recorded at both ends.
+///
+/// A self-loop is recorded once, at its single endpoint.
+///
+/// Removing a vertex tombstones its id rather than renumbering: the slot
+/// keeps an empty edge list, every edge into it is dropped, and no other
+/// id changes. Searches over [`neighbors`](Self::neighbors) therefore never
+/// reach a removed vertex, and ids held elsewhere (e.g. in a
+/// `StringInterner`) stay valid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UndirectedGraph<W> {
+    adj: AdjList<W>,
+    removed: BitSet,
+}
+
+impl<W: Clone> UndirectedGraph<W> {
+    /// A graph on the vertices `0..n` with no edges.
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Self {
+            adj: (0..n).map(|_| vec![]).collect(),
+            removed: BitSet::new(),
+        }
+    }
+
+    /// Symmetrizes a directed graph. An edge listed in both directions
+    /// becomes a single undirected edge, weighted as listed from the
+    /// smaller id.
+    ///
+    /// This copies every edge to both ends; to only traverse the graph,
+    /// [`as_undirected_view`] is cheaper.
+    #[must_use]
+    pub fn from_directed(directed: &AdjList<W>) -> Self {
+        let mut graph = Self::new(directed.len());
+        for (a, edges) in directed.iter().enumerate() {
+            for (b, w) in edges {
+                let listed_back = directed
+                    .get(*b)
+                    .is_some_and(|back| back.iter().any(|(x, _)| *x == a));
+                if a <= *b || !listed_back {
+                    graph.add_edge(a, *b, w.clone());
+                }
+            }
+        }
+        graph
+    }
+
+    /// Adds the edge `a - b` in both directions, growing the vertex set if
+    /// needed. Adding an edge at a removed vertex revives it.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: W) {
+        self.removed.remove(a);
+        self.removed.remove(b);
+        let n = self.adj.len().max(a + 1).max(b + 1);
+        self.adj.resize_with(n, Vec::new);
+        if a != b {
+            self.adj[b].push((a, weight.clone()));
+        }
+        self.adj[a].push((b, weight));
+    }
+}
+
+impl<W> UndirectedGraph<W> {
+    /// Number of vertex ids in use, including removed ones.
+    #[must_use]
+    pub const fn num_vertices(&self) -> usize {
+        self.adj.len()
+    }
+
+    #[must_use]
+    pub fn contains_vertex(&self, v: usize) -> bool {
+        v < self.adj.len() && !self.removed.contains(v)
+    }
+
+    /// Ids of the vertices that have not been removed.
+    pub fn vertices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.adj.len()).filter(|&v| !self.removed.contains(v))
+    }
+
+    /// Removes every edge between `a` and `b`, returning whether there was
+    /// one.
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> bool {
+        let mut found = false;
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(edges) = self.adj.get_mut(from) {
+                let before = edges.len();
+                edges.retain(|(x, _)| *x != to);
+                found |= edges.len() != before;
+            }
+        }
+        found
+    }
+
+    /// Removes `v` and all its edges, tombstoning its id.
+    pub fn remove_vertex(&mut self, v: usize) {
+        let Some(edges) = self.adj.get_mut(v) else {
+            return;
+        };
+        for (nbr, _) in std::mem::take(edges) {
+            self.adj[nbr].retain(|(x, _)| *x != v);
+        }
+        self.removed.insert(v);
+    }
+
+    /// Number of edge endpoints at `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not a vertex.
+    #[must_use]
+    pub fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Neighbors of `v` with edge weights, in insertion order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not a vertex.
+    #[must_use]
+    pub fn neighbors(&self, v: usize) -> &[(usize, W)] {
+        &self.adj[v]
+    }
+
+    /// Each edge once, as `(a, b, weight)` with `a <= b`.
+    pub fn edges_unordered(&self) -> impl Iterator<Item = (usize, usize, &W)> {
+        self.adj.iter().enumerate().flat_map(|(a, edges)| {
+            edges
+                .iter()
+                .filter(move |(b, _)| a <= *b)
+                .map(move |(b, w)| (a, *b, w))
+        })
+    }
+
+    /// Displays the graph using the labels in `interner` in place of ids.
+    /// Each edge appears at both ends, and removed vertices are left out.
+    #[must_use]
+    pub fn debug_with_labels<'a>(
+        &'a self,
+        interner: &'a StringInterner,
+    ) -> Labeled<'a, W> {
+        Labeled::new(interner, &self.adj, Some(&self.removed))
+    }
+
+    /// Vertices whose removal splits their connected component, in
+    /// increasing order.
+    #[must_use]
+    pub fn articulation_points(&self) -> Vec<usize> {
+        self.low_link().0
+    }
+
+    /// Edges whose removal splits their connected component, as `(a, b)`
+    /// with `a < b`, in increasing order. An edge with a parallel copy is
+    /// never a bridge.
+    #[must_use]
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.low_link().1
+    }
+
+    /// Tarjan's low-link computation, as an iterative DFS so that long
+    /// corridors do not overflow the stack. Returns the articulation points
+    /// and bridges.
+    fn low_link(&self) -> (Vec<usize>, Vec<(usize, usize)>) {
+        /// A vertex on the DFS stack.
+        struct Frame {
+            v: usize,
+            parent: Option<usize>,
+            next: usize,
+            /// Whether the edge back to `parent` has been skipped; any
+            /// further edges to it are parallel copies, i.e. back edges.
+            skipped_parent: bool,
+        }
+
+        let n = self.adj.len();
+        let mut disc = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut timer = 0;
+        let mut cut = BitSet::with_len(n);
+        let mut bridges = vec![];
+        for root in self.vertices() {
+            if disc[root] != usize::MAX {
+                continue;
+            }
+            disc[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            let mut stack = vec![Frame {
+                v: root,
+                parent: None,
+                next: 0,
+                skipped_parent: false,
+            }];
+            while let Some(frame) = stack.last_mut() {
+                let v = frame.v;
+                if let Some(&(u, _)) = self.adj[v].get(frame.next) {
+                    frame.next += 1;
+                    if u == v {
+                        continue;
+                    }
+                    if frame.parent == Some(u) && !frame.skipped_parent {
+                        frame.skipped_parent = true;
+                        continue;
+                    }
+                    if disc[u] == usize::MAX {
+                        disc[u] = timer;
+                        low[u] = timer;
+                        timer += 1;
+                        stack.push(Frame {
+                            v: u,
+                            parent: Some(v),
+                            next: 0,
+                            skipped_parent: false,
+                        });
+                    } else {
+                        low[v] = low[v].min(disc[u]);
+                    }
+                    continue;
+                }
+                let parent = frame.parent;
+                stack.pop();
+                let Some(p) = parent else {
+                    continue;
+                };
+                low[p] = low[p].min(low[v]);
+                if low[v] > disc[p] {
+                    bridges.push((p.min(v), p.max(v)));
+                }
+                if p == root {
+                    root_children += 1;
+                } else if low[v] >= disc[p] {
+                    cut.insert(p);
+                }
+            }
+            if root_children >= 2 {
+                cut.insert(root);
+            }
+        }
+        bridges.sort_unstable();
+        (cut.iter().collect(), bridges)
+    }
+
+    /// The symmetric adjacency lists, usable wherever a directed graph is.
+    /// Removed vertices appear as empty lists.
+    #[must_use]
+    pub fn into_directed(self) -> AdjList<W> {
+        self.adj
+    }
+}
+
+/// A directed graph read as undirected, without copying its edges: each
+/// vertex's neighbors are its own edges followed by those listed into it.
+/// Built by [`as_undirected_view`].
+///
+/// Unlike [`UndirectedGraph::from_directed`], an edge listed in both
+/// directions is not merged, so it appears twice at each end. A self-loop
+/// appears once.
+#[derive(Debug, Clone)]
+pub struct UndirectedView<'a, W> {
+    directed: &'a AdjList<W>,
+    /// The edges into `v`, other than self-loops, are
+    /// `reverse[offsets[v]..offsets[v + 1]]`, each as `(u, i)` for the edge
+    /// `directed[u][i]`.
+    offsets: Vec<usize>,
+    reverse: Vec<(usize, usize)>,
+}
+
+/// Views `directed` as undirected. The reverse edges are indexed once, in
+/// O(V + E), holding two ids per edge rather than a copy of its weight.
+#[must_use]
+pub fn as_undirected_view<W>(directed: &AdjList<W>) -> UndirectedView<'_, W> {
+    let n = directed
+        .iter()
+        .flatten()
+        .map(|&(b, _)| b + 1)
+        .fold(directed.len(), usize::max);
+    let mut offsets = vec![0; n + 1];
+    for (a, edges) in directed.iter().enumerate() {
+        for &(b, _) in edges {
+            if a != b {
+                offsets[b + 1] += 1;
+            }
+        }
+    }
+    for v in 0..n {
+        offsets[v + 1] += offsets[v];
+    }
+    let mut next = offsets.clone();
+    let mut reverse = vec![(0, 0); offsets[n]];
+    for (a, edges) in directed.iter().enumerate() {
+        for (i, &(b, _)) in edges.iter().enumerate() {
+            if a != b {
+                reverse[next[b]] = (a, i);
+                next[b] += 1;
+            }
+        }
+    }
+    UndirectedView {
+        directed,
+        offsets,
+        reverse,
+    }
+}
+
+impl<'a, W> UndirectedView<'a, W> {
+    /// Number of vertex ids, including those only listed as edge targets.
+    #[must_use]
+    pub const fn num_vertices(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn out_edges(&self, v: usize) -> &'a [(usize, W)] {
+        self.directed.get(v).map_or(&[], Vec::as_slice)
+    }
+
+    /// Number of edge endpoints at `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not a vertex.
+    #[must_use]
+    pub fn degree(&self, v: usize) -> usize {
+        self.out_edges(v).len() + self.offsets[v + 1] - self.offsets[v]
+    }
+
+    /// Neighbors of `v` with edge weights: its own edges in order, then
+    /// those listed into it by increasing source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not a vertex.
+    pub fn neighbors(
+        &self,
+        v: usize,
+    ) -> impl Iterator<Item = (usize, &'a W)> + '_ {
+        let incoming = &self.reverse[self.offsets[v]..self.offsets[v + 1]];
+        let directed = self.directed;
+        self.out_edges(v)
+            .iter()
+            .map(|(b, w)| (*b, w))
+            .chain(incoming.iter().map(move |&(a, i)| (a, &directed[a][i].1)))
+    }
+}
+
+/// A corridor collapsed by
+/// [`contract_degree_two_chains`](UndirectedGraph::contract_degree_two_chains):
+/// the path `ends.0, interior.., ends.1` became a single edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chain {
+    pub ends: (usize, usize),
+    /// The contracted vertices, in order from `ends.0`.
+    pub interior: Vec<usize>,
+}
+
+impl<W: Clone + Add<Output = W>> UndirectedGraph<W> {
+    /// Collapses every corridor into a single edge weighted with the sum of
+    /// its edges. A corridor vertex has degree two and two distinct
+    /// neighbors, neither of them itself.
+    ///
+    /// Contracted vertices are removed (tombstoned), so the remaining ids
+    /// are unchanged. Each new edge is described by a [`Chain`] for mapping
+    /// paths back; edges not through any corridor are kept as they are and
+    /// get no chain. A cycle made only of corridor vertices keeps its
+    /// smallest vertex, which gets a self-loop.
+    #[must_use]
+    pub fn contract_degree_two_chains(&self) -> (Self, Vec<Chain>) {
+        let n = self.adj.len();
+        let is_corridor = |v: usize| match self.adj[v].as_slice() {
+            [(a, _), (b, _)] => a != b && *a != v && *b != v,
+            _ => false,
+        };
+        let mut out = Self::new(n);
+        out.removed = self.removed.clone();
+        let mut chains = vec![];
+        // Corridor vertices already walked through, or kept to anchor a
+        // cycle.
+        let mut seen = BitSet::with_len(n);
+
+        let anchors = (0..n).filter(|&v| !is_corridor(v));
+        let cycles = (0..n).filter(|&v| is_corridor(v));
+        for start in anchors.chain(cycles) {
+            if is_corridor(start) && !seen.insert(start) {
+                continue;
+            }
+            for (first, weight) in &self.adj[start] {
+                let (mut prev, mut cur) = (start, *first);
+                if !is_corridor(cur) {
+                    // Add a plain edge from its smaller end only.
+                    if start <= cur {
+                        out.add_edge(start, cur, weight.clone());
+                    }
+                    continue;
+                }
+                if !seen.insert(cur) {
+                    continue;
+                }
+                let mut total = weight.clone();
+                let mut interior = vec![];
+                while is_corridor(cur) && cur != start {
+                    interior.push(cur);
+                    seen.insert(cur);
+                    let [(a, wa), (b, wb)] = self.adj[cur].as_slice() else {
+                        break;
+                    };
+                    let (next, w) = if *a == prev { (b, wb) } else { (a, wa) };
+                    total = total + w.clone();
+                    (prev, cur) = (cur, *next);
+                }
+                for &v in &interior {
+                    out.removed.insert(v);
+                }
+                out.add_edge(start, cur, total);
+                chains.push(Chain {
+                    ends: (start, cur),
+                    interior,
+                });
+            }
+        }
+        (out, chains)
+    }
+}
+
+/// One vertex per line as `id: nbr (weight), nbr (weight)`; see
+/// [`UndirectedGraph::debug_with_labels`] for labeled output.
+impl<W: fmt::Display> fmt::Display for UndirectedGraph<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        interner::write_adjacency(f, &self.adj, Some(&self.removed), |v| v)
+    }
+}
@@ -0,0 +1,208 @@
+use std::hash::Hash;
+
+use crate::hashing::FastMap;
+
+/// A commutative group of offsets between elements of an [`UpTreeW`].
+///
+/// `()` is the trivial group, which turns [`UpTreeW`] into a plain
+/// union-find ([`UpTree`]); `bool` (under xor) tracks parity.
+pub trait Offset: Copy + PartialEq {
+    const ZERO: Self;
+
+    #[must_use]
+    fn plus(self, other: Self) -> Self;
+
+    #[must_use]
+    fn minus(self, other: Self) -> Self;
+}
+
+impl Offset for () {
+    const ZERO: Self = ();
+
+    fn plus(self, (): Self) -> Self {}
+
+    fn minus(self, (): Self) -> Self {}
+}
+
+impl Offset for bool {
+    const ZERO: Self = false;
+
+    fn plus(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn minus(self, other: Self) -> Self {
+        self ^ other
+    }
+}
+
+macro_rules! impl_offset_int {
+    ($($t:ty),*) => {$(
+        impl Offset for $t {
+            const ZERO: Self = 0;
+
+            fn plus(self, other: Self) -> Self {
+                self + other
+            }
+
+            fn minus(self, other: Self) -> Self {
+                self - other
+            }
+        }
+    )*};
+}
+
+impl_offset_int!(i32, i64, i128, isize);
+
+/// Weighted union-find: every element carries an offset `W` relative to the
+/// root of its set, so the difference between any two elements in the same
+/// set is known.
+///
+/// Elements are added on first use.
+#[derive(Debug, Clone)]
+pub struct UpTreeW<T, W> {
+    index: FastMap<T, usize>,
+    elements: Vec<T>,
+    parent: Vec<usize>,
+    offset: Vec<W>,
+    size: Vec<usize>,
+    num_sets: usize,
+}
+
+/// Plain union-find.
+pub type UpTree<T> = UpTreeW<T, ()>;
+
+impl<T: Clone + Eq + Hash, W: Offset> UpTreeW<T, W> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            index: FastMap::default(),
+            elements: vec![],
+            parent: vec![],
+            offset: vec![],
+            size: vec![],
+            num_sets: 0,
+        }
+    }
+
+    fn idx(&mut self, t: &T) -> usize {
+        if let Some(&i) = self.index.get(t) {
+            return i;
+        }
+        let i = self.elements.len();
+        self.index.insert(t.clone(), i);
+        self.elements.push(t.clone());
+        self.parent.push(i);
+        self.offset.push(W::ZERO);
+        self.size.push(1);
+        self.num_sets += 1;
+        i
+    }
+
+    /// Root of `i` and the offset of `i` relative to it, compressing the
+    /// path on the way.
+    fn find_idx(&mut self, i: usize) -> (usize, W) {
+        let mut path = vec![];
+        let mut root = i;
+        while self.parent[root] != root {
+            path.push(root);
+            root = self.parent[root];
+        }
+        for &v in path.iter().rev() {
+            let p = self.parent[v];
+            if p != root {
+                self.offset[v] = self.offset[v].plus(self.offset[p]);
+                self.parent[v] = root;
+            }
+        }
+        (root, if i == root { W::ZERO } else { self.offset[i] })
+    }
+
+    pub fn insert(&mut self, t: &T) {
+        self.idx(t);
+    }
+
+    /// The representative of the set containing `t`.
+    pub fn find(&mut self, t: &T) -> T {
+        let i = self.idx(t);
+        let (root, _) = self.find_idx(i);
+        self.elements[root].clone()
+    }
+
+    /// Records that `value(b) - value(a) == w`, merging their sets.
+    ///
+    /// # Errors
+    ///
+    /// If `a` and `b` are already in the same set with a different offset,
+    /// nothing changes and the existing offset is returned.
+    pub fn union_with_offset(&mut self, a: &T, b: &T, w: W) -> Result<(), W> {
+        let (ia, ib) = (self.idx(a), self.idx(b));
+        let (ra, oa) = self.find_idx(ia);
+        let (rb, ob) = self.find_idx(ib);
+        if ra == rb {
+            let existing = ob.minus(oa);
+            return if existing == w { Ok(()) } else { Err(existing) };
+        }
+        if self.size[ra] >= self.size[rb] {
+            self.parent[rb] = ra;
+            self.offset[rb] = oa.plus(w).minus(ob);
+            self.size[ra] += self.size[rb];
+        } else {
+            self.parent[ra] = rb;
+            self.offset[ra] = ob.minus(oa).minus(w);
+            self.size[rb] += self.size[ra];
+        }
+        self.num_sets -= 1;
+        Ok(())
+    }
+
+    /// `value(b) - value(a)`, if `a` and `b` are in the same set.
+    pub fn relative_offset(&mut self, a: &T, b: &T) -> Option<W> {
+        let (ia, ib) = (self.idx(a), self.idx(b));
+        let (ra, oa) = self.find_idx(ia);
+        let (rb, ob) = self.find_idx(ib);
+        (ra == rb).then(|| ob.minus(oa))
+    }
+
+    pub fn same_set(&mut self, a: &T, b: &T) -> bool {
+        self.relative_offset(a, b).is_some()
+    }
+
+    /// Number of elements in the set containing `t`.
+    pub fn set_size(&mut self, t: &T) -> usize {
+        let i = self.idx(t);
+        let (root, _) = self.find_idx(i);
+        self.size[root]
+    }
+
+    #[must_use]
+    pub const fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl<T: Clone + Eq + Hash> UpTreeW<T, ()> {
+    /// Merges the sets containing `a` and `b`. Returns `false` if they were
+    /// already in the same set.
+    pub fn union(&mut self, a: &T, b: &T) -> bool {
+        let was_separate = !self.same_set(a, b);
+        let _ = self.union_with_offset(a, b, ());
+        was_separate
+    }
+}
+
+impl<T: Clone + Eq + Hash, W: Offset> Default for UpTreeW<T, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
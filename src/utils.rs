@@ -0,0 +1,883 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{ControlFlow, Range};
+use std::str::FromStr;
+
+use rayon::prelude::*;
+
+fn digit_runs(line: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let mut start = i;
+        if signed
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(&line[start..i])
+    })
+}
+
+/// Extracts every integer in `line`, ignoring any surrounding text.
+///
+/// A `-` directly before a number is treated as a sign unless it follows a
+/// letter or digit, so `"x=-3"` yields `-3` but `"1-3"` yields `1, 3`.
+///
+/// # Panics
+///
+/// Panics if a number does not fit in an `i64`.
+#[must_use]
+pub fn ints(line: &str) -> Vec<i64> {
+    digit_runs(line, true)
+        .map(|s| s.parse().unwrap_or_else(|_| panic!("{s} overflows i64")))
+        .collect()
+}
+
+/// Extracts every run of digits in `line`, ignoring signs.
+///
+/// # Panics
+///
+/// Panics if a number does not fit in a `u64`.
+#[must_use]
+pub fn uints(line: &str) -> Vec<u64> {
+    digit_runs(line, false)
+        .map(|s| s.parse().unwrap_or_else(|_| panic!("{s} overflows u64")))
+        .collect()
+}
+
+/// Parses every whitespace-separated token of `line` as a `T`.
+///
+/// # Errors
+///
+/// Returns the first parse error encountered.
+pub fn parse_split_whitespace<T: FromStr>(
+    line: &str,
+) -> Result<Vec<T>, T::Err> {
+    line.split_whitespace().map(str::parse).collect()
+}
+
+/// Parses every `sep`-separated, trimmed, non-empty token of `line` as a `T`.
+///
+/// # Errors
+///
+/// Returns the first parse error encountered.
+pub fn parse_split<T: FromStr>(
+    line: &str,
+    sep: &str,
+) -> Result<Vec<T>, T::Err> {
+    line.split(sep)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Splits `input` into blocks of lines separated by one or more blank
+/// lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = input.lines();
+    std::iter::from_fn(move || {
+        let mut block = vec![];
+        for line in lines.by_ref() {
+            if line.trim().is_empty() {
+                if block.is_empty() {
+                    continue;
+                }
+                return Some(block);
+            }
+            block.push(line);
+        }
+        if block.is_empty() { None } else { Some(block) }
+    })
+}
+
+/// Splits `input` into blank-line-separated blocks and parses each one.
+pub fn parse_blocks<'a, T, F>(input: &'a str, parse_block: F) -> Vec<T>
+where
+    F: FnMut(Vec<&'a str>) -> T,
+{
+    blocks(input).map(parse_block).collect()
+}
+
+/// Collapses runs of equal items into `(item, run_length)` pairs, so
+/// `"aaabcc"` becomes `[('a', 3), ('b', 1), ('c', 2)]`.
+pub fn run_length_encode<T, I>(iter: I) -> Vec<(T, usize)>
+where
+    T: PartialEq,
+    I: IntoIterator<Item = T>,
+{
+    let mut runs: Vec<(T, usize)> = vec![];
+    for item in iter {
+        match runs.last_mut() {
+            Some((last, n)) if *last == item => *n += 1,
+            _ => runs.push((item, 1)),
+        }
+    }
+    runs
+}
+
+/// Splits `iter` into maximal runs of consecutive items sharing the same
+/// `key_fn`, returning each key with the items of its run.
+pub fn group_consecutive_by<T, K, I, F>(
+    iter: I,
+    mut key_fn: F,
+) -> Vec<(K, Vec<T>)>
+where
+    K: PartialEq,
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> K,
+{
+    let mut groups: Vec<(K, Vec<T>)> = vec![];
+    for item in iter {
+        let key = key_fn(&item);
+        match groups.last_mut() {
+            Some((last, group)) if *last == key => group.push(item),
+            _ => groups.push((key, vec![item])),
+        }
+    }
+    groups
+}
+
+/// Running totals of `iter` with a leading zero, so the sum of items
+/// `i..j` is `prefix[j] - prefix[i]`.
+pub fn prefix_sums<I: IntoIterator<Item = i64>>(iter: I) -> Vec<i64> {
+    let mut out = vec![0];
+    let mut total = 0;
+    for x in iter {
+        total += x;
+        out.push(total);
+    }
+    out
+}
+
+/// Sum of the items in `range`, given the output of [`prefix_sums`].
+///
+/// # Panics
+///
+/// Panics if `range` runs past the end of the summed items.
+#[must_use]
+pub fn range_sum(prefix: &[i64], range: Range<usize>) -> i64 {
+    prefix[range.end] - prefix[range.start]
+}
+
+/// Midpoint of `lo < hi`, rounded down, without overflowing.
+const fn mid_i64(lo: i64, hi: i64) -> i64 {
+    lo.saturating_add_unsigned(hi.abs_diff(lo) / 2)
+}
+
+/// The first `x` in `lo..hi` for which `pred` is false, or `hi` if there is
+/// none, assuming `pred` is true on a prefix of the range and false after.
+///
+/// An empty range, including `lo > hi`, gives `lo` without calling `pred`.
+pub fn partition_point_i64<F>(mut lo: i64, mut hi: i64, mut pred: F) -> i64
+where
+    F: FnMut(i64) -> bool,
+{
+    while lo < hi {
+        let mid = mid_i64(lo, hi);
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// The smallest `x` in `lo..=hi` satisfying `pred`, assuming `pred` is
+/// monotone (false, then true from some point on). `None` if even `hi`
+/// does not satisfy it or if `lo > hi`.
+pub fn binary_search_min_satisfying<F>(
+    mut lo: i64,
+    mut hi: i64,
+    mut pred: F,
+) -> Option<i64>
+where
+    F: FnMut(i64) -> bool,
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    while lo < hi {
+        let mid = mid_i64(lo, hi);
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some(lo)
+}
+
+/// Applies `step` to `state` `n` times, detecting when the sequence of
+/// states starts repeating and skipping ahead by whole cycles, so `n` can be
+/// astronomically large.
+///
+/// # Panics
+///
+/// Panics if the states before the cycle do not fit in memory.
+pub fn nth_iterate<T, F>(state: T, n: u64, mut step: F) -> T
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut seen: HashMap<T, u64> = HashMap::new();
+    let mut history = vec![];
+    let mut cur = state;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&cur) {
+            let len = i - start;
+            let offset = (n - start) % len;
+            return history.swap_remove(
+                usize::try_from(start + offset)
+                    .expect("history fits in memory"),
+            );
+        }
+        seen.insert(cur.clone(), i);
+        history.push(cur.clone());
+        cur = step(&cur);
+    }
+    cur
+}
+
+/// Maps sparse coordinates onto dense indices: returns the distinct values
+/// in increasing order, and the index of each in that list.
+///
+/// To keep the gaps between values visible after compression (e.g. to tell
+/// touching rectangles from separated ones), include `v + 1` alongside
+/// each `v`.
+pub fn compress_coords(
+    values: impl IntoIterator<Item = i64>,
+) -> (Vec<i64>, HashMap<i64, usize>) {
+    let mut sorted: Vec<i64> = values.into_iter().collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let index = sorted.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    (sorted, index)
+}
+
+/// Compresses each axis of `points` independently with [`compress_coords`].
+///
+/// Returns the sorted distinct `x`s and `y`s, and each point's dense
+/// `(x, y)` indices in input order, so a grid of size `xs.len()` by
+/// `ys.len()` can stand in for the sparse plane.
+pub fn compress_coords_2d(
+    points: impl IntoIterator<Item = (i64, i64)>,
+) -> (Vec<i64>, Vec<i64>, Vec<(usize, usize)>) {
+    let points: Vec<(i64, i64)> = points.into_iter().collect();
+    let (xs, x_index) = compress_coords(points.iter().map(|&(x, _)| x));
+    let (ys, y_index) = compress_coords(points.iter().map(|&(_, y)| y));
+    let dense = points
+        .iter()
+        .map(|(x, y)| (x_index[x], y_index[y]))
+        .collect();
+    (xs, ys, dense)
+}
+
+/// Number of candidates each worker thread checks per batch in
+/// [`par_find_first`].
+const PAR_CHUNK: u64 = 1 << 14;
+
+/// The smallest value in `range` satisfying `pred`, checked in parallel.
+///
+/// The range is scanned in batches of consecutive chunks, one per thread,
+/// so the threads all work near the front of the range rather than one of
+/// them crawling from the start alone. Within a batch, work on values past
+/// a match is cancelled, and later batches are never started, so the
+/// result is the same as `range.find(pred)`.
+pub fn par_find_first<P>(range: Range<u64>, pred: P) -> Option<u64>
+where
+    P: Fn(u64) -> bool + Sync,
+{
+    let threads = rayon::current_num_threads() as u64;
+    let batch = PAR_CHUNK.saturating_mul(threads.max(1));
+    let mut lo = range.start;
+    while lo < range.end {
+        let hi = lo.saturating_add(batch).min(range.end);
+        if let Some(found) = (lo..hi).into_par_iter().find_first(|&n| pred(n)) {
+            return Some(found);
+        }
+        lo = hi;
+    }
+    None
+}
+
+/// Number of positions at which `a` and `b` have different chars, or
+/// `None` if they differ in length (counted in chars, not bytes).
+#[must_use]
+pub fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    let (mut a, mut b) = (a.chars(), b.chars());
+    let mut dist = 0;
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => dist += usize::from(x != y),
+            (None, None) => return Some(dist),
+            _ => return None,
+        }
+    }
+}
+
+/// Whether `a` and `b` have the same length and differ at exactly one char.
+#[must_use]
+pub fn differs_by_exactly_one(a: &str, b: &str) -> bool {
+    hamming_distance(a, b) == Some(1)
+}
+
+/// The chars that `a` and `b` agree on position by position, in order,
+/// e.g. `"fghij"` and `"fguij"` give `"fgij"`. Stops at the shorter string.
+#[must_use]
+pub fn chars_in_common(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .filter_map(|(x, y)| (x == y).then_some(x))
+        .collect()
+}
+
+/// The longest prefix shared by `a` and `b`, as a slice of `a`.
+#[must_use]
+pub fn longest_common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|&((_, x), y)| x != y)
+        .map_or_else(|| a.len().min(b.len()), |((i, _), _)| i);
+    &a[..len]
+}
+
+/// The longest suffix shared by `a` and `b`, as a slice of `a`.
+#[must_use]
+pub fn longest_common_suffix<'a>(a: &'a str, b: &str) -> &'a str {
+    let start = a
+        .char_indices()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|&((_, x), y)| x == y)
+        .last()
+        .map_or(a.len(), |((i, _), _)| i);
+    &a[start..]
+}
+
+struct HeapEntry<K, V> {
+    key: K,
+    seq: usize,
+    value: V,
+}
+
+impl<K: Ord, V> PartialEq for HeapEntry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.seq == other.seq
+    }
+}
+
+impl<K: Ord, V> Eq for HeapEntry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for HeapEntry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for HeapEntry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the std max-heap pops the smallest key, oldest first.
+        other.key.cmp(&self.key).then(other.seq.cmp(&self.seq))
+    }
+}
+
+/// A min-priority queue keyed by `K`, carrying arbitrary (unordered) values.
+///
+/// Entries with equal keys pop in insertion order.
+pub struct MinHeap<K, V> {
+    heap: BinaryHeap<HeapEntry<K, V>>,
+    seq: usize,
+}
+
+impl<K: Ord, V> MinHeap<K, V> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            seq: 0,
+        }
+    }
+
+    pub fn push(&mut self, key: K, value: V) {
+        self.heap.push(HeapEntry {
+            key,
+            seq: self.seq,
+            value,
+        });
+        self.seq += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.heap.pop().map(|e| (e.key, e.value))
+    }
+
+    /// Pops entries until one for which `is_current` holds, discarding the
+    /// stale ones. This is the lazy-deletion pattern used in Dijkstra in
+    /// place of decrease-key.
+    pub fn pop_current<F>(&mut self, mut is_current: F) -> Option<(K, V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        while let Some((key, value)) = self.pop() {
+            if is_current(&key, &value) {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.heap.peek().map(|e| (&e.key, &e.value))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<K: Ord, V> Default for MinHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An item of [`top_k`]'s heap, ordered so that the greatest is the one to
+/// drop first: the smallest item, and of equal items the latest.
+struct Ranked<T> {
+    item: T,
+    seq: usize,
+}
+
+impl<T: Ord> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Ranked<T> {}
+
+impl<T: Ord> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.item.cmp(&self.item).then(self.seq.cmp(&other.seq))
+    }
+}
+
+/// The `k` largest items, largest first, keeping only `k` of them at a
+/// time in a heap: O(n log k) rather than sorting all n.
+///
+/// Ties go to the earliest: the result is what a stable sort from largest
+/// to smallest would start with, so equal items keep their input order and
+/// later ones are dropped first. Fewer than `k` items are all returned.
+///
+/// ```ignore
+/// let top_three: u64 = top_k(elf_totals, 3).iter().sum();
+/// ```
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    if k == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(k);
+    for (seq, item) in iter.into_iter().enumerate() {
+        if heap.len() < k {
+            heap.push(Ranked { item, seq });
+        } else if let Some(mut worst) = heap.peek_mut()
+            && item > worst.item
+        {
+            *worst = Ranked { item, seq };
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|r| r.item).collect()
+}
+
+/// The `k` smallest items, smallest first, with ties going to the earliest
+/// as in [`top_k`].
+pub fn bottom_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    top_k(iter.into_iter().map(Reverse), k)
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+/// Maps a state to a canonical representative of its symmetry class, so
+/// that equivalent states share one memo or transposition-table entry.
+///
+/// `Vec`s are treated as collections of interchangeable items and sorted;
+/// tuples canonicalize component-wise; scalars are already canonical.
+pub trait Canonicalize {
+    #[must_use]
+    fn canonicalize(&self) -> Self;
+}
+
+macro_rules! canonical_as_is {
+    ($($t:ty),*) => {$(
+        impl Canonicalize for $t {
+            fn canonicalize(&self) -> Self {
+                self.clone()
+            }
+        }
+    )*};
+}
+
+canonical_as_is!(
+    bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize,
+    String
+);
+
+macro_rules! canonical_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Canonicalize),*> Canonicalize for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn canonicalize(&self) -> Self {
+                let ($($name,)*) = self;
+                ($($name.canonicalize(),)*)
+            }
+        }
+    };
+}
+
+canonical_tuple!(A);
+canonical_tuple!(A, B);
+canonical_tuple!(A, B, C);
+canonical_tuple!(A, B, C, D);
+canonical_tuple!(A, B, C, D, E);
+
+impl<T: Canonicalize + Ord> Canonicalize for Vec<T> {
+    fn canonicalize(&self) -> Self {
+        let mut items: Self = self.iter().map(T::canonicalize).collect();
+        items.sort_unstable();
+        items
+    }
+}
+
+/// A memo table for recursive computations over an acyclic dependency
+/// structure, evaluated with an explicit stack so deep recursions cannot
+/// overflow the call stack.
+///
+/// A value is described by the keys it depends on (`deps`) and how to
+/// combine their values (`combine`), instead of by a recursive closure.
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Clone + Eq + Hash, V> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Computes (or looks up) the value for `key`.
+    ///
+    /// `deps(k)` lists the keys `k` depends on, and `combine(k, values)`
+    /// builds the value for `k` from theirs, in the same order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dependencies contain a cycle.
+    pub fn get<D, I, C>(&mut self, key: &K, mut deps: D, mut combine: C) -> &V
+    where
+        D: FnMut(&K) -> I,
+        I: IntoIterator<Item = K>,
+        C: FnMut(&K, &[&V]) -> V,
+    {
+        let mut in_progress = HashSet::new();
+        let mut stack = vec![(key.clone(), None)];
+        while let Some((k, expanded)) = stack.pop() {
+            if self.cache.contains_key(&k) {
+                continue;
+            }
+            match expanded {
+                None => {
+                    assert!(
+                        in_progress.insert(k.clone()),
+                        "cyclic dependency in Memo::get"
+                    );
+                    let ks: Vec<K> = deps(&k).into_iter().collect();
+                    let pending: Vec<K> = ks
+                        .iter()
+                        .filter(|d| !self.cache.contains_key(*d))
+                        .cloned()
+                        .collect();
+                    stack.push((k, Some(ks)));
+                    stack.extend(pending.into_iter().map(|d| (d, None)));
+                }
+                Some(ks) => {
+                    let values: Vec<&V> =
+                        ks.iter().map(|d| &self.cache[d]).collect();
+                    let value = combine(&k, &values);
+                    in_progress.remove(&k);
+                    self.cache.insert(k, value);
+                }
+            }
+        }
+        &self.cache[key]
+    }
+
+    #[must_use]
+    pub fn cached(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What one call of a recursive function does, for [`recurse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step<A, C, R> {
+    /// Return this value without recursing.
+    Done(R),
+    /// Recurse on each argument, in order, then pass the saved context and
+    /// their results to `combine`.
+    Recurse(C, Vec<A>),
+}
+
+/// Evaluates a recursive function with an explicit stack on the heap, so
+/// it cannot overflow the call stack however deep it goes.
+///
+/// The recursive function
+///
+/// ```ignore
+/// fn f(a: A) -> R {
+///     if base_case { return r; }
+///     let results = children(a).map(f);
+///     finish(ctx, results)
+/// }
+/// ```
+///
+/// is written as `step` returning `Step::Done(r)` or
+/// `Step::Recurse(ctx, children)`, with `combine` doing `finish`. For
+/// example, the magnitude of a snailfish number held in an arena:
+///
+/// ```ignore
+/// recurse(root, |i| match nodes[i] {
+///     Node::Num(n) => Step::Done(n),
+///     Node::Pair(l, r) => Step::Recurse((), vec![l, r]),
+/// }, |(), m| 3 * m[0] + 2 * m[1])
+/// ```
+///
+/// For memoized recursion over shared subproblems, use [`Memo`]; for tail
+/// recursion, [`trampoline`].
+pub fn recurse<A, C, R, S, F>(arg: A, mut step: S, mut combine: F) -> R
+where
+    S: FnMut(A) -> Step<A, C, R>,
+    F: FnMut(C, Vec<R>) -> R,
+{
+    // A call waiting on its children: those still to run, in reverse,
+    // and the results of those that have.
+    struct Frame<A, C, R> {
+        ctx: C,
+        pending: Vec<A>,
+        results: Vec<R>,
+    }
+    let mut stack: Vec<Frame<A, C, R>> = vec![];
+    let mut arg = arg;
+    loop {
+        // Descend through first children until a call returns a value.
+        let mut value = loop {
+            match step(arg) {
+                Step::Done(r) => break r,
+                Step::Recurse(ctx, mut pending) => {
+                    pending.reverse();
+                    let Some(first) = pending.pop() else {
+                        break combine(ctx, vec![]);
+                    };
+                    let results = Vec::with_capacity(pending.len() + 1);
+                    stack.push(Frame {
+                        ctx,
+                        pending,
+                        results,
+                    });
+                    arg = first;
+                }
+            }
+        };
+        // Hand the value up until a caller has another child to run.
+        loop {
+            let Some(mut frame) = stack.pop() else {
+                return value;
+            };
+            frame.results.push(value);
+            if let Some(next) = frame.pending.pop() {
+                stack.push(frame);
+                arg = next;
+                break;
+            }
+            value = combine(frame.ctx, frame.results);
+        }
+    }
+}
+
+/// Runs a tail-recursive function as a loop: `step` either continues with
+/// the next state or breaks with the answer. Mutually recursive functions
+/// can share a state enum saying which one to run next.
+///
+/// ```ignore
+/// let steps = trampoline((n, 0), |(n, steps)| match n {
+///     1 => ControlFlow::Break(steps),
+///     n if n % 2 == 0 => ControlFlow::Continue((n / 2, steps + 1)),
+///     n => ControlFlow::Continue((3 * n + 1, steps + 1)),
+/// });
+/// ```
+pub fn trampoline<S, R, F>(mut state: S, mut step: F) -> R
+where
+    F: FnMut(S) -> ControlFlow<R, S>,
+{
+    loop {
+        match step(state) {
+            ControlFlow::Continue(next) => state = next,
+            ControlFlow::Break(r) => return r,
+        }
+    }
+}
+
+/// A small seeded pseudo-random generator (xorshift64*), so generated
+/// inputs and randomized tests can be replayed from their seed. Fast and
+/// good enough for puzzles, but not for anything needing secrecy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// A generator whose output is fixed by `seed`. The seed is scrambled
+    /// with `SplitMix64` first, so nearby seeds give unrelated streams.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        // xorshift never leaves an all-zero state, so avoid starting there.
+        Self {
+            state: if z == 0 { 1 } else { z },
+        }
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// A uniform value in `range`, without the bias of taking a remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    // The casts keep the low and high halves of a 128-bit product.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "gen_range on an empty range");
+        let span = range.end - range.start;
+        // Lemire's multiply-shift, rejecting the few low products that
+        // would make some results more likely than others.
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(span);
+            if product as u64 >= threshold {
+                return range.start + (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// A uniform index into a collection of `len` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0.
+    // usize is at most 64 bits on every supported target.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn gen_index(&mut self, len: usize) -> usize {
+        self.gen_range(0..len as u64) as usize
+    }
+
+    /// A uniform float in `[0, 1)`.
+    // 53 random bits fit an f64's mantissa exactly.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// `true` with probability `p`.
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Shuffles `items` in place, every order being equally likely
+    /// (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.gen_index(i + 1));
+        }
+    }
+
+    /// A uniformly chosen item, or `None` if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        (!items.is_empty()).then(|| &items[self.gen_index(items.len())])
+    }
+}
+
+/// [`Rng::shuffle`] and [`Rng::choose`] as slice methods, e.g.
+/// `order.shuffle(&mut rng)`.
+pub trait SliceRandom<T> {
+    fn shuffle(&mut self, rng: &mut Rng);
+
+    fn choose(&self, rng: &mut Rng) -> Option<&T>;
+}
+
+impl<T> SliceRandom<T> for [T] {
+    fn shuffle(&mut self, rng: &mut Rng) {
+        rng.shuffle(self);
+    }
+
+    fn choose(&self, rng: &mut Rng) -> Option<&T> {
+        rng.choose(self)
+    }
+}
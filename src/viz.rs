@@ -0,0 +1,285 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use crate::direction::{Coords, ICoords};
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    const fn ansi_code(self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+        }
+    }
+
+    /// Wraps `s` in the escape codes for this color.
+    #[must_use]
+    pub fn paint(self, s: &str) -> String {
+        format!("\x1b[1;{}m{s}\x1b[0m", self.ansi_code())
+    }
+}
+
+/// State that can be drawn as a grid of characters, so a solver can show
+/// it with [`stepper::frame`](crate::stepper::frame).
+pub trait Visualizable {
+    fn frame(&self) -> Grid<char>;
+}
+
+impl Visualizable for Grid<char> {
+    fn frame(&self) -> Grid<char> {
+        self.clone()
+    }
+}
+
+/// `#` for `true` and `.` for `false`.
+impl Visualizable for Grid<bool> {
+    fn frame(&self) -> Grid<char> {
+        self.map(|&b| if b { '#' } else { '.' })
+    }
+}
+
+/// The color a frame cell is drawn in by [`to_ppm`]: black for blank
+/// cells, white for walls and live cells, and a fixed bright color per
+/// character otherwise.
+#[must_use]
+pub const fn cell_color(c: char) -> [u8; 3] {
+    const PALETTE: [[u8; 3]; 8] = [
+        [230, 25, 75],
+        [60, 180, 75],
+        [255, 225, 25],
+        [0, 130, 200],
+        [245, 130, 48],
+        [145, 30, 180],
+        [70, 240, 240],
+        [240, 50, 230],
+    ];
+    match c {
+        '.' | ' ' => [0, 0, 0],
+        '#' | '█' => [255, 255, 255],
+        _ => PALETTE[c as usize % PALETTE.len()],
+    }
+}
+
+/// Encodes a frame as a binary PPM image, each cell a `scale` by `scale`
+/// block colored by [`cell_color`].
+#[must_use]
+pub fn to_ppm(frame: &Grid<char>, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+    out.reserve(width * height * 3);
+    for row in frame.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|&c| cell_color(c).repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            out.extend_from_slice(&line);
+        }
+    }
+    out
+}
+
+impl<T> Grid<T> {
+    /// Renders the grid one row per line, using `to_char` for each cell.
+    pub fn render<F>(&self, mut to_char: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut out = String::with_capacity(self.height() * (self.width() + 1));
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut to_char));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Like [`Grid::render`], but cells in `highlight` are painted `color`.
+    pub fn render_highlighted<F, S>(
+        &self,
+        mut to_char: F,
+        highlight: &HashSet<Coords, S>,
+        color: Color,
+    ) -> String
+    where
+        F: FnMut(&T) -> char,
+        S: BuildHasher,
+    {
+        let mut out = String::new();
+        for (pos, cell) in self.iter() {
+            let ch = to_char(cell);
+            if highlight.contains(&pos) {
+                out.push_str(&color.paint(&ch.to_string()));
+            } else {
+                out.push(ch);
+            }
+            if pos.1 + 1 == self.width() {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// Renders a sparse grid over its bounding box. Missing cells are passed to
+/// `to_char` as `None`.
+pub fn render_sparse<T, F, S>(
+    cells: &HashMap<ICoords, T, S>,
+    mut to_char: F,
+) -> String
+where
+    F: FnMut(Option<&T>) -> char,
+    S: BuildHasher,
+{
+    let Some((min_r, max_r, min_c, max_c)) = bounding_box(cells.keys()) else {
+        return String::new();
+    };
+    let mut out = String::new();
+    for r in min_r..=max_r {
+        for c in min_c..=max_c {
+            out.push(to_char(cells.get(&ICoords(r, c))));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders a set of points as `#` on a `.` background over their bounding
+/// box.
+#[must_use]
+pub fn render_points<S: BuildHasher>(points: &HashSet<ICoords, S>) -> String {
+    let cells: HashMap<ICoords, char> =
+        points.iter().map(|&p| (p, '#')).collect();
+    render_sparse(&cells, |cell| cell.copied().unwrap_or('.'))
+}
+
+/// Returns `(min_r, max_r, min_c, max_c)` over `points`, if any.
+pub fn bounding_box<'a, I>(points: I) -> Option<(i64, i64, i64, i64)>
+where
+    I: IntoIterator<Item = &'a ICoords>,
+{
+    points.into_iter().fold(None, |acc, &ICoords(r, c)| {
+        Some(acc.map_or((r, r, c, c), |(r0, r1, c0, c1)| {
+            (r0.min(r), r1.max(r), c0.min(c), c1.max(c))
+        }))
+    })
+}
+
+/// Renders a distance field with the last digit of each distance, and `#`
+/// for unreachable cells.
+#[must_use]
+pub fn render_distance_field(field: &Grid<Option<usize>>) -> String {
+    field.render(|d| d.map_or('#', |d| char::from(b"0123456789"[d % 10])))
+}
+
+/// Renders a line-by-line diff of `actual` against `expected`: unchanged
+/// lines are prefixed with two spaces, expected lines missing from `actual`
+/// with `- `, and lines only in `actual` with `+ `.
+///
+/// When a run of removed lines is followed by added ones, the lines are
+/// paired up and the characters that differ are highlighted, which makes a
+/// wrong pixel in a grid of letters easy to spot. With `color`, removed
+/// lines are marked green and added ones red.
+#[must_use]
+pub fn render_diff(expected: &str, actual: &str, color: bool) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    // lcs[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut removed, mut added) = (vec![], vec![]);
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push_changes(&mut out, &mut removed, &mut added, color);
+            out.push_str("  ");
+            out.push_str(old[i]);
+            out.push('\n');
+            i += 1;
+            j += 1;
+        } else if i < old.len()
+            && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1])
+        {
+            removed.push(old[i]);
+            i += 1;
+        } else {
+            added.push(new[j]);
+            j += 1;
+        }
+    }
+    push_changes(&mut out, &mut removed, &mut added, color);
+    out
+}
+
+/// Writes out and clears a run of removed lines and the added lines that
+/// replace them, pairing them up in order.
+fn push_changes(
+    out: &mut String,
+    removed: &mut Vec<&str>,
+    added: &mut Vec<&str>,
+    color: bool,
+) {
+    for (k, line) in removed.iter().enumerate() {
+        out.push_str("- ");
+        push_marked(out, line, added.get(k).copied(), color, Color::Green);
+    }
+    for (k, line) in added.iter().enumerate() {
+        out.push_str("+ ");
+        push_marked(out, line, removed.get(k).copied(), color, Color::Red);
+    }
+    removed.clear();
+    added.clear();
+}
+
+/// Writes `line`, painting the characters that differ from the same
+/// position in `other` (all of them if there is no `other`).
+fn push_marked(
+    out: &mut String,
+    line: &str,
+    other: Option<&str>,
+    color: bool,
+    paint: Color,
+) {
+    let Some(other) = other.filter(|_| color) else {
+        if color {
+            out.push_str(&paint.paint(line));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+        return;
+    };
+    let mut other = other.chars();
+    for ch in line.chars() {
+        if other.next() == Some(ch) {
+            out.push(ch);
+        } else {
+            out.push_str(&paint.paint(&ch.to_string()));
+        }
+    }
+    out.push('\n');
+}
@@ -0,0 +1,413 @@
+//! A small register machine covering the assembly dialects that keep
+//! turning up in puzzles (`cpy`/`inc`/`jnz`, `set`/`mul`/`jgz`, `hlf`/`tpl`/
+//! `jie`, ...).
+//!
+//! ```ignore
+//! let program = decode(&lines)?;
+//! let mut vm = Machine::new(program);
+//! vm.regs[reg('c')] = 1;
+//! vm.run();
+//! // Part 2: make every `mul` a no-op.
+//! let mut vm = Machine::new(program).with_hooks(|_, instr: Instr, _: &mut _| {
+//!     matches!(instr, Instr::Mul(..)).then_some(Flow::Next)
+//! });
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// One register per lowercase letter.
+pub type Registers = [i64; 26];
+
+/// Index of the register named `name`.
+///
+/// # Panics
+///
+/// Panics if `name` is not a lowercase ASCII letter.
+#[must_use]
+pub fn reg(name: char) -> usize {
+    assert!(name.is_ascii_lowercase(), "invalid register {name:?}");
+    (name as u8 - b'a') as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Reg(usize),
+    Imm(i64),
+}
+
+impl Operand {
+    #[must_use]
+    pub const fn value(self, regs: &Registers) -> i64 {
+        match self {
+            Self::Reg(r) => regs[r],
+            Self::Imm(n) => n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instr {
+    /// `set x y` / `cpy y x`: `x = y`.
+    Set(usize, Operand),
+    Add(usize, Operand),
+    Sub(usize, Operand),
+    Mul(usize, Operand),
+    Div(usize, Operand),
+    Mod(usize, Operand),
+    Inc(usize),
+    Dec(usize),
+    Hlf(usize),
+    Tpl(usize),
+    /// Relative jump.
+    Jmp(Operand),
+    /// Jump by the second operand if the first is non-zero.
+    Jnz(Operand, Operand),
+    /// Jump by the second operand if the first is positive.
+    Jgz(Operand, Operand),
+    /// Jump by the offset if the register is even.
+    Jie(usize, Operand),
+    /// Jump by the offset if the register is one.
+    Jio(usize, Operand),
+    Out(Operand),
+    Nop,
+}
+
+/// What happens after an instruction runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flow {
+    Next,
+    Jump(i64),
+    Output(i64),
+    Halt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: cannot decode {:?}", self.line + 1, self.text)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn decode_operand(tok: &str) -> Option<Operand> {
+    let mut chars = tok.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_lowercase() => Some(Operand::Reg(reg(c))),
+        _ => tok.parse().ok().map(Operand::Imm),
+    }
+}
+
+fn decode_reg(tok: &str) -> Option<usize> {
+    match decode_operand(tok)? {
+        Operand::Reg(r) => Some(r),
+        Operand::Imm(_) => None,
+    }
+}
+
+/// Decodes one instruction such as `"cpy 41 a"` or `"jie a, +4"`.
+#[must_use]
+pub fn decode_instr(line: &str) -> Option<Instr> {
+    let toks: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+    let instr = match toks.as_slice() {
+        ["set", x, y] | ["cpy", y, x] => {
+            Instr::Set(decode_reg(x)?, decode_operand(y)?)
+        }
+        ["add", x, y] => Instr::Add(decode_reg(x)?, decode_operand(y)?),
+        ["sub", x, y] => Instr::Sub(decode_reg(x)?, decode_operand(y)?),
+        ["mul", x, y] => Instr::Mul(decode_reg(x)?, decode_operand(y)?),
+        ["div", x, y] => Instr::Div(decode_reg(x)?, decode_operand(y)?),
+        ["mod", x, y] => Instr::Mod(decode_reg(x)?, decode_operand(y)?),
+        ["inc", x] => Instr::Inc(decode_reg(x)?),
+        ["dec", x] => Instr::Dec(decode_reg(x)?),
+        ["hlf", x] => Instr::Hlf(decode_reg(x)?),
+        ["tpl", x] => Instr::Tpl(decode_reg(x)?),
+        ["jmp", off] => Instr::Jmp(decode_operand(off)?),
+        ["jnz", x, off] => Instr::Jnz(decode_operand(x)?, decode_operand(off)?),
+        ["jgz", x, off] => Instr::Jgz(decode_operand(x)?, decode_operand(off)?),
+        ["jie", x, off] => Instr::Jie(decode_reg(x)?, decode_operand(off)?),
+        ["jio", x, off] => Instr::Jio(decode_reg(x)?, decode_operand(off)?),
+        ["out" | "snd", x] => Instr::Out(decode_operand(x)?),
+        ["nop", ..] => Instr::Nop,
+        _ => return None,
+    };
+    Some(instr)
+}
+
+/// Decodes a whole program, one instruction per line, skipping blank lines.
+///
+/// # Errors
+///
+/// Returns the first line that is not a valid instruction.
+pub fn decode<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Instr>, DecodeError> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().trim().is_empty())
+        .map(|(i, line)| {
+            decode_instr(line.as_ref()).ok_or_else(|| DecodeError {
+                line: i,
+                text: line.as_ref().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The standard behaviour of `instr`, or `None` on division by zero or
+/// arithmetic overflow.
+#[must_use]
+pub fn execute(instr: Instr, regs: &mut Registers) -> Option<Flow> {
+    let jump_if = |cond: bool, off: Operand, regs: &Registers| {
+        if cond {
+            Flow::Jump(off.value(regs))
+        } else {
+            Flow::Next
+        }
+    };
+    let flow = match instr {
+        Instr::Set(x, y) => {
+            regs[x] = y.value(regs);
+            Flow::Next
+        }
+        Instr::Add(x, y) => {
+            regs[x] = regs[x].checked_add(y.value(regs))?;
+            Flow::Next
+        }
+        Instr::Sub(x, y) => {
+            regs[x] = regs[x].checked_sub(y.value(regs))?;
+            Flow::Next
+        }
+        Instr::Mul(x, y) => {
+            regs[x] = regs[x].checked_mul(y.value(regs))?;
+            Flow::Next
+        }
+        Instr::Div(x, y) => {
+            regs[x] = regs[x].checked_div(y.value(regs))?;
+            Flow::Next
+        }
+        Instr::Mod(x, y) => {
+            regs[x] = regs[x].checked_rem_euclid(y.value(regs))?;
+            Flow::Next
+        }
+        Instr::Inc(x) => {
+            regs[x] = regs[x].checked_add(1)?;
+            Flow::Next
+        }
+        Instr::Dec(x) => {
+            regs[x] = regs[x].checked_sub(1)?;
+            Flow::Next
+        }
+        Instr::Hlf(x) => {
+            regs[x] /= 2;
+            Flow::Next
+        }
+        Instr::Tpl(x) => {
+            regs[x] = regs[x].checked_mul(3)?;
+            Flow::Next
+        }
+        Instr::Jmp(off) => Flow::Jump(off.value(regs)),
+        Instr::Jnz(x, off) => jump_if(x.value(regs) != 0, off, regs),
+        Instr::Jgz(x, off) => jump_if(x.value(regs) > 0, off, regs),
+        Instr::Jie(x, off) => jump_if(regs[x] % 2 == 0, off, regs),
+        Instr::Jio(x, off) => jump_if(regs[x] == 1, off, regs),
+        Instr::Out(x) => Flow::Output(x.value(regs)),
+        Instr::Nop => Flow::Next,
+    };
+    Some(flow)
+}
+
+/// Overrides the behaviour of individual instructions, e.g. when part 2
+/// asks what happens if one opcode behaved differently.
+///
+/// Closures `FnMut(ip, instr, &mut regs) -> Option<Flow>` implement this.
+pub trait Hooks {
+    /// Called before the instruction at `ip` runs. Returning `Some` skips
+    /// the standard behaviour and uses the given flow instead.
+    fn intercept(
+        &mut self,
+        ip: usize,
+        instr: Instr,
+        regs: &mut Registers,
+    ) -> Option<Flow>;
+}
+
+/// No overrides.
+impl Hooks for () {
+    fn intercept(
+        &mut self,
+        _: usize,
+        _: Instr,
+        _: &mut Registers,
+    ) -> Option<Flow> {
+        None
+    }
+}
+
+impl<F> Hooks for F
+where
+    F: FnMut(usize, Instr, &mut Registers) -> Option<Flow>,
+{
+    fn intercept(
+        &mut self,
+        ip: usize,
+        instr: Instr,
+        regs: &mut Registers,
+    ) -> Option<Flow> {
+        self(ip, instr, regs)
+    }
+}
+
+/// The outcome of a single step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Ran,
+    Output(i64),
+    /// The instruction pointer is outside the program, or an instruction
+    /// asked to halt.
+    Halted,
+    /// Division by zero or overflow at this address.
+    Fault(usize),
+}
+
+/// Why [`Machine::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stop {
+    Halted,
+    Breakpoint(usize),
+    Fault(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<H = ()> {
+    program: Vec<Instr>,
+    pub regs: Registers,
+    ip: Option<usize>,
+    breakpoints: BTreeSet<usize>,
+    steps: u64,
+    hooks: H,
+}
+
+impl Machine {
+    #[must_use]
+    pub const fn new(program: Vec<Instr>) -> Self {
+        Self {
+            program,
+            regs: [0; 26],
+            ip: Some(0),
+            breakpoints: BTreeSet::new(),
+            steps: 0,
+            hooks: (),
+        }
+    }
+}
+
+impl<H: Hooks> Machine<H> {
+    /// Replaces the instruction overrides.
+    #[must_use]
+    pub fn with_hooks<G: Hooks>(self, hooks: G) -> Machine<G> {
+        Machine {
+            program: self.program,
+            regs: self.regs,
+            ip: self.ip,
+            breakpoints: self.breakpoints,
+            steps: self.steps,
+            hooks,
+        }
+    }
+
+    #[must_use]
+    pub fn program(&self) -> &[Instr] {
+        &self.program
+    }
+
+    /// The address of the next instruction, or `None` once halted.
+    #[must_use]
+    pub fn ip(&self) -> Option<usize> {
+        self.ip.filter(|&ip| ip < self.program.len())
+    }
+
+    /// Number of instructions executed so far.
+    #[must_use]
+    pub const fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn add_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn remove_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.remove(&ip);
+    }
+
+    /// Executes the instruction at the instruction pointer.
+    pub fn step(&mut self) -> Step {
+        let Some(ip) = self.ip() else {
+            return Step::Halted;
+        };
+        let instr = self.program[ip];
+        let flow = self
+            .hooks
+            .intercept(ip, instr, &mut self.regs)
+            .or_else(|| execute(instr, &mut self.regs));
+        let Some(flow) = flow else {
+            return Step::Fault(ip);
+        };
+        self.steps += 1;
+        let (next, step) = match flow {
+            Flow::Next => (ip.checked_add(1), Step::Ran),
+            Flow::Jump(off) => (
+                isize::try_from(off)
+                    .ok()
+                    .and_then(|o| ip.checked_add_signed(o)),
+                Step::Ran,
+            ),
+            Flow::Output(v) => (ip.checked_add(1), Step::Output(v)),
+            Flow::Halt => (None, Step::Halted),
+        };
+        self.ip = next;
+        step
+    }
+
+    /// Runs until the program halts or faults, or reaches a breakpoint. A
+    /// breakpoint at the starting address does not stop the run, so calling
+    /// `run` again resumes past it. Output is discarded.
+    pub fn run(&mut self) -> Stop {
+        let mut first = true;
+        loop {
+            if let Some(ip) = self.ip()
+                && !first
+                && self.breakpoints.contains(&ip)
+            {
+                return Stop::Breakpoint(ip);
+            }
+            first = false;
+            match self.step() {
+                Step::Ran | Step::Output(_) => {}
+                Step::Halted => return Stop::Halted,
+                Step::Fault(ip) => return Stop::Fault(ip),
+            }
+        }
+    }
+
+    /// Runs until the next output instruction, returning its value, or
+    /// `None` if the program halts or faults first.
+    pub fn run_until_output(&mut self) -> Option<i64> {
+        loop {
+            match self.step() {
+                Step::Ran => {}
+                Step::Output(v) => return Some(v),
+                Step::Halted | Step::Fault(_) => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! A filesystem-free entry point for running the solutions in a browser,
+//! behind the `wasm` feature.
+//!
+//! Build with `cargo build --lib --release --target wasm32-unknown-unknown
+//! --features wasm`, then generate the JavaScript bindings with
+//! `wasm-bindgen --target web`. From JavaScript, `solve` returns the answer
+//! or throws the error message.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::try_solve;
+
+/// Solves one part of `day` for `input`, returning the answer as it would
+/// be printed.
+///
+/// Only the solvers run: no config, cache, checkpoint or input file is
+/// read, and nothing is written.
+///
+/// # Errors
+///
+/// Returns a message if the day or part is out of range, the day has no
+/// solver, or the solver panics; see [`try_solve`].
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    try_solve(day, part, input).map_err(|err| err.to_string())
+}
@@ -0,0 +1,84 @@
+//! Integer binary searches at the edges of `i64`, where a naive midpoint
+//! would overflow, and on empty, single-point and one-sided ranges.
+
+use std::cell::Cell;
+
+use aoc25::utils::{binary_search_min_satisfying, partition_point_i64};
+
+const MIN: i64 = i64::MIN;
+const MAX: i64 = i64::MAX;
+
+#[test]
+fn partition_point_on_empty_and_single_ranges() {
+    let calls = Cell::new(0);
+    let counted = |x: i64| {
+        calls.set(calls.get() + 1);
+        x < 0
+    };
+    assert_eq!(partition_point_i64(5, 5, counted), 5);
+    assert_eq!(partition_point_i64(7, 3, counted), 7);
+    assert_eq!(partition_point_i64(MAX, MIN, counted), MAX);
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(partition_point_i64(-1, 0, |x| x < 0), 0);
+    assert_eq!(partition_point_i64(0, 1, |x| x < 0), 0);
+}
+
+#[test]
+fn partition_point_over_all_of_i64() {
+    assert_eq!(partition_point_i64(MIN, MAX, |_| true), MAX);
+    assert_eq!(partition_point_i64(MIN, MAX, |_| false), MIN);
+    assert_eq!(partition_point_i64(MIN, MAX, |x| x < 0), 0);
+    // Flipping at either end of the range.
+    assert_eq!(partition_point_i64(MIN, MAX, |x| x == MIN), MIN + 1);
+    assert_eq!(partition_point_i64(MIN, MAX, |x| x < MAX - 1), MAX - 1);
+    for flip in [MIN + 1, -12_345, 1 << 40, MAX - 2] {
+        assert_eq!(partition_point_i64(MIN, MAX, |x| x < flip), flip);
+    }
+}
+
+#[test]
+fn partition_point_takes_logarithmic_steps() {
+    let calls = Cell::new(0);
+    partition_point_i64(MIN, MAX, |x| {
+        calls.set(calls.get() + 1);
+        x < 42
+    });
+    assert!(calls.get() <= 64, "{} calls", calls.get());
+}
+
+#[test]
+fn min_satisfying_on_empty_and_single_ranges() {
+    let calls = Cell::new(0);
+    let counted = |_| {
+        calls.set(calls.get() + 1);
+        true
+    };
+    assert_eq!(binary_search_min_satisfying(3, 2, counted), None);
+    assert_eq!(binary_search_min_satisfying(MAX, MIN, counted), None);
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(binary_search_min_satisfying(4, 4, |_| true), Some(4));
+    assert_eq!(binary_search_min_satisfying(4, 4, |_| false), None);
+    assert_eq!(binary_search_min_satisfying(MAX, MAX, |_| true), Some(MAX));
+    assert_eq!(binary_search_min_satisfying(MIN, MIN, |_| true), Some(MIN));
+}
+
+#[test]
+fn min_satisfying_over_all_of_i64() {
+    let search =
+        |pred: fn(i64) -> bool| binary_search_min_satisfying(MIN, MAX, pred);
+    assert_eq!(search(|_| true), Some(MIN));
+    assert_eq!(search(|_| false), None);
+    assert_eq!(search(|x| x >= 0), Some(0));
+    // Flipping at either end of the range.
+    assert_eq!(search(|x| x > MIN), Some(MIN + 1));
+    assert_eq!(search(|x| x == MAX), Some(MAX));
+    assert_eq!(search(|x| x >= MAX - 1), Some(MAX - 1));
+    for flip in [MIN + 1, -987_654_321, 1 << 50, MAX - 2] {
+        assert_eq!(
+            binary_search_min_satisfying(MIN, MAX, |x| x >= flip),
+            Some(flip)
+        );
+    }
+}
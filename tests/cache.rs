@@ -0,0 +1,38 @@
+//! The `--cache` parse cache: reused for the same input and type, and
+//! ignored when either changes.
+
+use std::cell::Cell;
+use std::fs;
+
+use aoc25::cache::{cached, configure};
+
+/// A day number no puzzle uses, so real cache files are left alone.
+const DAY: u8 = 250;
+
+#[test]
+fn cache_is_keyed_on_input_and_type() {
+    let path = format!("./cache/day{DAY}.bin");
+    let _ = fs::remove_file(&path);
+    configure(DAY, true);
+    let parses = Cell::new(0);
+    let parse_u32 = |s: &str| -> Vec<u32> {
+        parses.set(parses.get() + 1);
+        s.split(' ').map(|n| n.parse().unwrap()).collect()
+    };
+    let parse_u64 = |s: &str| -> Vec<u64> {
+        parses.set(parses.get() + 1);
+        s.split(' ').map(|n| n.parse().unwrap()).collect()
+    };
+
+    assert_eq!(cached("1 2 3", parse_u32), [1, 2, 3]);
+    assert_eq!(cached("1 2 3", parse_u32), [1, 2, 3]);
+    assert_eq!(parses.get(), 1);
+    // The same bytes would decode as a Vec<u64>, but must not be reused.
+    assert_eq!(cached("1 2 3", parse_u64), [1, 2, 3]);
+    assert_eq!(parses.get(), 2);
+    assert_eq!(cached("4 5", parse_u64), [4, 5]);
+    assert_eq!(parses.get(), 3);
+
+    configure(DAY, false);
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,175 @@
+//! Command-line parsing: each command and the `<day> <part> [path]`
+//! shorthand, flags anywhere among the operands, and every error.
+
+use std::path::PathBuf;
+
+use aoc25::cli::{Cli, CliError, Command, Flags, parse_args, parse_args_with};
+
+fn parse(line: &str) -> Result<Cli, CliError> {
+    let args: Vec<String> = std::iter::once("aoc25")
+        .chain(line.split_whitespace())
+        .map(String::from)
+        .collect();
+    parse_args(&args)
+}
+
+fn command(line: &str) -> Command {
+    parse(line).unwrap().command
+}
+
+fn flags(line: &str) -> Flags {
+    parse(line).unwrap().flags
+}
+
+#[test]
+fn commands() {
+    assert_eq!(command("run 7"), Command::Run { day: 7, part: None });
+    assert_eq!(
+        command("run 7 2"),
+        Command::Run {
+            day: 7,
+            part: Some(2)
+        }
+    );
+    assert_eq!(command("all"), Command::All);
+    assert_eq!(command("new 12"), Command::New { day: 12 });
+    assert_eq!(
+        command("bench 3 1"),
+        Command::Bench {
+            day: 3,
+            part: Some(1)
+        }
+    );
+    assert_eq!(command("bench-report"), Command::BenchReport { day: None });
+    assert_eq!(
+        command("bench-report 25"),
+        Command::BenchReport { day: Some(25) }
+    );
+    assert_eq!(command("fetch 1"), Command::Fetch { day: 1 });
+    assert_eq!(command("submit 4 2"), Command::Submit { day: 4, part: 2 });
+    assert_eq!(command("gen 7"), Command::Gen { day: 7, size: None });
+    assert_eq!(
+        command("gen 7 500"),
+        Command::Gen {
+            day: 7,
+            size: Some(500)
+        }
+    );
+}
+
+#[test]
+fn day_part_path_shorthand() {
+    let cli = parse("7 2").unwrap();
+    assert_eq!(
+        cli.command,
+        Command::Run {
+            day: 7,
+            part: Some(2)
+        }
+    );
+    assert_eq!(cli.flags, Flags::default());
+
+    let cli = parse("07 1 inputs/big.txt").unwrap();
+    assert_eq!(
+        cli.command,
+        Command::Run {
+            day: 7,
+            part: Some(1)
+        }
+    );
+    assert_eq!(cli.flags.input, Some(PathBuf::from("inputs/big.txt")));
+
+    assert_eq!(parse("7"), Err(CliError::MissingArgument("part")));
+    assert_eq!(
+        parse("7 1 a.txt b.txt"),
+        Err(CliError::UnexpectedArgument("b.txt".into()))
+    );
+}
+
+#[test]
+fn verbosity_counts_every_v() {
+    assert_eq!(flags("all").verbosity, 0);
+    assert_eq!(flags("all -v").verbosity, 1);
+    assert_eq!(flags("all -vv").verbosity, 2);
+    assert_eq!(flags("-v all -vv --verbose").verbosity, 4);
+    let many = format!("all -{}", "v".repeat(300));
+    assert_eq!(flags(&many).verbosity, u8::MAX);
+}
+
+#[test]
+fn flags_may_follow_operands() {
+    let cli = parse("run 7 --time 2 --input x.txt --check --seed 9").unwrap();
+    assert_eq!(
+        cli.command,
+        Command::Run {
+            day: 7,
+            part: Some(2)
+        }
+    );
+    assert!(cli.flags.time && cli.flags.check);
+    assert_eq!(cli.flags.input, Some(PathBuf::from("x.txt")));
+    assert_eq!(cli.flags.seed, Some(9));
+
+    let f = flags("bench 1 --iterations 3 --threshold 25 --json");
+    assert_eq!((f.iterations, f.threshold, f.json), (3, 25, true));
+    let f = flags("run 1 --delay 40 --record --cache --resume --example");
+    assert_eq!(f.delay_ms, Some(40));
+    assert!(f.step && f.record && f.cache && f.resume && f.example);
+    assert!(flags("run 1 --step --verify-against-naive").verify_naive);
+}
+
+#[test]
+fn defaults_are_overridden_only_by_given_flags() {
+    let defaults = Flags {
+        iterations: 50,
+        time: true,
+        ..Flags::default()
+    };
+    let args: Vec<String> = ["aoc25", "bench", "2", "--iterations", "5"]
+        .map(String::from)
+        .into();
+    let cli = parse_args_with(&args, defaults).unwrap();
+    assert_eq!(cli.flags.iterations, 5);
+    assert!(cli.flags.time);
+}
+
+#[test]
+fn every_error() {
+    use CliError::*;
+    let cases = [
+        ("", MissingCommand),
+        ("--time", MissingCommand),
+        ("frobnicate 3", UnknownCommand("frobnicate".into())),
+        ("run 1 --fast", UnknownFlag("--fast".into())),
+        ("run 1 --input", MissingValue("--input")),
+        ("bench 1 --iterations", MissingValue("--iterations")),
+        ("bench-report --threshold", MissingValue("--threshold")),
+        ("run 1 --delay", MissingValue("--delay")),
+        ("gen 7 --seed", MissingValue("--seed")),
+        ("run", MissingArgument("day")),
+        ("submit 3", MissingArgument("part")),
+        ("all 3", UnexpectedArgument("3".into())),
+        ("run 0", InvalidDay("0".into())),
+        ("fetch 26", InvalidDay("26".into())),
+        ("99 1", InvalidDay("99".into())),
+        ("run 1 3", InvalidPart("3".into())),
+        ("submit 1 x", InvalidPart("x".into())),
+        ("bench 1 --iterations 0", InvalidIterations("0".into())),
+        ("bench-report --threshold -5", InvalidThreshold("-5".into())),
+        ("run 1 --delay soon", InvalidDelay("soon".into())),
+        ("gen 7 --seed -1", InvalidSeed("-1".into())),
+        ("gen 7 0", InvalidSize("0".into())),
+    ];
+    for (line, err) in cases {
+        assert_eq!(parse(line), Err(err), "{line:?}");
+    }
+}
+
+#[test]
+fn errors_name_the_offending_argument() {
+    let message = |line| parse(line).unwrap_err().to_string();
+    assert_eq!(message("run 30"), "day must be 1 to 25, not `30`");
+    assert_eq!(message("run 1 --input"), "`--input` needs a value");
+    assert_eq!(message("submit 2"), "missing <part>");
+    assert_eq!(message("frob"), "unknown command `frob`");
+}
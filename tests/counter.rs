@@ -0,0 +1,70 @@
+//! `Counter` keeps only positive counts through every operation, breaks
+//! `most_common` ties by value, and merges counts when mapping.
+
+use std::hash::Hash;
+
+use aoc25::counter::Counter;
+
+fn counts<T: Copy + Eq + Hash>(pairs: &[(T, usize)]) -> Counter<T> {
+    pairs.iter().copied().collect()
+}
+
+#[test]
+fn zero_counts_are_never_stored() {
+    let mut counter: Counter<char> = "hello".chars().collect();
+    assert_eq!((counter.len(), counter.total()), (4, 5));
+    counter.add('z', 0);
+    assert_eq!(counter.len(), 4);
+    assert_eq!(counter.remove(&'l', 1), 1);
+    assert_eq!(counter.get(&'l'), 1);
+    assert_eq!(counter.remove(&'l', 5), 1);
+    assert_eq!(counter.len(), 3);
+    assert_eq!(counter.remove(&'q', 2), 0);
+    assert_eq!((counter[&'l'], counter[&'h']), (0, 1));
+    assert!(counter.iter().all(|(_, &n)| n > 0));
+}
+
+#[test]
+fn most_common_breaks_ties_by_value() {
+    let counter: Counter<&str> = counts(&[
+        ("pear", 2),
+        ("fig", 5),
+        ("apple", 2),
+        ("kiwi", 2),
+        ("date", 5),
+    ]);
+    assert_eq!(
+        counter.most_common(4),
+        [(&"date", 5), (&"fig", 5), (&"apple", 2), (&"kiwi", 2)]
+    );
+    assert_eq!(counter.most_common(10).len(), 5);
+    assert!(counter.most_common(0).is_empty());
+}
+
+#[test]
+fn subtraction_saturates_at_zero() {
+    let mut a: Counter<u8> = counts(&[(1, 3), (2, 1)]);
+    let b: Counter<u8> = counts(&[(1, 1), (2, 4), (3, 2)]);
+    a -= b;
+    assert_eq!(a, counts(&[(1, 2)]));
+    assert_eq!((a.len(), a.total()), (1, 2));
+
+    let mut c: Counter<u8> = [1, 1].into_iter().collect();
+    c -= counts(&[(1, 2)]);
+    assert!(c.is_empty());
+    c += counts(&[(7, 1)]);
+    c += counts(&[(7, 2), (8, 1)]);
+    assert_eq!((c[&7], c[&8], c.len()), (3, 1, 2));
+}
+
+#[test]
+fn map_merges_counts() {
+    // Stones 0, 1, 10 and 11 (twice), keyed by their last digit.
+    let stones: Counter<u64> = [0, 1, 10, 11, 11].into_iter().collect();
+    let last_digit = stones.map(|n| n % 10);
+    assert_eq!(last_digit, counts(&[(0, 2), (1, 3)]));
+    assert_eq!(last_digit.total(), stones.total());
+    let all_same = stones.map(|_| ());
+    assert_eq!((all_same.len(), all_same[&()]), (1, 5));
+    assert!(Counter::<u8>::new().map(|&n| n).is_empty());
+}
@@ -0,0 +1,128 @@
+//! Property tests checking the cuboid set operations against brute-force
+//! sets of cells in a small box.
+
+use std::collections::HashSet;
+
+use aoc25::geometry::{Cuboid, CuboidSet};
+use proptest::prelude::*;
+
+const SIDE: i64 = 6;
+
+type Cells = HashSet<[i64; 3]>;
+
+fn cuboid() -> impl Strategy<Value = Cuboid> {
+    let corner = || prop::array::uniform3(0..SIDE);
+    (corner(), corner()).prop_map(|(a, b)| Cuboid::new(a, b))
+}
+
+/// A sequence of on (`true`) and off (`false`) steps.
+fn steps() -> impl Strategy<Value = Vec<(bool, Cuboid)>> {
+    prop::collection::vec((any::<bool>(), cuboid()), 0..8)
+}
+
+fn cells(cuboid: &Cuboid) -> Cells {
+    let (lo, hi) = (cuboid.lo(), cuboid.hi());
+    let mut out = HashSet::new();
+    for x in lo[0]..=hi[0] {
+        for y in lo[1]..=hi[1] {
+            for z in lo[2]..=hi[2] {
+                out.insert([x, y, z]);
+            }
+        }
+    }
+    out
+}
+
+fn volume_of(cells: &Cells) -> i64 {
+    i64::try_from(cells.len()).unwrap()
+}
+
+fn set_cells(set: &CuboidSet) -> Cells {
+    set.pieces().iter().flat_map(cells).collect()
+}
+
+fn apply(steps: &[(bool, Cuboid)]) -> (CuboidSet, Cells) {
+    let mut set = CuboidSet::new();
+    let mut naive = HashSet::new();
+    for (on, cuboid) in steps {
+        if *on {
+            set.insert(*cuboid);
+            naive.extend(cells(cuboid));
+        } else {
+            set.remove(cuboid);
+            naive.retain(|p| !cuboid.contains(*p));
+        }
+    }
+    (set, naive)
+}
+
+fn pairwise_disjoint(set: &CuboidSet) -> bool {
+    let pieces = set.pieces();
+    pieces.iter().enumerate().all(|(i, a)| {
+        pieces[i + 1..].iter().all(|b| a.intersection(b).is_none())
+    })
+}
+
+#[test]
+fn reboot_example() {
+    let steps = [
+        (true, Cuboid::new([10, 10, 10], [12, 12, 12])),
+        (true, Cuboid::new([11, 11, 11], [13, 13, 13])),
+        (false, Cuboid::new([9, 9, 9], [11, 11, 11])),
+        (true, Cuboid::new([10, 10, 10], [10, 10, 10])),
+    ];
+    let (set, _) = apply(&steps);
+    assert_eq!(set.volume(), 39);
+}
+
+proptest! {
+    #[test]
+    fn subtract_leaves_exactly_the_difference(a in cuboid(), b in cuboid()) {
+        let pieces = a.subtract(&b);
+        prop_assert!(pieces.len() <= 6);
+        let expected = &cells(&a) - &cells(&b);
+        let got: Cells = pieces.iter().flat_map(cells).collect();
+        let volume: i64 = pieces.iter().map(Cuboid::volume).sum();
+        prop_assert_eq!(volume, volume_of(&got));
+        prop_assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn intersection_matches_cells(a in cuboid(), b in cuboid()) {
+        let expected = &cells(&a) & &cells(&b);
+        let got = a.intersection(&b).map(|c| cells(&c)).unwrap_or_default();
+        prop_assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn reboot_steps_match_naive_cells(steps in steps()) {
+        let (set, naive) = apply(&steps);
+        prop_assert!(pairwise_disjoint(&set));
+        prop_assert_eq!(set.volume(), volume_of(&naive));
+        prop_assert_eq!(set_cells(&set), naive);
+    }
+
+    #[test]
+    fn set_operations_match_naive_cells(a in steps(), b in steps()) {
+        let (a, naive_a) = apply(&a);
+        let (b, naive_b) = apply(&b);
+        for (got, expected) in [
+            (a.union(&b), &naive_a | &naive_b),
+            (a.intersection(&b), &naive_a & &naive_b),
+            (a.difference(&b), &naive_a - &naive_b),
+        ] {
+            prop_assert!(pairwise_disjoint(&got));
+            prop_assert_eq!(got.volume(), volume_of(&expected));
+            prop_assert_eq!(set_cells(&got), expected);
+        }
+    }
+
+    #[test]
+    fn retain_within_matches_naive_cells(steps in steps(), bounds in cuboid()) {
+        let (mut set, naive) = apply(&steps);
+        set.retain_within(&bounds);
+        let expected: Cells =
+            naive.into_iter().filter(|p| bounds.contains(*p)).collect();
+        prop_assert_eq!(set_cells(&set), expected);
+    }
+}
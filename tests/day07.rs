@@ -0,0 +1,41 @@
+//! Day 7 part 2 timeline counts on the puzzle example and small manifolds
+//! worked out by hand.
+
+use aoc25::common::solve_day;
+
+fn timelines(manifold: &str) -> String {
+    solve_day(7, 2, manifold).unwrap().to_string()
+}
+
+#[test]
+fn example() {
+    let example = include_str!("../inputs/examples/day07.txt");
+    assert_eq!(timelines(example), "40");
+}
+
+#[test]
+fn no_splitters_is_one_timeline() {
+    assert_eq!(timelines("..S..\n.....\n.....\n"), "1");
+}
+
+#[test]
+fn merging_branches_stay_distinct() {
+    let one = "..S..\n.....\n..^..\n.....\n";
+    assert_eq!(timelines(one), "2");
+    // The left splitter's right branch and the right splitter's left one
+    // land in the same column, but remain separate timelines.
+    let merge = "\
+..S...
+......
+..^...
+......
+.^.^..
+......";
+    assert_eq!(timelines(merge), "4");
+}
+
+#[test]
+fn beams_split_off_the_side_are_lost() {
+    assert_eq!(timelines("S..\n...\n^..\n...\n"), "1");
+    assert_eq!(timelines("S\n.\n^\n.\n"), "0");
+}
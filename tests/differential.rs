@@ -0,0 +1,33 @@
+//! Every day's solvers against its naive ones, on small generated inputs.
+
+use aoc25::common::{DAYS, generate_input, solve_day, solve_naive};
+
+const SIZES: [usize; 6] = [1, 2, 3, 7, 16, 31];
+const SEEDS: u64 = 25;
+
+#[test]
+fn solvers_match_naive_on_generated_inputs() {
+    let mut compared = 0;
+    for &day in DAYS {
+        for size in SIZES {
+            for seed in 0..SEEDS {
+                let Some(input) = generate_input(day, Some(size), seed) else {
+                    continue;
+                };
+                for part in [1, 2] {
+                    let Some(naive) = solve_naive(day, part, &input) else {
+                        continue;
+                    };
+                    let answer = solve_day(day, part, &input).unwrap();
+                    assert_eq!(
+                        answer, naive,
+                        "day {day} part {part}, size {size}, seed {seed}:\n\
+                         {input}"
+                    );
+                    compared += 1;
+                }
+            }
+        }
+    }
+    assert!(compared > 0, "no day has both a generator and naive solver");
+}
@@ -0,0 +1,123 @@
+//! `eval_expr` under different operator tables, and the errors it reports
+//! for malformed, unbalanced and out-of-range expressions.
+
+use aoc25::parse::{Assoc, OpTable, ParseError, eval_expr};
+
+fn flipped() -> OpTable {
+    OpTable::new()
+        .with('+', 2, Assoc::Left)
+        .with('*', 1, Assoc::Left)
+}
+
+fn standard(expr: &str) -> Result<i128, ParseError> {
+    eval_expr(expr, &OpTable::standard())
+}
+
+/// The byte position and expectation of the error for `expr`.
+fn error(expr: &str) -> (usize, String) {
+    let err = standard(expr).unwrap_err();
+    (err.pos, err.expected)
+}
+
+#[test]
+fn precedence_follows_the_table() {
+    let cases = [
+        // (expression, standard, flat, addition first)
+        ("2 * 3 + (4 * 5)", 26, 26, 46),
+        ("1 + 2 * 3", 7, 9, 9),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51, 51),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 74, 437, 1445),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            3208,
+            13632,
+            23340,
+        ),
+    ];
+    for (expr, std, flat, plus_first) in cases {
+        assert_eq!(standard(expr), Ok(std), "{expr}");
+        assert_eq!(eval_expr(expr, &OpTable::flat()), Ok(flat), "{expr}");
+        assert_eq!(eval_expr(expr, &flipped()), Ok(plus_first), "{expr}");
+    }
+}
+
+#[test]
+fn associativity() {
+    assert_eq!(standard("2 ^ 3 ^ 2"), Ok(512));
+    assert_eq!(standard("2 * 3 ^ 2"), Ok(18));
+    assert_eq!(standard("(2 ^ 3) ^ 2"), Ok(64));
+    let left_pow = OpTable::standard().with('^', 3, Assoc::Left);
+    assert_eq!(eval_expr("2 ^ 3 ^ 2", &left_pow), Ok(64));
+    assert_eq!(standard("10 - 3 - 2"), Ok(5));
+    assert_eq!(standard("100 / 10 / 5"), Ok(2));
+    assert_eq!(standard("7 / 2 % 2"), Ok(1));
+}
+
+#[test]
+fn nested_parentheses() {
+    assert_eq!(standard("((((1))))"), Ok(1));
+    assert_eq!(standard("(1 + (2 * (3 + 4)))"), Ok(15));
+    assert_eq!(standard("((1 + 2) * (3 + 4)) ^ 2"), Ok(441));
+    assert_eq!(standard("  12\t+3 "), Ok(15));
+}
+
+#[test]
+fn unbalanced_parentheses() {
+    assert_eq!(error("(1 + 2"), (0, "a matching ')'".into()));
+    assert_eq!(error("((1) + 2"), (0, "a matching ')'".into()));
+    assert_eq!(error("1 + 2)"), (5, "a matching '('".into()));
+    assert_eq!(error("(1))"), (3, "a matching '('".into()));
+    assert_eq!(error("()"), (1, "a number".into()));
+    assert_eq!(error("1 (2)"), (2, "an operator".into()));
+}
+
+#[test]
+fn malformed_expressions() {
+    assert_eq!(error(""), (0, "a number".into()));
+    assert_eq!(error("1 +"), (3, "a number".into()));
+    assert_eq!(error("* 2"), (0, "a number".into()));
+    assert_eq!(error("-3"), (0, "a number".into()));
+    assert_eq!(error("1 2"), (2, "an operator".into()));
+    assert_eq!(
+        error("1 + x"),
+        (4, "a number, operator or parenthesis".into())
+    );
+    // An operator missing from the table is not recognized.
+    let err = eval_expr("5 % 2", &OpTable::flat()).unwrap_err();
+    assert_eq!(err.pos, 2);
+}
+
+#[test]
+fn division_by_zero_and_overflow() {
+    let out_of_range = "an in-range operation".to_string();
+    assert_eq!(error("1 / 0"), (2, out_of_range.clone()));
+    assert_eq!(error("5 % (3 - 3)"), (2, out_of_range.clone()));
+    let max = i128::MAX;
+    assert_eq!(standard(&format!("{max} + 0")), Ok(max));
+    assert_eq!(error(&format!("{max} + 1")), (40, out_of_range.clone()));
+    assert_eq!(error(&format!("1 + {max} + 1")), (2, out_of_range.clone()));
+    // A literal too large to read at all.
+    let too_big = "170141183460469231731687303715884105728";
+    assert_eq!(
+        error(&format!("1 + {too_big}")),
+        (4, "an unsigned integer".into())
+    );
+    assert_eq!(error("2 ^ 200"), (2, out_of_range.clone()));
+    assert_eq!(error("2 ^ (0 - 1)"), (2, out_of_range));
+}
+
+#[test]
+fn display_points_at_the_column() {
+    let err = standard("1 + x").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a number, operator or parenthesis at column 5\n  1 + x\n      ^"
+    );
+    let err = standard("(1 * 2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a matching ')' at column 1\n  (1 * 2\n  ^"
+    );
+    let err = standard("7 +").unwrap_err();
+    assert!(err.to_string().starts_with("expected a number at column 4"));
+}
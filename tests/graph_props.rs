@@ -0,0 +1,319 @@
+//! Property tests checking the graph searches against brute-force
+//! references on small random digraphs.
+
+use std::collections::HashSet;
+
+use aoc25::graph::{
+    NodeBudgetExceeded, all_simple_paths, dfs_depth_limited, dijkstra_fn,
+    iddfs, materialize, num_reachable_targets_indexed, reachable_from_reverse,
+    reversed, shortest_path, shortest_path_indexed, zero_one_bfs,
+};
+use aoc25::interner::AdjList;
+use proptest::prelude::*;
+
+const MAX_N: usize = 12;
+
+/// A random digraph on `0..n` as adjacency lists, possibly with self-loops
+/// and parallel edges.
+fn digraph() -> impl Strategy<Value = Vec<Vec<usize>>> {
+    (1..=MAX_N).prop_flat_map(|n| {
+        prop::collection::vec(prop::collection::vec(0..n, 0..4), n)
+    })
+}
+
+fn floyd_warshall(adj: &[Vec<usize>]) -> Vec<Vec<Option<usize>>> {
+    let n = adj.len();
+    let mut dist = vec![vec![None; n]; n];
+    for (v, nbrs) in adj.iter().enumerate() {
+        dist[v][v] = Some(0);
+        for &w in nbrs {
+            if v != w {
+                dist[v][w] = Some(1);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                if let (Some(a), Some(b)) = (dist[i][k], dist[k][j])
+                    && dist[i][j].is_none_or(|d| a + b < d)
+                {
+                    dist[i][j] = Some(a + b);
+                }
+            }
+        }
+    }
+    dist
+}
+
+fn naive_reachable(adj: &[Vec<usize>], src: usize) -> HashSet<usize> {
+    let mut seen = HashSet::from([src]);
+    let mut stack = vec![src];
+    while let Some(v) = stack.pop() {
+        for &w in &adj[v] {
+            if seen.insert(w) {
+                stack.push(w);
+            }
+        }
+    }
+    seen
+}
+
+/// `path` is a walk along edges of `adj` from `src` to `tgt` that never
+/// repeats a vertex.
+fn is_valid_path(
+    adj: &[Vec<usize>],
+    path: &[usize],
+    src: usize,
+    tgt: usize,
+) -> bool {
+    let distinct: HashSet<_> = path.iter().collect();
+    path.first() == Some(&src)
+        && path.last() == Some(&tgt)
+        && distinct.len() == path.len()
+        && path.windows(2).all(|e| adj[e[0]].contains(&e[1]))
+}
+
+/// Every simple path from the end of `path` to `tgt` with at most
+/// `max_len` edges in total, by plain recursion.
+fn naive_simple_paths(
+    adj: &[Vec<usize>],
+    path: &mut Vec<usize>,
+    tgt: usize,
+    max_len: usize,
+    out: &mut HashSet<Vec<usize>>,
+) {
+    let v = *path.last().unwrap();
+    if v == tgt {
+        out.insert(path.clone());
+        return;
+    }
+    if path.len() > max_len {
+        return;
+    }
+    for &w in &adj[v] {
+        if !path.contains(&w) {
+            path.push(w);
+            naive_simple_paths(adj, path, tgt, max_len, out);
+            path.pop();
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn shortest_path_matches_floyd_warshall(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let expected = floyd_warshall(&adj)[src][tgt];
+        let path = shortest_path(src, &tgt, |v| adj[v].clone());
+        prop_assert_eq!(path.as_ref().map(|p| p.len() - 1), expected);
+        if let Some(path) = path {
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+        }
+    }
+
+    #[test]
+    fn indexed_shortest_path_matches_hashed(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let hashed = shortest_path(src, &tgt, |v| adj[v].clone());
+        let indexed =
+            shortest_path_indexed(src, &tgt, |v| adj[v].clone(), |&v| v);
+        prop_assert_eq!(
+            hashed.map(|p| p.len()),
+            indexed.as_ref().map(Vec::len)
+        );
+        if let Some(path) = indexed {
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+        }
+    }
+
+    #[test]
+    fn num_reachable_targets_matches_naive_dfs(
+        adj in digraph(),
+        src in 0..MAX_N,
+        modulus in 1..4usize,
+    ) {
+        let src = src % adj.len();
+        let is_tgt = |v: &usize| v.is_multiple_of(modulus);
+        let expected =
+            naive_reachable(&adj, src).into_iter().filter(is_tgt).count();
+        let count = num_reachable_targets_indexed(
+            src,
+            |v| adj[v].clone(),
+            |&v| v,
+            is_tgt,
+        );
+        prop_assert_eq!(count, expected);
+    }
+
+    #[test]
+    fn dijkstra_matches_bfs_on_unit_weights(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let bfs = shortest_path(src, &tgt, |v| adj[v].clone());
+        let dijkstra = dijkstra_fn(src, &tgt, |v| {
+            adj[v].iter().map(|&w| (w, 1)).collect::<Vec<_>>()
+        });
+        prop_assert_eq!(
+            bfs.map(|p| p.len() as u64 - 1),
+            dijkstra.as_ref().map(|(cost, _)| *cost)
+        );
+        if let Some((cost, path)) = dijkstra {
+            prop_assert_eq!(path.len() as u64 - 1, cost);
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+        }
+    }
+
+    #[test]
+    fn reachable_from_reverse_matches_floyd_warshall(
+        adj in digraph(),
+        tgt in 0..MAX_N,
+    ) {
+        let tgt = tgt % adj.len();
+        let dist = floyd_warshall(&adj);
+        let reach = reachable_from_reverse(tgt, 0..adj.len(), |v| {
+            adj[v].clone()
+        });
+        for (v, row) in dist.iter().enumerate() {
+            prop_assert_eq!(reach.get(&v).copied(), row[tgt]);
+        }
+    }
+
+    #[test]
+    fn reversing_twice_restores_the_edges(adj in digraph()) {
+        let weighted: AdjList<usize> = adj
+            .iter()
+            .enumerate()
+            .map(|(v, nbrs)| nbrs.iter().map(|&w| (w, v * MAX_N + w)).collect())
+            .collect();
+        let rev = reversed(&weighted);
+        for (a, edges) in weighted.iter().enumerate() {
+            for &(b, w) in edges {
+                prop_assert!(rev[b].contains(&(a, w)));
+            }
+        }
+        let mut again = reversed(&rev);
+        let mut expected = weighted;
+        for list in again.iter_mut().chain(&mut expected) {
+            list.sort_unstable();
+        }
+        prop_assert_eq!(again, expected);
+    }
+
+    #[test]
+    fn zero_one_bfs_matches_dijkstra(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+        salt in 0..4usize,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let cost = |v: usize, w: usize| u64::from((v * 3 + w + salt).is_multiple_of(2));
+        let edges = |v: usize| {
+            adj[v].iter().map(|&w| (w, cost(v, w))).collect::<Vec<_>>()
+        };
+        let deque = zero_one_bfs(src, &tgt, edges);
+        let dijkstra = dijkstra_fn(src, &tgt, edges);
+        prop_assert_eq!(
+            deque.as_ref().map(|(c, _)| *c),
+            dijkstra.map(|(c, _)| c)
+        );
+        if let Some((total, path)) = deque {
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+            let sum: u64 = path.windows(2).map(|p| cost(p[0], p[1])).sum();
+            prop_assert_eq!(sum, total);
+        }
+    }
+
+    #[test]
+    fn depth_limited_dfs_finds_a_path_iff_one_fits(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+        limit in 0..MAX_N,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let dist = floyd_warshall(&adj)[src][tgt];
+        let (path, stats) =
+            dfs_depth_limited(src, |v| adj[v].clone(), limit, |&v| v == tgt);
+        prop_assert_eq!(path.is_some(), dist.is_some_and(|d| d <= limit));
+        if let Some(path) = path {
+            prop_assert!(path.len() - 1 <= limit);
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+        }
+        prop_assert!(stats.max_frontier <= limit);
+    }
+
+    #[test]
+    fn iddfs_finds_shortest_paths(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        let dist = floyd_warshall(&adj)[src][tgt];
+        let (path, _) = iddfs(src, |v| adj[v].clone(), MAX_N, |&v| v == tgt);
+        prop_assert_eq!(path.as_ref().map(|p| p.len() - 1), dist);
+        if let Some(path) = path {
+            prop_assert!(is_valid_path(&adj, &path, src, tgt));
+        }
+    }
+
+    #[test]
+    fn materialize_copies_the_reachable_part(
+        adj in digraph(),
+        src in 0..MAX_N,
+    ) {
+        let src = src % adj.len();
+        let edges = |v: usize| adj[v].iter().map(move |&w| (w, v * MAX_N + w));
+        let reachable = naive_reachable(&adj, src);
+        let g = materialize(src, edges, reachable.len()).unwrap();
+        prop_assert_eq!(g.node(0), &src);
+        prop_assert_eq!(g.len(), reachable.len());
+        prop_assert!(g.nodes.iter().all(|v| reachable.contains(v)));
+        for (id, &v) in g.nodes.iter().enumerate() {
+            prop_assert_eq!(g.id(&v), Some(id));
+            let back: Vec<_> =
+                g.adj[id].iter().map(|&(w, weight)| (g.nodes[w], weight)).collect();
+            prop_assert_eq!(back, edges(v).collect::<Vec<_>>());
+        }
+        let max_nodes = reachable.len() - 1;
+        prop_assert_eq!(
+            materialize(src, edges, max_nodes).unwrap_err(),
+            NodeBudgetExceeded { max_nodes }
+        );
+    }
+
+    #[test]
+    fn all_simple_paths_lists_each_path_once(
+        adj in digraph(),
+        src in 0..MAX_N,
+        tgt in 0..MAX_N,
+        max_len in 0..6_usize,
+    ) {
+        let (src, tgt) = (src % adj.len(), tgt % adj.len());
+        // Doubling every edge must not double the paths.
+        let edges = |v: usize| adj[v].iter().flat_map(|&w| [w, w]);
+        let paths = all_simple_paths(src, &tgt, edges, max_len);
+        let mut expected = HashSet::new();
+        naive_simple_paths(&adj, &mut vec![src], tgt, max_len, &mut expected);
+        let found: HashSet<Vec<usize>> = paths.iter().cloned().collect();
+        prop_assert_eq!(found.len(), paths.len(), "duplicate paths");
+        prop_assert_eq!(found, expected);
+        for path in &paths {
+            prop_assert!(path.len() <= max_len + 1);
+            prop_assert!(is_valid_path(&adj, path, src, tgt));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! The hashes in `hashing` against their published test vectors, and
+//! rolling-hash substring comparisons against direct comparison.
+
+use aoc25::hashing::{
+    RollingHash, knot_hash_hex, knot_rounds, md5_hex, search_nonce,
+};
+
+#[test]
+fn knot_rounds_example() {
+    let list = knot_rounds(&[3, 4, 1, 5], 1, 5);
+    assert_eq!(list, [3, 4, 2, 1, 0]);
+}
+
+#[test]
+fn knot_hash_vectors() {
+    for (input, hash) in [
+        ("", "a2582a3a0e66e6e86e3812dcb672a272"),
+        ("AoC 2017", "33efeb34ea91902bb2f59c9920caa6cd"),
+        ("1,2,3", "3efbe78a8d82f29979031a4aa0b16a9d"),
+        ("1,2,4", "63960835bcdc130f0b66d7ff4f6a5a8e"),
+    ] {
+        assert_eq!(knot_hash_hex(input), hash, "{input:?}");
+    }
+}
+
+#[test]
+fn md5_rfc_1321_vectors() {
+    for (input, digest) in [
+        ("", "d41d8cd98f00b204e9800998ecf8427e"),
+        ("a", "0cc175b9c0f1b6a831c399e269772661"),
+        ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+        ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+        (
+            "abcdefghijklmnopqrstuvwxyz",
+            "c3fcd3d76192e4007dfb496cca67e13b",
+        ),
+        (
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            "d174ab98d277d9f5a5611c2c9f419d9f",
+        ),
+        (
+            "1234567890123456789012345678901234567890\
+             1234567890123456789012345678901234567890",
+            "57edf4a22be3c955ac49da2e2107b67a",
+        ),
+    ] {
+        assert_eq!(md5_hex(input.as_bytes()), digest, "{input:?}");
+    }
+}
+
+#[test]
+fn nonce_search_finds_the_first_match() {
+    // The 2015 day 4 example, searched from just below the answer.
+    let five_zeros = |d: &[u8; 16]| d[0] == 0 && d[1] == 0 && d[2] >> 4 == 0;
+    let (nonce, digest) = search_nonce("abcdef", 600_000, five_zeros).unwrap();
+    assert_eq!(nonce, 609_043);
+    assert!(md5_hex(b"abcdef609043").starts_with("00000"));
+    assert_eq!(digest[..2], [0, 0]);
+}
+
+#[test]
+fn rolling_hash_compares_substrings() {
+    let text = b"abracadabra";
+    let hash = RollingHash::new(text);
+    assert_eq!(hash.len(), text.len());
+    assert!(hash.same(0..4, 7..11));
+    assert!(hash.same(0..1, 3..4));
+    assert!(hash.same(2..2, 5..5));
+    assert!(!hash.same(0..4, 1..5));
+    assert!(!hash.same(0..3, 0..4));
+    // Same bytes, in a different order.
+    assert!(!RollingHash::new(b"ab\0ba").same(0..2, 3..5));
+    // Zero bytes still add to the hash.
+    let zeros = RollingHash::new(b"\0\0\0");
+    assert_ne!(zeros.hash(0..1), zeros.hash(0..2));
+    assert_ne!(zeros.hash(0..0), zeros.hash(0..1));
+
+    for i in 0..text.len() {
+        for j in 0..text.len() {
+            for len in 0..=text.len() - i.max(j) {
+                assert_eq!(
+                    hash.same(i..i + len, j..j + len),
+                    text[i..i + len] == text[j..j + len],
+                    "{i} {j} {len}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn rolling_hash_windows() {
+    let hash = RollingHash::new(b"abcabc");
+    let windows: Vec<u64> = hash.windows(3).collect();
+    assert_eq!(windows.len(), 4);
+    assert_eq!(windows[0], windows[3]);
+    assert_ne!(windows[0], windows[1]);
+    assert_ne!(windows[1], windows[2]);
+    assert_eq!(windows[1], hash.hash(1..4));
+    assert_eq!(hash.windows(6).count(), 1);
+    assert_eq!(hash.windows(7).count(), 0);
+    assert_eq!(RollingHash::new(b"").windows(1).count(), 0);
+    assert!(RollingHash::new(b"").is_empty());
+}
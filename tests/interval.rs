@@ -0,0 +1,123 @@
+//! Intervals and interval sets: merging on insert, splitting on remove,
+//! and the arithmetic at the ends of `i64`.
+
+use aoc25::interval::{Interval, IntervalSet};
+
+const MIN: i64 = i64::MIN;
+const MAX: i64 = i64::MAX;
+
+const fn iv(lo: i64, hi: i64) -> Interval {
+    Interval::new(lo, hi)
+}
+
+fn ranges(set: &IntervalSet) -> Vec<(i64, i64)> {
+    set.iter().map(|iv| (iv.lo, iv.hi)).collect()
+}
+
+#[test]
+fn adjacent_intervals_merge() {
+    let mut set = IntervalSet::new();
+    set.insert(iv(1, 3));
+    set.insert(iv(4, 6));
+    assert_eq!(ranges(&set), [(1, 6)]);
+    set.insert(iv(-2, 0));
+    assert_eq!(ranges(&set), [(-2, 6)]);
+    // A gap of one keeps them apart.
+    set.insert(iv(8, 9));
+    assert_eq!(ranges(&set), [(-2, 6), (8, 9)]);
+    set.insert(iv(7, 7));
+    assert_eq!(ranges(&set), [(-2, 9)]);
+}
+
+#[test]
+fn insert_bridges_several_ranges() {
+    let mut set: IntervalSet =
+        [iv(0, 1), iv(5, 6), iv(10, 12), iv(20, 25), iv(40, 41)]
+            .into_iter()
+            .collect();
+    set.insert(iv(2, 21));
+    assert_eq!(ranges(&set), [(0, 25), (40, 41)]);
+    // Inside an existing range, nothing changes.
+    set.insert(iv(3, 4));
+    assert_eq!(ranges(&set), [(0, 25), (40, 41)]);
+    set.insert(iv(-10, 100));
+    assert_eq!(ranges(&set), [(-10, 100)]);
+}
+
+#[test]
+fn remove_splits_and_trims() {
+    let mut set: IntervalSet = [iv(0, 10), iv(20, 30)].into_iter().collect();
+    set.remove(iv(4, 6));
+    assert_eq!(ranges(&set), [(0, 3), (7, 10), (20, 30)]);
+    set.remove(iv(9, 22));
+    assert_eq!(ranges(&set), [(0, 3), (7, 8), (23, 30)]);
+    set.remove(iv(11, 19));
+    assert_eq!(ranges(&set), [(0, 3), (7, 8), (23, 30)]);
+    set.remove(iv(-5, 100));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn contains_and_total_len_after_mixed_operations() {
+    let mut set = IntervalSet::new();
+    set.insert(iv(1, 10));
+    set.insert(iv(20, 29));
+    set.remove(iv(5, 5));
+    set.insert(iv(11, 12));
+    set.remove(iv(25, 40));
+    set.insert(iv(-3, -3));
+    assert_eq!(ranges(&set), [(-3, -3), (1, 4), (6, 12), (20, 24)]);
+    assert_eq!(set.total_len(), 1 + 4 + 7 + 5);
+    for (x, inside) in [
+        (-4, false),
+        (-3, true),
+        (0, false),
+        (1, true),
+        (5, false),
+        (12, true),
+        (13, false),
+        (24, true),
+        (25, false),
+    ] {
+        assert_eq!(set.contains(x), inside, "{x}");
+    }
+}
+
+#[test]
+fn sets_at_the_ends_of_i64() {
+    let mut set = IntervalSet::new();
+    set.insert(iv(MIN, MIN + 1));
+    set.insert(iv(MAX - 1, MAX));
+    set.insert(iv(MIN + 2, MIN + 2));
+    assert_eq!(ranges(&set), [(MIN, MIN + 2), (MAX - 1, MAX)]);
+    assert!(set.contains(MIN) && set.contains(MAX));
+    assert!(!set.contains(0));
+    set.remove(iv(MIN, MIN));
+    set.remove(iv(MAX, MAX));
+    assert_eq!(ranges(&set), [(MIN + 1, MIN + 2), (MAX - 1, MAX - 1)]);
+    assert_eq!(set.total_len(), 3);
+}
+
+#[test]
+fn subtract_at_the_ends_of_i64() {
+    let wide = iv(MIN, MAX);
+    assert_eq!(wide.subtract(&iv(MIN, MIN)), [iv(MIN + 1, MAX)]);
+    assert_eq!(wide.subtract(&iv(MAX, MAX)), [iv(MIN, MAX - 1)]);
+    assert_eq!(wide.subtract(&iv(0, 0)), [iv(MIN, -1), iv(1, MAX)]);
+    assert!(wide.subtract(&wide).is_empty());
+    assert_eq!(iv(MIN, 0).subtract(&iv(1, MAX)), [iv(MIN, 0)]);
+}
+
+#[test]
+fn split_at_the_ends_of_i64() {
+    let wide = iv(MIN, MAX);
+    assert_eq!(wide.split_at(MIN), (None, Some(wide)));
+    assert_eq!(
+        wide.split_at(MAX),
+        (Some(iv(MIN, MAX - 1)), Some(iv(MAX, MAX)))
+    );
+    assert_eq!(wide.split_at(0), (Some(iv(MIN, -1)), Some(iv(0, MAX))));
+    assert_eq!(iv(MIN, MIN).split_at(MIN), (None, Some(iv(MIN, MIN))));
+    assert_eq!(iv(3, 5).split_at(3), (None, Some(iv(3, 5))));
+    assert_eq!(iv(3, 5).split_at(6), (Some(iv(3, 5)), None));
+}
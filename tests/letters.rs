@@ -0,0 +1,101 @@
+//! The letter fonts, checked by drawing every glyph and reading it back,
+//! and the errors for text that cannot be read.
+
+use aoc25::grid::Grid;
+use aoc25::letters::{OcrError, font, read_letters};
+
+/// Draws `glyphs` side by side with one blank column between letters and a
+/// blank border all round.
+fn render(glyphs: &[&str], height: usize) -> Grid<bool> {
+    let mut rows = vec![vec![false]; height + 2];
+    for art in glyphs {
+        let lines: Vec<&str> = art.lines().collect();
+        assert_eq!(lines.len(), height, "{art}");
+        let width = lines[0].len();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let line = r.checked_sub(1).and_then(|i| lines.get(i));
+            if let Some(line) = line {
+                assert_eq!(line.len(), width, "ragged glyph:\n{art}");
+                row.extend(line.chars().map(|c| c == '#'));
+            } else {
+                row.extend(std::iter::repeat_n(false, width));
+            }
+            row.push(false);
+        }
+    }
+    Grid::from_rows(rows)
+}
+
+fn check_font(height: usize, alphabet: &str) {
+    let glyphs = font(height).unwrap();
+    let letters: String = glyphs.iter().map(|&(c, _)| c).collect();
+    assert_eq!(letters, alphabet);
+    let arts: Vec<&str> = glyphs.iter().map(|&(_, art)| art).collect();
+    assert_eq!(read_letters(&render(&arts, height)), Ok(letters));
+    // Each glyph alone, and each doubled, in case one only reads in context.
+    for &(c, art) in glyphs {
+        assert_eq!(
+            read_letters(&render(&[art, art], height)),
+            Ok(format!("{c}{c}"))
+        );
+    }
+}
+
+#[test]
+fn small_font_reads_back() {
+    check_font(6, "ABCEFGHIJKLOPRSUYZ");
+}
+
+#[test]
+fn large_font_reads_back() {
+    check_font(10, "ABCEFGHJKLNPRXZ");
+}
+
+#[test]
+fn glyphs_are_distinct_and_trimmed() {
+    for height in [6, 10] {
+        let glyphs = font(height).unwrap();
+        for (i, &(a, art_a)) in glyphs.iter().enumerate() {
+            assert!(glyphs[i + 1..].iter().all(|&(_, b)| b != art_a), "{a}");
+            let lines: Vec<&str> = art_a.lines().collect();
+            let lit = |c: usize| lines.iter().any(|l| l.as_bytes()[c] == b'#');
+            assert!((0..lines[0].len()).all(lit), "blank column in {a}");
+        }
+    }
+    assert!(font(7).is_none());
+}
+
+#[test]
+fn blank_grid_reads_as_nothing() {
+    assert_eq!(read_letters(&Grid::new(6, 10, false)), Ok(String::new()));
+}
+
+#[test]
+fn unsupported_height() {
+    let mut grid = Grid::new(9, 4, false);
+    for r in 1..8 {
+        grid[aoc25::direction::Coords(r, 1)] = true;
+    }
+    let err = read_letters(&grid).unwrap_err();
+    assert_eq!(err, OcrError::UnsupportedHeight(7));
+    assert_eq!(err.to_string(), "letters must be 6 or 10 rows tall, not 7");
+}
+
+#[test]
+fn unknown_glyph_shows_where_and_what() {
+    let (_, h) = font(6).unwrap()[6];
+    let square = "####\n#..#\n#..#\n#..#\n#..#\n####";
+    let err = read_letters(&render(&[h, square], 6)).unwrap_err();
+    // The border column, then H's four columns and the gap.
+    assert_eq!(
+        err,
+        OcrError::UnknownGlyph {
+            column: 6,
+            glyph: square.to_string(),
+        }
+    );
+    assert!(
+        err.to_string()
+            .starts_with("unknown glyph at column 6:\n####")
+    );
+}
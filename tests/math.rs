@@ -0,0 +1,103 @@
+//! Number theory in `math`: modular products near `i128::MAX`, where a
+//! plain product or sum would overflow, and CRT and inverses on systems
+//! and values that have no solution.
+
+use aoc25::math::{crt, egcd, gcd, lcm, mod_inverse, mod_mul, mod_pow};
+use proptest::prelude::*;
+
+const MAX: i128 = i128::MAX;
+
+#[test]
+fn mod_mul_near_i128_max() {
+    assert_eq!(mod_mul(MAX - 1, MAX - 1, MAX), 1);
+    assert_eq!(mod_mul(MAX - 1, 2, MAX), MAX - 2);
+    assert_eq!(mod_mul(-1, 5, MAX), MAX - 5);
+    assert_eq!(mod_mul(MAX, MAX, MAX), 0);
+    // 2^127 = 1 mod 2^127 - 1, so 2^200 = 2^73.
+    assert_eq!(mod_mul(1 << 100, 1 << 100, MAX), 1 << 73);
+    let m = MAX - 1;
+    assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+    assert_eq!(mod_mul(m / 2 + 1, 2, m), 2);
+    assert_eq!(mod_pow(2, 127, MAX), 1);
+    assert_eq!(mod_pow(MAX - 1, 3, MAX), MAX - 1);
+}
+
+#[test]
+#[should_panic(expected = "modulus must be positive")]
+fn mod_mul_rejects_zero_modulus() {
+    let _ = mod_mul(3, 4, 0);
+}
+
+#[test]
+fn crt_with_coprime_moduli() {
+    assert_eq!(crt(&[(0, 3), (3, 4), (4, 5)]), Some((39, 60)));
+    assert_eq!(crt(&[(-1, 7), (13, 5)]), Some((13, 35)));
+    assert_eq!(crt(&[]), Some((0, 1)));
+    assert_eq!(crt(&[(5, MAX)]), Some((5, MAX)));
+}
+
+#[test]
+fn crt_with_shared_factors() {
+    assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+    assert_eq!(crt(&[(3, 6), (3, 6)]), Some((3, 6)));
+    assert_eq!(crt(&[(3, 4), (3, 8), (7, 12)]), Some((19, 24)));
+}
+
+#[test]
+fn crt_rejects_inconsistent_or_invalid_systems() {
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt(&[(0, 2), (1, 2)]), None);
+    assert_eq!(crt(&[(1, 0)]), None);
+    assert_eq!(crt(&[(1, 3), (0, -5)]), None);
+    // The combined modulus would not fit in an i128.
+    assert_eq!(crt(&[(0, MAX), (0, 3)]), None);
+}
+
+#[test]
+fn egcd_and_inverses() {
+    assert_eq!(egcd(240, 46), (2, -9, 47));
+    assert_eq!(egcd(0, 0), (0, 1, 0));
+    assert_eq!(egcd(-4, 6).0, 2);
+    assert_eq!(gcd(-12, 18), 6);
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(MAX, 2), None);
+
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(-3, 11), Some(7));
+    assert_eq!(mod_inverse(5, 1), Some(0));
+    // Values sharing a factor with the modulus have no inverse.
+    assert_eq!(mod_inverse(4, 6), None);
+    assert_eq!(mod_inverse(0, 7), None);
+    assert_eq!(mod_inverse(14, 7), None);
+    assert_eq!(mod_inverse(3, 0), None);
+    assert_eq!(mod_inverse(3, -11), None);
+}
+
+proptest! {
+    #[test]
+    fn mod_mul_with_huge_moduli(
+        m in MAX / 2..=MAX,
+        a in any::<i128>(),
+        b in any::<i128>(),
+    ) {
+        let ab = mod_mul(a, b, m);
+        prop_assert!((0..m).contains(&ab));
+        prop_assert_eq!(ab, mod_mul(b, a, m));
+        // a * b + a * (-b) = 0.
+        let neg = mod_mul(a, m - b.rem_euclid(m), m);
+        prop_assert!(ab == 0 && neg == 0 || ab == m - neg);
+        // a * (2b) = 2 (a * b).
+        let twice = mod_mul(a, mod_mul(b, 2, m), m);
+        prop_assert_eq!(twice, mod_mul(ab, 2, m));
+    }
+
+    #[test]
+    fn mod_mul_matches_the_exact_product(
+        m in 1..=i128::from(i64::MAX),
+        a in any::<i64>(),
+        b in any::<i64>(),
+    ) {
+        let exact = (i128::from(a) * i128::from(b)).rem_euclid(m);
+        prop_assert_eq!(mod_mul(a.into(), b.into(), m), exact);
+    }
+}
@@ -0,0 +1,73 @@
+//! Matrix powers and `nth_term` on linear recurrences with known closed
+//! forms, at indices far too large to step through.
+
+use aoc25::matrix::{Matrix, nth_term};
+
+const P: i128 = 1_000_000_007;
+const MAX: i128 = i128::MAX;
+
+fn fib(n: u64, modulus: i128) -> i128 {
+    nth_term(&[1, 1], &[0, 1], n, modulus)
+}
+
+#[test]
+fn fibonacci_at_large_n() {
+    assert_eq!(fib(10, P), 55);
+    assert_eq!(fib(90, MAX), 2_880_067_194_370_816_120);
+    assert_eq!(fib(1000, P), 517_691_607);
+    assert_eq!(fib(1_000_000_000_000_000_000, P), 209_783_453);
+    // Entries close to i128::MAX, whose sums would overflow.
+    assert_eq!(
+        fib(200, MAX),
+        8_361_466_196_376_912_059_568_585_545_786_845_702
+    );
+    assert_eq!(
+        fib(1_000_000_000_000_000_000, MAX),
+        123_290_909_414_740_091_413_961_777_814_629_569_736
+    );
+}
+
+#[test]
+fn initial_terms_are_returned_directly() {
+    let coeffs = [1, 1, 1];
+    let initial = [5, -3, 12];
+    assert_eq!(nth_term(&coeffs, &initial, 0, 10), 5);
+    assert_eq!(nth_term(&coeffs, &initial, 1, 10), 7);
+    assert_eq!(nth_term(&coeffs, &initial, 2, 10), 2);
+    assert_eq!(nth_term(&coeffs, &initial, 3, 100), 14);
+    assert_eq!(nth_term(&[], &[], 5, 10), 0);
+}
+
+#[test]
+fn negative_coefficients_and_initial_terms() {
+    // a[i] = 2 a[i-1] - a[i-2] is arithmetic: a[n] = a[0] + n (a[1] - a[0]).
+    assert_eq!(
+        nth_term(&[2, -1], &[0, 1], 1_000_000_000_000_000_000, P),
+        49
+    );
+    assert_eq!(nth_term(&[2, -1], &[-5, -3], 100, 1000), 195);
+    // 5 - 2 * 10 = -15, which is 6 mod 7.
+    assert_eq!(nth_term(&[2, -1], &[5, 3], 10, 7), 6);
+    // a[i] = -a[i-1] alternates sign.
+    assert_eq!(nth_term(&[-1], &[3], 7, 10), 7);
+    assert_eq!(nth_term(&[-1], &[3], 8, 10), 3);
+}
+
+#[test]
+#[should_panic(expected = "one initial term per coefficient")]
+fn mismatched_lengths_panic() {
+    let _ = nth_term(&[1, 1], &[0], 5, P);
+}
+
+#[test]
+fn pow_zero_is_the_identity() {
+    let m =
+        Matrix::from_rows(vec![vec![2, -1, 0], vec![4, 5, 6], vec![7, 8, 9]]);
+    assert_eq!(m.pow(0), Matrix::identity(3));
+    assert_eq!(m.pow_mod(0, P), Matrix::identity(3));
+    assert_eq!(m.pow(1), m);
+    assert_eq!(m.pow(3), &(&m * &m) * &m);
+    let fib_step = Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]);
+    assert_eq!(fib_step.pow(10)[(0, 1)], 55);
+    assert_eq!(fib_step.pow_mod(1000, P)[(0, 1)], 517_691_607);
+}
@@ -0,0 +1,91 @@
+//! `ModInt` arithmetic wrapping around the modulus, including moduli close
+//! to `u64::MAX` where the sum of two residues overflows a `u64`.
+
+use aoc25::math::ModInt;
+use proptest::prelude::*;
+
+/// The largest prime below 2^64.
+const BIG: u64 = u64::MAX - 58;
+type Big = ModInt<BIG>;
+type Mod6 = ModInt<6>;
+type Mod7 = ModInt<7>;
+
+#[test]
+fn add_sub_neg_wrap_near_u64_max() {
+    let top = Big::new(BIG - 1);
+    assert_eq!((top + top).value(), BIG - 2);
+    assert_eq!((top + Big::new(1)).value(), 0);
+    assert_eq!((top + Big::new(0)).value(), BIG - 1);
+    assert_eq!((Big::new(0) - Big::new(1)).value(), BIG - 1);
+    assert_eq!((Big::new(1) - top).value(), 2);
+    assert_eq!((-Big::new(1)).value(), BIG - 1);
+    assert_eq!((-Big::new(0)).value(), 0);
+    assert_eq!((top * top).value(), 1);
+    assert_eq!(Big::new(u64::MAX).value(), 58);
+
+    let mut acc = top;
+    acc += top;
+    acc -= Big::new(BIG - 3);
+    assert_eq!(acc.value(), 1);
+}
+
+#[test]
+fn from_negative_i64() {
+    assert_eq!(Mod7::from(-1_i64).value(), 6);
+    assert_eq!(Mod7::from(-14_i64).value(), 0);
+    // 2^63 = 1 (mod 7).
+    assert_eq!(Mod7::from(i64::MIN).value(), 6);
+    assert_eq!(Big::from(-1_i64).value(), BIG - 1);
+    assert_eq!(Big::from(i64::MIN).value(), BIG - (1 << 63));
+    assert_eq!(Big::from(i64::MAX).value(), i64::MAX.unsigned_abs());
+}
+
+#[test]
+fn pow_edge_cases() {
+    assert_eq!(ModInt::<1>::new(5).pow(0).value(), 0);
+    assert_eq!(ModInt::<1>::new(0).pow(0).value(), 0);
+    assert_eq!(Mod7::new(0).pow(0).value(), 1);
+    assert_eq!(Mod7::new(3).pow(6).value(), 1);
+    assert_eq!(Big::new(2).pow(BIG - 1).value(), 1);
+}
+
+#[test]
+fn inverses_exist_only_for_units() {
+    assert_eq!(Mod6::new(5).inv(), Some(Mod6::new(5)));
+    assert_eq!(Mod6::new(4).inv(), None);
+    assert_eq!(Mod6::new(3).inv(), None);
+    assert_eq!(Mod7::new(0).inv(), None);
+    assert_eq!(ModInt::<1>::new(0).inv(), Some(ModInt::new(0)));
+    assert_eq!((Mod7::new(3) / Mod7::new(5)).value(), 2);
+    let top = Big::new(BIG - 1);
+    assert_eq!(top.inv(), Some(top));
+    assert_eq!((Big::new(1) / top).value(), BIG - 1);
+}
+
+#[test]
+#[should_panic(expected = "not invertible")]
+fn dividing_by_a_non_unit_panics() {
+    let _ = Mod6::new(1) / Mod6::new(2);
+}
+
+#[test]
+fn default_sum_and_product() {
+    assert_eq!(Big::default().value(), 0);
+    let xs = [Big::new(BIG - 1), Big::new(BIG - 2), Big::new(5)];
+    assert_eq!(xs.into_iter().sum::<Big>().value(), 2);
+    assert_eq!(xs.into_iter().product::<Big>().value(), 10);
+    assert_eq!(std::iter::empty::<Mod7>().product::<Mod7>().value(), 1);
+}
+
+proptest! {
+    #[test]
+    fn matches_u128_arithmetic(a in any::<u64>(), b in any::<u64>()) {
+        let (x, y) = (Big::new(a), Big::new(b));
+        let (a, b, m) = (u128::from(x.value()), u128::from(y.value()), u128::from(BIG));
+        let expect = |v: u128| u64::try_from(v % m).unwrap();
+        prop_assert_eq!((x + y).value(), expect(a + b));
+        prop_assert_eq!((x - y).value(), expect(a + m - b));
+        prop_assert_eq!((-y).value(), expect(m - b));
+        prop_assert_eq!((x * y).value(), expect(a * b));
+    }
+}
@@ -0,0 +1,124 @@
+//! The neighbor table on a garden-plot map, and on random grids checked
+//! against direct neighbor lookups.
+
+use aoc25::direction::{Coords, DirSet, Direction};
+use aoc25::graph::bfs_distances;
+use aoc25::grid::{Grid, NbrTable};
+use proptest::prelude::*;
+
+const GARDEN: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+/// The plots of every region, each listed once.
+fn regions(grid: &Grid<char>, table: &NbrTable) -> Vec<Vec<Coords>> {
+    let mut seen = Grid::new(grid.height(), grid.width(), false);
+    let mut out = vec![];
+    for (pos, _) in grid.iter() {
+        if seen[pos] {
+            continue;
+        }
+        let region: Vec<Coords> = bfs_distances([pos], |p| {
+            table
+                .neighbors(p)
+                .filter(move |&n| n.0 == p.0 || n.1 == p.1)
+        })
+        .into_keys()
+        .collect();
+        for &p in &region {
+            seen[p] = true;
+        }
+        out.push(region);
+    }
+    out
+}
+
+/// Corners of `pos`'s region at `pos`: outer ones where two adjacent sides
+/// are both missing, inner ones where both are present but the diagonal
+/// between them is not.
+fn corners(table: &NbrTable, pos: Coords) -> usize {
+    let dirs = table.dirs(pos);
+    Direction::CARDINALS
+        .into_iter()
+        .filter(|&d| {
+            let (side, diag) = (d.turn_right(), d.rotate(1));
+            let (a, b) = (dirs.contains(d), dirs.contains(side));
+            (!a && !b) || (a && b && !dirs.contains(diag))
+        })
+        .count()
+}
+
+#[test]
+fn garden_fence_prices() {
+    let grid: Grid<char> = GARDEN.parse().unwrap();
+    let table = grid.neighbor_table(|a, b| a == b);
+    let regions = regions(&grid, &table);
+    assert_eq!(regions.len(), 11);
+
+    let price = |cost: &dyn Fn(Coords) -> usize| -> usize {
+        regions
+            .iter()
+            .map(|r| r.len() * r.iter().map(|&p| cost(p)).sum::<usize>())
+            .sum()
+    };
+    assert_eq!(price(&|p| table.missing_cardinals(p).len()), 1930);
+    assert_eq!(price(&|p| corners(&table, p)), 1206);
+}
+
+#[test]
+fn edges_and_lookups() {
+    let grid: Grid<char> = "ab\ncc".parse().unwrap();
+    let table = grid.neighbor_table(|_, _| true);
+    let corner = Coords(0, 0);
+    assert_eq!(table.cardinal_count(corner), 2);
+    assert_eq!(
+        table.missing_cardinals(corner),
+        DirSet::from_iter([Direction::N, Direction::W])
+    );
+    assert_eq!(table.get(corner, Direction::SE), Some(Coords(1, 1)));
+    assert_eq!(table.get(corner, Direction::N), None);
+    assert_eq!(table.dirs(Coords(5, 5)), DirSet::EMPTY);
+
+    let same = grid.neighbor_table(|a, b| a == b);
+    assert_eq!(same.cardinal_count(corner), 0);
+    assert_eq!(
+        same.neighbors(Coords(1, 0)).collect::<Vec<_>>(),
+        [Coords(1, 1)]
+    );
+}
+
+fn small_grid() -> impl Strategy<Value = Grid<u8>> {
+    (1..6usize, 1..6usize).prop_flat_map(|(h, w)| {
+        prop::collection::vec(prop::collection::vec(0..3u8, w), h)
+            .prop_map(Grid::from_rows)
+    })
+}
+
+proptest! {
+    #[test]
+    fn table_matches_direct_lookup(grid in small_grid()) {
+        let table = grid.neighbor_table(|a, b| a <= b);
+        for (pos, here) in grid.iter() {
+            for dir in Direction::ALL {
+                let expected = pos
+                    .step(dir)
+                    .filter(|&n| grid.get(n).is_some_and(|there| here <= there));
+                prop_assert_eq!(table.get(pos, dir), expected);
+            }
+            let cardinal = Direction::CARDINALS
+                .into_iter()
+                .filter(|&d| table.get(pos, d).is_some())
+                .count();
+            prop_assert_eq!(table.cardinal_count(pos), cardinal);
+            prop_assert_eq!(table.missing_cardinals(pos).len(), 4 - cardinal);
+        }
+    }
+}
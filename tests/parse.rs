@@ -0,0 +1,95 @@
+//! The parser combinators: where errors point, which error `alt` keeps,
+//! and how `sep_by`, `many` and `parse_all` end.
+
+use aoc25::parse::{
+    Input, alt, delimited, int, many, map, pair, parse_all, preceded, sep_by,
+    tag, terminated, uint, word, ws,
+};
+
+#[test]
+fn game_line_from_the_module_docs() {
+    let cube = pair(preceded(ws, uint::<u32>), preceded(ws, word));
+    let game = pair(
+        delimited(tag("Game "), uint::<u32>, tag(":")),
+        sep_by(sep_by(cube, tag(",")), tag(";")),
+    );
+    let (id, rounds) =
+        parse_all(game, "Game 3: 1 red, 5 blue; 2 green").unwrap();
+    assert_eq!(id, 3);
+    assert_eq!(rounds, [vec![(1, "red"), (5, "blue")], vec![(2, "green")]]);
+}
+
+#[test]
+fn errors_point_at_the_failing_column() {
+    let game = preceded(tag("Game "), uint::<u32>);
+    let err = parse_all(&game, "Game x").unwrap_err();
+    assert_eq!(err.pos, 5);
+    assert_eq!(err.expected, "an unsigned integer");
+    assert_eq!(
+        err.to_string(),
+        "expected an unsigned integer at column 6\n  Game x\n       ^"
+    );
+    // Columns count characters, not bytes.
+    let err = parse_all(preceded(tag("é "), int::<i32>), "é x").unwrap_err();
+    assert_eq!(err.pos, 3);
+    assert!(
+        err.to_string()
+            .starts_with("expected an integer at column 3")
+    );
+
+    let err = parse_all(uint::<u8>, "300").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (0, "an unsigned integer"));
+    assert_eq!(parse_all(int::<i32>, "-5"), Ok(-5));
+    assert_eq!(parse_all(int::<i32>, "+5"), Ok(5));
+    assert_eq!(parse_all(int::<i32>, "-").unwrap_err().pos, 0);
+}
+
+#[test]
+fn alt_reports_the_error_that_got_further() {
+    let abc = preceded(tag("ab"), tag("c"));
+    let err = parse_all(alt(&abc, tag("x")), "abd").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (2, "\"c\""));
+    let err = parse_all(alt(tag("x"), &abc), "abd").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (2, "\"c\""));
+    // At the same position, both expectations are listed.
+    let err = parse_all(alt(tag("a"), tag("b")), "c").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (0, "\"a\" or \"b\""));
+    assert_eq!(parse_all(alt(tag("a"), tag("b")), "b"), Ok("b"));
+}
+
+#[test]
+fn sep_by_ends_at_a_missing_separator_or_fails_after_one() {
+    let list = sep_by(uint::<u32>, tag(","));
+    assert_eq!(parse_all(&list, "1,2,3"), Ok(vec![1, 2, 3]));
+    assert_eq!(parse_all(&list, ""), Ok(vec![]));
+    let (items, rest) = list(Input::new("1,2;3")).unwrap();
+    assert_eq!((items, rest.rest()), (vec![1, 2], ";3"));
+    // Once a separator is consumed, a missing item is an error.
+    let err = parse_all(&list, "1,2,x").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (4, "an unsigned integer"));
+    let err = parse_all(&list, "1,").unwrap_err();
+    assert_eq!(err.pos, 2);
+}
+
+#[test]
+fn sep_by_stops_when_nothing_is_consumed() {
+    let groups = sep_by(many(tag("x")), ws);
+    assert_eq!(
+        parse_all(&groups, "xx x"),
+        Ok(vec![vec!["x", "x"], vec!["x"]])
+    );
+    assert_eq!(parse_all(&groups, ""), Ok(vec![vec![]]));
+    // The space is a separator, so an empty group follows it.
+    let (out, rest) = groups(Input::new("xx y")).unwrap();
+    assert_eq!((out, rest.rest()), (vec![vec!["x", "x"], vec![]], "y"));
+}
+
+#[test]
+fn parse_all_rejects_trailing_input() {
+    let err = parse_all(uint::<u32>, "12 ").unwrap_err();
+    assert_eq!((err.pos, err.expected.as_str()), (2, "end of input"));
+    let pos = map(terminated(int::<i64>, tag(",")), |x| x * 2);
+    assert_eq!(parse_all(&pos, "-4,"), Ok(-8));
+    assert_eq!(parse_all(&pos, "-4,,").unwrap_err().pos, 3);
+    assert_eq!(parse_all(many(tag("ab")), "ababa").unwrap_err().pos, 4);
+}
@@ -0,0 +1,65 @@
+//! Integer extraction from free-form lines: signs, punctuation around and
+//! between numbers, and numbers too large for the result type, which panic
+//! rather than being skipped or wrapped.
+
+use aoc25::utils::{ints, parse_split_whitespace, uints};
+
+#[test]
+fn signs_are_read_only_where_they_are_signs() {
+    assert_eq!(ints("x=-3,y=+4"), [-3, 4]);
+    assert_eq!(uints("x=-3,y=+4"), [3, 4]);
+    // A dash between numbers is a range, not a negative second number.
+    assert_eq!(ints("1-3"), [1, 3]);
+    assert_eq!(ints("a-3"), [3]);
+    assert_eq!(ints("--3"), [-3]);
+    assert_eq!(ints("- 3"), [3]);
+    assert!(ints("a-").is_empty());
+    assert!(ints("-").is_empty());
+    assert!(ints("").is_empty());
+}
+
+#[test]
+fn punctuation_separates_numbers() {
+    let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+    assert_eq!(ints(line), [2, -18, -2, 15]);
+    assert_eq!(uints("3.14"), [3, 14]);
+    assert_eq!(uints("#1 @ 861,330: 20x10"), [1, 861, 330, 20, 10]);
+    assert_eq!(ints("007"), [7]);
+}
+
+#[test]
+fn extremes_that_fit() {
+    assert_eq!(ints("-9223372036854775808"), [i64::MIN]);
+    assert_eq!(ints("9223372036854775807"), [i64::MAX]);
+    assert_eq!(uints("18446744073709551615"), [u64::MAX]);
+}
+
+#[test]
+#[should_panic(expected = "9223372036854775808 overflows i64")]
+fn ints_panics_past_i64_max() {
+    let _ = ints("1, 9223372036854775808");
+}
+
+#[test]
+#[should_panic(expected = "-9223372036854775809 overflows i64")]
+fn ints_panics_past_i64_min() {
+    let _ = ints("-9223372036854775809");
+}
+
+#[test]
+#[should_panic(expected = "18446744073709551616 overflows u64")]
+fn uints_panics_past_u64_max() {
+    let _ = uints("18446744073709551616");
+}
+
+#[test]
+fn split_whitespace_parses_signed_tokens_or_fails() {
+    assert_eq!(
+        parse_split_whitespace::<i64>(" -3  +4\t5 "),
+        Ok(vec![-3, 4, 5])
+    );
+    assert_eq!(parse_split_whitespace::<i64>(""), Ok(vec![]));
+    assert!(parse_split_whitespace::<i64>("1 x=2").is_err());
+    assert!(parse_split_whitespace::<u64>("1 -2").is_err());
+    assert!(parse_split_whitespace::<i64>("9223372036854775808").is_err());
+}
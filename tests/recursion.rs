@@ -0,0 +1,118 @@
+//! Recursion through `recurse` and `trampoline` at depths that would
+//! overflow the call stack if written as plain recursive functions, which
+//! is how deep-recursing solutions should be written.
+
+use std::ops::ControlFlow;
+
+use aoc25::utils::{Step, recurse, trampoline};
+
+const DEPTH: usize = 1_000_000;
+const MODULUS: u64 = 1_000_000_007;
+
+/// A snailfish number stored in an arena: recursive types built from
+/// `Box`es would also overflow the stack when dropped at this depth.
+#[derive(Clone, Copy)]
+enum Node {
+    Num(u64),
+    Pair(usize, usize),
+}
+
+/// `[1,[1,[1,...[1,1]...]]]`, nested `depth` pairs deep, with the root last.
+fn right_spine(depth: usize) -> Vec<Node> {
+    let mut nodes = vec![Node::Num(1)];
+    for _ in 0..depth {
+        let inner = nodes.len() - 1;
+        nodes.push(Node::Num(1));
+        nodes.push(Node::Pair(nodes.len() - 1, inner));
+    }
+    nodes
+}
+
+fn magnitude(nodes: &[Node], root: usize) -> u64 {
+    recurse(
+        root,
+        |i| match nodes[i] {
+            Node::Num(n) => Step::Done(n),
+            Node::Pair(l, r) => Step::Recurse((), vec![l, r]),
+        },
+        |(), m| (3 * m[0] + 2 * m[1]) % MODULUS,
+    )
+}
+
+#[test]
+fn snailfish_magnitude_a_million_pairs_deep() {
+    let nodes = right_spine(DEPTH);
+    let expected = (0..DEPTH).fold(1, |inner, _| (3 + 2 * inner) % MODULUS);
+    assert_eq!(magnitude(&nodes, nodes.len() - 1), expected);
+}
+
+#[test]
+fn depth_of_a_million_deep_chain() {
+    // Vertex v's only child is v + 1; the last vertex is a leaf.
+    let depth = recurse(
+        0,
+        |v| {
+            if v == DEPTH {
+                Step::Done(0)
+            } else {
+                Step::Recurse((), vec![v + 1])
+            }
+        },
+        |(), below: Vec<usize>| below[0] + 1,
+    );
+    assert_eq!(depth, DEPTH);
+}
+
+fn naive_tree(n: u32) -> String {
+    if n < 2 {
+        n.to_string()
+    } else {
+        format!("({n} {} {})", naive_tree(n - 1), naive_tree(n - 2))
+    }
+}
+
+#[test]
+fn results_arrive_in_argument_order() {
+    let tree = |n| {
+        recurse(
+            n,
+            |n: u32| {
+                if n < 2 {
+                    Step::Done(n.to_string())
+                } else {
+                    Step::Recurse(n, vec![n - 1, n - 2])
+                }
+            },
+            |n, kids| format!("({n} {} {})", kids[0], kids[1]),
+        )
+    };
+    for n in 0..15 {
+        assert_eq!(tree(n), naive_tree(n));
+    }
+    // A call may recurse on nothing, and is then combined straight away.
+    let empty = recurse(
+        5,
+        |n| Step::Recurse(n, vec![]),
+        |n, kids: Vec<String>| format!("{n} with {} results", kids.len()),
+    );
+    assert_eq!(empty, "5 with 0 results");
+}
+
+#[test]
+fn mutual_recursion_a_million_calls_deep() {
+    // is_even(n) = n == 0 || is_odd(n - 1), and is_odd likewise.
+    enum Call {
+        IsEven(usize),
+        IsOdd(usize),
+    }
+    let parity = |n| {
+        trampoline(Call::IsEven(n), |call| match call {
+            Call::IsEven(0) => ControlFlow::Break(true),
+            Call::IsOdd(0) => ControlFlow::Break(false),
+            Call::IsEven(n) => ControlFlow::Continue(Call::IsOdd(n - 1)),
+            Call::IsOdd(n) => ControlFlow::Continue(Call::IsEven(n - 1)),
+        })
+    };
+    assert!(parity(DEPTH));
+    assert!(!parity(DEPTH + 1));
+}
@@ -0,0 +1,69 @@
+//! The seeded generator in utils: replayable from a seed, in range, and
+//! roughly uniform.
+
+use aoc25::utils::{Rng, SliceRandom};
+
+#[test]
+fn same_seed_same_stream() {
+    let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+    let xs: Vec<u64> = (0..100).map(|_| a.next_u64()).collect();
+    let ys: Vec<u64> = (0..100).map(|_| b.next_u64()).collect();
+    assert_eq!(xs, ys);
+
+    let mut c = Rng::new(8);
+    assert_ne!(xs[..4], [0, 1, 2, 3].map(|_| c.next_u64()));
+    // A zero seed must not get stuck at zero.
+    let mut zero = Rng::new(0);
+    assert!((0..10).any(|_| zero.next_u64() != 0));
+}
+
+#[test]
+fn gen_range_is_in_range_and_roughly_uniform() {
+    let mut rng = Rng::new(1);
+    let mut counts = [0_u32; 6];
+    for _ in 0..60_000 {
+        let x = rng.gen_range(10..16);
+        assert!((10..16).contains(&x));
+        counts[usize::try_from(x - 10).unwrap()] += 1;
+    }
+    for count in counts {
+        assert!((9_000..11_000).contains(&count), "{counts:?}");
+    }
+    assert_eq!(rng.gen_range(5..6), 5);
+    let big = rng.gen_range(u64::MAX - 3..u64::MAX);
+    assert!(big >= u64::MAX - 3);
+}
+
+#[test]
+#[should_panic(expected = "empty range")]
+fn gen_range_rejects_empty_ranges() {
+    Rng::new(1).gen_range(3..3);
+}
+
+#[test]
+fn gen_bool_follows_its_probability() {
+    let mut rng = Rng::new(2);
+    let hits = (0..10_000).filter(|_| rng.gen_bool(0.25)).count();
+    assert!((2_200..2_800).contains(&hits), "{hits}");
+    assert!((0..100).all(|_| !rng.gen_bool(0.0)));
+    assert!((0..100).all(|_| rng.gen_bool(1.0)));
+}
+
+#[test]
+fn shuffle_permutes_and_choose_picks_members() {
+    let mut rng = Rng::new(3);
+    let mut items: Vec<u32> = (0..50).collect();
+    items.shuffle(&mut rng);
+    assert_ne!(items, (0..50).collect::<Vec<_>>());
+    let mut sorted = items.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+
+    let mut seen = [false; 4];
+    for _ in 0..200 {
+        let &pick = [0, 1, 2, 3].choose(&mut rng).unwrap();
+        seen[pick] = true;
+    }
+    assert_eq!(seen, [true; 4]);
+    assert_eq!(<[u8]>::choose(&[], &mut rng), None);
+}
@@ -0,0 +1,115 @@
+//! `scan!` and `scan_fields`: templates with literal text between
+//! placeholders, and each way a line or template can fail to match.
+
+use aoc25::scan;
+use aoc25::scan::{ScanError, scan_fields};
+
+const POLICY: &str = "{}-{} {}: {}";
+
+#[test]
+fn password_policy_example() {
+    let fields = scan!("1-3 a: abcde", POLICY, u32, u32, char, String);
+    assert_eq!(fields, Ok((1, 3, 'a', "abcde".to_string())));
+    let fields = scan!("2-9 c: ccccccccc", POLICY, usize, usize, char, String);
+    assert_eq!(fields, Ok((2, 9, 'c', "ccccccccc".to_string())));
+    assert_eq!(
+        scan_fields("1-3 a: abcde", POLICY),
+        Ok(vec!["1", "3", "a", "abcde"])
+    );
+}
+
+#[test]
+fn fields_take_up_to_the_next_literal() {
+    assert_eq!(
+        scan_fields("x=-3, y=12..-1", "x={}, y={}..{}"),
+        Ok(vec!["-3", "12", "-1"])
+    );
+    assert_eq!(scan_fields("a=,b=2", "a={},b={}"), Ok(vec!["", "2"]));
+    assert_eq!(scan_fields("whole line", "{}"), Ok(vec!["whole line"]));
+    assert_eq!(scan_fields("no fields", "no fields"), Ok(vec![]));
+}
+
+#[test]
+fn arity_mismatch_either_way() {
+    let line = "1-3 a: abcde";
+    assert_eq!(scan!(line, POLICY, u32, u32, char), Err(ScanError::Arity));
+    assert_eq!(
+        scan!(line, POLICY, u32, u32, char, String, u8),
+        Err(ScanError::Arity)
+    );
+}
+
+#[test]
+fn fields_that_do_not_parse() {
+    assert_eq!(
+        scan!("x-3 a: abc", POLICY, u32, u32, char, String),
+        Err(ScanError::Parse {
+            index: 0,
+            field: "x".into(),
+            ty: "u32",
+        })
+    );
+    let err = scan!("1-3 ab: abc", POLICY, u32, u32, char, String);
+    assert_eq!(
+        err,
+        Err(ScanError::Parse {
+            index: 2,
+            field: "ab".into(),
+            ty: "char",
+        })
+    );
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "field 2 (\"ab\") is not a valid char"
+    );
+    assert!(matches!(
+        scan!("1-300 a: b", POLICY, u8, u8, char, String),
+        Err(ScanError::Parse { index: 1, .. })
+    ));
+}
+
+#[test]
+fn missing_literals_and_trailing_text() {
+    assert_eq!(
+        scan_fields("1 3 a: b", POLICY),
+        Err(ScanError::Mismatch {
+            expected: "-".into(),
+            at: 0,
+        })
+    );
+    assert_eq!(
+        scan_fields("Game 1", "Round {}"),
+        Err(ScanError::Mismatch {
+            expected: "Round ".into(),
+            at: 0,
+        })
+    );
+    let err = scan_fields("1-3!x", "{}-{}!").unwrap_err();
+    assert_eq!(
+        err,
+        ScanError::Mismatch {
+            expected: String::new(),
+            at: 4,
+        }
+    );
+    assert_eq!(err.to_string(), "unexpected trailing text at byte 4");
+    assert_eq!(
+        scan_fields("a 1", "a {}!").unwrap_err().to_string(),
+        "expected \"!\" at byte 2"
+    );
+}
+
+#[test]
+fn adjacent_placeholders_are_rejected() {
+    for template in ["{}{}", "x={}{}", "{}{}!", "{}-{}{} {}"] {
+        assert_eq!(
+            scan_fields("12-34 5", template),
+            Err(ScanError::AdjacentPlaceholders),
+            "{template}"
+        );
+    }
+    assert_eq!(
+        scan!("12", "{}{}", u8, u8),
+        Err(ScanError::AdjacentPlaceholders)
+    );
+}
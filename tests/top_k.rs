@@ -0,0 +1,93 @@
+//! `top_k` and `bottom_k`: the answers a full stable sort would give,
+//! including which of several equal items are kept and in what order.
+
+use std::cmp::Ordering;
+
+use aoc25::utils::{bottom_k, top_k};
+use proptest::prelude::*;
+
+/// An item compared by `key` alone, so that `tag` shows which of several
+/// equal items came through.
+#[derive(Clone, Copy, Debug)]
+struct Keyed {
+    key: u8,
+    tag: usize,
+}
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Keyed {}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+fn keyed(keys: &[u8]) -> Vec<Keyed> {
+    keys.iter()
+        .enumerate()
+        .map(|(tag, &key)| Keyed { key, tag })
+        .collect()
+}
+
+fn tags(items: &[Keyed]) -> Vec<usize> {
+    items.iter().map(|item| item.tag).collect()
+}
+
+#[test]
+fn sum_of_the_three_largest() {
+    let elves = [6000_u64, 4000, 11000, 24000, 10000];
+    assert_eq!(top_k(elves, 3), [24000, 11000, 10000]);
+    assert_eq!(top_k(elves, 3).iter().sum::<u64>(), 45000);
+    assert_eq!(bottom_k(elves, 2), [4000, 6000]);
+}
+
+#[test]
+fn ties_keep_the_earliest_in_input_order() {
+    let items = keyed(&[5, 9, 5, 9, 5, 1]);
+    // Both 9s, then the first 5 of three.
+    assert_eq!(tags(&top_k(items.clone(), 3)), [1, 3, 0]);
+    assert_eq!(tags(&top_k(items.clone(), 4)), [1, 3, 0, 2]);
+    assert_eq!(tags(&bottom_k(items, 3)), [5, 0, 2]);
+    // A later equal item never displaces an earlier one.
+    assert_eq!(tags(&top_k(keyed(&[7; 6]), 2)), [0, 1]);
+    assert_eq!(tags(&bottom_k(keyed(&[7; 6]), 2)), [0, 1]);
+}
+
+#[test]
+fn k_of_zero_or_beyond_the_input() {
+    assert!(top_k([3, 1, 2], 0).is_empty());
+    assert!(bottom_k([3, 1, 2], 0).is_empty());
+    assert_eq!(top_k([3, 1, 2], 10), [3, 2, 1]);
+    assert_eq!(bottom_k([3, 1, 2], 10), [1, 2, 3]);
+    assert!(top_k(Vec::<u32>::new(), 3).is_empty());
+}
+
+proptest! {
+    #[test]
+    fn same_as_a_stable_sort(
+        keys in prop::collection::vec(0..8_u8, 0..60),
+        k in 0..70_usize,
+    ) {
+        let items = keyed(&keys);
+        let mut largest = items.clone();
+        largest.sort_by(|a, b| b.cmp(a));
+        largest.truncate(k);
+        let mut smallest = items.clone();
+        smallest.sort();
+        smallest.truncate(k);
+        prop_assert_eq!(tags(&top_k(items.clone(), k)), tags(&largest));
+        prop_assert_eq!(tags(&bottom_k(items, k)), tags(&smallest));
+    }
+}
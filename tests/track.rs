@@ -0,0 +1,82 @@
+//! Track connectivity where curves touch: adjacent loops must stay apart
+//! both when flooding through connected neighbors and when walking with
+//! `follow_track`.
+
+use std::collections::HashSet;
+
+use aoc25::direction::{Coords, DirSet, Direction, follow_track};
+use aoc25::grid::Grid;
+
+/// Two loops side by side, the right one's corners touching the left's.
+const TWIN_LOOPS: &str = "\
+/-\\/-\\
+| || |
+\\-/\\-/";
+
+fn grid(text: &str) -> Grid<char> {
+    Grid::from_rows(text.lines().map(|l| l.chars().collect()).collect())
+}
+
+/// Every cell reachable from `start` through connected neighbors.
+fn flood(grid: &Grid<char>, start: Coords) -> HashSet<Coords> {
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(pos) = stack.pop() {
+        for dir in Direction::CARDINALS {
+            if let Some(nbr) = grid.connected_neighbor(pos, dir)
+                && seen.insert(nbr)
+            {
+                stack.push(nbr);
+            }
+        }
+    }
+    seen
+}
+
+#[test]
+fn touching_curves_of_side_by_side_loops_do_not_connect() {
+    let g = grid(TWIN_LOOPS);
+    assert_eq!(g.connected_neighbor(Coords(0, 2), Direction::E), None);
+    assert_eq!(g.connected_neighbor(Coords(2, 2), Direction::E), None);
+    assert_eq!(
+        g.connected_neighbor(Coords(0, 2), Direction::S),
+        Some(Coords(1, 2))
+    );
+
+    let left = flood(&g, Coords(0, 0));
+    let right = flood(&g, Coords(0, 5));
+    assert_eq!(left.len(), 8);
+    assert_eq!(right.len(), 8);
+    assert!(left.iter().all(|&Coords(_, c)| c < 3));
+    assert!(right.iter().all(|&Coords(_, c)| c >= 3));
+}
+
+#[test]
+fn curves_resolve_from_adjacent_track() {
+    use Direction::{E, N, S, W};
+    let g = grid(TWIN_LOOPS);
+    let set = |a, b| DirSet::single(a) | DirSet::single(b);
+    assert_eq!(g.track_connections(Coords(0, 2)), Some(set(S, W)));
+    assert_eq!(g.track_connections(Coords(0, 3)), Some(set(S, E)));
+    assert_eq!(g.track_connections(Coords(2, 2)), Some(set(N, W)));
+    assert_eq!(g.track_connections(Coords(2, 3)), Some(set(N, E)));
+    // Only curves around it: nothing to tell which way it goes.
+    assert_eq!(grid("/\\\n\\/").track_connections(Coords(0, 0)), None);
+}
+
+#[test]
+fn following_the_track_goes_round_one_loop() {
+    let g = grid(TWIN_LOOPS);
+    let (mut pos, mut dir) = (Coords(0, 1), Direction::E);
+    let mut visited = vec![pos];
+    loop {
+        pos = pos.step(dir).unwrap();
+        dir = follow_track(g[pos], dir).unwrap();
+        if pos == Coords(0, 1) {
+            break;
+        }
+        visited.push(pos);
+    }
+    assert_eq!(visited.len(), 8);
+    assert!(visited.iter().all(|&Coords(_, c)| c < 3));
+}
@@ -0,0 +1,125 @@
+//! 2-SAT on small hand-built instances, and on random ones checked against
+//! trying every assignment.
+
+use aoc25::twosat::{Lit, TwoSat};
+use proptest::prelude::*;
+
+const MAX_VARS: usize = 6;
+
+fn holds(lit: Lit, assignment: &[bool]) -> bool {
+    assignment[lit.var] == lit.positive
+}
+
+#[test]
+fn contradictory_unit_clauses() {
+    // (x ∨ x) ∧ (¬x ∨ ¬x)
+    let mut sat = TwoSat::new(1);
+    sat.add_clause(Lit::pos(0), Lit::pos(0));
+    sat.add_clause(Lit::neg(0), Lit::neg(0));
+    assert_eq!(sat.solve(), None);
+}
+
+#[test]
+fn force_fixes_a_variable() {
+    let mut sat = TwoSat::new(2);
+    sat.force(Lit::neg(0));
+    sat.force(Lit::pos(1));
+    assert_eq!(sat.solve(), Some(vec![false, true]));
+    sat.force(Lit::pos(0));
+    assert_eq!(sat.solve(), None);
+}
+
+#[test]
+fn implications_propagate_along_a_chain() {
+    let mut sat = TwoSat::new(0);
+    for v in 0..5 {
+        sat.implies(Lit::pos(v), Lit::pos(v + 1));
+    }
+    assert_eq!(sat.num_vars(), 6);
+    sat.force(Lit::pos(2));
+    let solution = sat.solve().unwrap();
+    assert!(solution[2..].iter().all(|&x| x));
+    sat.force(Lit::neg(5));
+    assert_eq!(sat.solve(), None);
+}
+
+#[test]
+fn exactly_one_of_two() {
+    let mut sat = TwoSat::new(2);
+    sat.exactly_one(Lit::pos(0), Lit::pos(1));
+    let s = sat.solve().unwrap();
+    assert_ne!(s[0], s[1]);
+    sat.force(Lit::pos(1));
+    assert_eq!(sat.solve(), Some(vec![false, true]));
+    sat.force(Lit::pos(0));
+    assert_eq!(sat.solve(), None);
+
+    // With negated literals: exactly one of ¬a and b, i.e. a == b.
+    let mut sat = TwoSat::new(2);
+    sat.exactly_one(Lit::neg(0), Lit::pos(1));
+    sat.force(Lit::pos(0));
+    assert_eq!(sat.solve(), Some(vec![true, true]));
+}
+
+#[test]
+fn at_most_one_of_many() {
+    let lits: Vec<Lit> = (0..4).map(Lit::pos).collect();
+    let mut sat = TwoSat::new(4);
+    sat.at_most_one(&lits);
+    let s = sat.solve().unwrap();
+    assert!(s.iter().filter(|&&x| x).count() <= 1);
+    sat.force(Lit::pos(2));
+    assert_eq!(sat.solve(), Some(vec![false, false, true, false]));
+    sat.force(Lit::pos(3));
+    assert_eq!(sat.solve(), None);
+    // Zero or one literal adds no constraint.
+    let mut sat = TwoSat::new(1);
+    sat.at_most_one(&[Lit::pos(0)]);
+    sat.at_most_one(&[]);
+    sat.force(Lit::pos(0));
+    assert_eq!(sat.solve(), Some(vec![true]));
+}
+
+#[test]
+fn variables_are_added_on_demand() {
+    let mut sat = TwoSat::default();
+    assert_eq!(sat.solve(), Some(vec![]));
+    assert_eq!(sat.add_var(), 0);
+    sat.add_clause(Lit::new(3, true), Lit::new(0, false));
+    assert_eq!(sat.num_vars(), 4);
+    assert_eq!(sat.solve().map(|s| s.len()), Some(4));
+}
+
+fn clause() -> impl Strategy<Value = (Lit, Lit)> {
+    let lit = (0..MAX_VARS, any::<bool>()).prop_map(|(v, b)| Lit::new(v, b));
+    (lit.clone(), lit)
+}
+
+proptest! {
+    #[test]
+    fn agrees_with_brute_force(
+        clauses in prop::collection::vec(clause(), 0..24),
+    ) {
+        let mut sat = TwoSat::new(MAX_VARS);
+        for &(a, b) in &clauses {
+            sat.add_clause(a, b);
+        }
+        let satisfies = |assignment: &[bool]| {
+            clauses
+                .iter()
+                .all(|&(a, b)| holds(a, assignment) || holds(b, assignment))
+        };
+        let brute = (0..1_u32 << MAX_VARS).any(|bits| {
+            let assignment: Vec<bool> =
+                (0..MAX_VARS).map(|v| bits >> v & 1 == 1).collect();
+            satisfies(&assignment)
+        });
+        match sat.solve() {
+            Some(assignment) => {
+                prop_assert_eq!(assignment.len(), MAX_VARS);
+                prop_assert!(satisfies(&assignment));
+            }
+            None => prop_assert!(!brute),
+        }
+    }
+}
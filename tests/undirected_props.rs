@@ -0,0 +1,160 @@
+//! Articulation points and bridges on known small graphs, and on random
+//! multigraphs checked against brute-force removal. Also the undirected
+//! view of a directed graph, checked against building the undirected graph.
+
+use aoc25::interner::AdjList;
+use aoc25::undirected::{UndirectedGraph, as_undirected_view};
+use proptest::prelude::*;
+
+const MAX_N: usize = 10;
+
+fn graph(n: usize, edges: &[(usize, usize)]) -> UndirectedGraph<u32> {
+    let mut graph = UndirectedGraph::new(n);
+    for &(a, b) in edges {
+        graph.add_edge(a, b, 1);
+    }
+    graph
+}
+
+/// A random multigraph as a vertex count and edge list, possibly with
+/// self-loops and parallel edges.
+fn multigraph() -> impl Strategy<Value = (usize, Vec<(usize, usize)>)> {
+    (1..=MAX_N).prop_flat_map(|n| {
+        (Just(n), prop::collection::vec((0..n, 0..n), 0..2 * n))
+    })
+}
+
+fn find(parent: &mut [usize], v: usize) -> usize {
+    if parent[v] != v {
+        parent[v] = find(parent, parent[v]);
+    }
+    parent[v]
+}
+
+/// Number of connected components among the vertices `alive`, using only
+/// the given edges.
+fn components(
+    n: usize,
+    edges: &[(usize, usize)],
+    alive: impl Fn(usize) -> bool,
+) -> usize {
+    let mut parent: Vec<usize> = (0..n).collect();
+    for &(a, b) in edges {
+        if alive(a) && alive(b) {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            parent[ra] = rb;
+        }
+    }
+    (0..n)
+        .filter(|&v| alive(v) && find(&mut parent, v) == v)
+        .count()
+}
+
+#[test]
+fn triangle_with_tail() {
+    // 0 - 1 - 2 - 0, then 1 - 3 - 4.
+    let g = graph(5, &[(0, 1), (1, 2), (2, 0), (1, 3), (3, 4)]);
+    assert_eq!(g.articulation_points(), vec![1, 3]);
+    assert_eq!(g.bridges(), vec![(1, 3), (3, 4)]);
+}
+
+#[test]
+fn path_and_cycle() {
+    let path = graph(4, &[(0, 1), (1, 2), (2, 3)]);
+    assert_eq!(path.articulation_points(), vec![1, 2]);
+    assert_eq!(path.bridges(), vec![(0, 1), (1, 2), (2, 3)]);
+
+    let cycle = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert!(cycle.articulation_points().is_empty());
+    assert!(cycle.bridges().is_empty());
+}
+
+#[test]
+fn parallel_edges_are_not_bridges() {
+    let g = graph(3, &[(0, 1), (1, 0), (1, 2)]);
+    assert_eq!(g.articulation_points(), vec![1]);
+    assert_eq!(g.bridges(), vec![(1, 2)]);
+}
+
+#[test]
+fn bowtie_and_removed_vertex() {
+    // Two triangles sharing vertex 2.
+    let mut g = graph(5, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)]);
+    assert_eq!(g.articulation_points(), vec![2]);
+    assert!(g.bridges().is_empty());
+    g.remove_vertex(4);
+    assert_eq!(g.articulation_points(), vec![2]);
+    assert_eq!(g.bridges(), vec![(2, 3)]);
+}
+
+#[test]
+fn long_path_does_not_overflow() {
+    let n = 200_000;
+    let edges: Vec<_> = (1..n).map(|v| (v - 1, v)).collect();
+    let g = graph(n, &edges);
+    assert_eq!(g.articulation_points().len(), n - 2);
+    assert_eq!(g.bridges().len(), n - 1);
+}
+
+#[test]
+fn view_of_vertex_only_listed_as_target() {
+    let directed: AdjList<u32> = vec![vec![(3, 7)]];
+    let view = as_undirected_view(&directed);
+    assert_eq!(view.num_vertices(), 4);
+    assert_eq!(view.neighbors(3).collect::<Vec<_>>(), [(0, &7)]);
+    assert_eq!(view.degree(1), 0);
+}
+
+proptest! {
+    #[test]
+    fn view_matches_adding_each_edge((n, edges) in multigraph()) {
+        // Each edge listed once, from its first endpoint, weighted by its
+        // index so the weights can be checked too.
+        let mut directed: AdjList<usize> = vec![vec![]; n];
+        let mut g = UndirectedGraph::new(n);
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            directed[a].push((b, i));
+            g.add_edge(a, b, i);
+        }
+        let view = as_undirected_view(&directed);
+        prop_assert_eq!(view.num_vertices(), n);
+        for v in 0..n {
+            let mut seen: Vec<(usize, usize)> =
+                view.neighbors(v).map(|(u, &w)| (u, w)).collect();
+            let mut expected = g.neighbors(v).to_vec();
+            seen.sort_unstable();
+            expected.sort_unstable();
+            prop_assert_eq!(view.degree(v), g.degree(v));
+            prop_assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn articulation_points_match_brute_force((n, edges) in multigraph()) {
+        let g = graph(n, &edges);
+        let before = components(n, &edges, |_| true);
+        let expected: Vec<usize> = (0..n)
+            .filter(|&v| components(n, &edges, |u| u != v) > before)
+            .collect();
+        prop_assert_eq!(g.articulation_points(), expected);
+    }
+
+    #[test]
+    fn bridges_match_brute_force((n, edges) in multigraph()) {
+        let g = graph(n, &edges);
+        let before = components(n, &edges, |_| true);
+        let mut expected: Vec<(usize, usize)> = (0..edges.len())
+            .filter(|&i| {
+                let mut rest = edges.clone();
+                rest.remove(i);
+                components(n, &rest, |_| true) > before
+            })
+            .map(|i| {
+                let (a, b) = edges[i];
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        expected.sort_unstable();
+        prop_assert_eq!(g.bridges(), expected);
+    }
+}
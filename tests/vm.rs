@@ -0,0 +1,160 @@
+//! The register machine on each assembly dialect it decodes, and its
+//! control: halting on jumps out of the program, breakpoints, output, hooks
+//! and faults.
+
+use aoc25::vm::{
+    Flow, Instr, Machine, Operand, Step, Stop, decode, decode_instr, reg,
+};
+
+fn machine(source: &str) -> Machine {
+    let lines: Vec<&str> = source.lines().collect();
+    Machine::new(decode(&lines).unwrap())
+}
+
+const A: usize = 0;
+
+#[test]
+fn cpy_jnz_dialect() {
+    use Operand::{Imm, Reg};
+    assert_eq!(decode_instr("cpy 41 a"), Some(Instr::Set(A, Imm(41))));
+    assert_eq!(decode_instr("cpy c a"), Some(Instr::Set(A, Reg(reg('c')))));
+    assert_eq!(decode_instr("jnz c -2"), Some(Instr::Jnz(Reg(2), Imm(-2))));
+    assert_eq!(decode_instr("cpy 1 2"), None);
+
+    let mut vm = machine("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a");
+    assert_eq!(vm.run(), Stop::Halted);
+    assert_eq!(vm.regs[A], 42);
+    assert_eq!(vm.steps(), 5);
+}
+
+#[test]
+fn set_jgz_dialect() {
+    use Operand::{Imm, Reg};
+    assert_eq!(decode_instr("set a 1"), Some(Instr::Set(A, Imm(1))));
+    assert_eq!(decode_instr("jgz 1 -3"), Some(Instr::Jgz(Imm(1), Imm(-3))));
+    assert_eq!(decode_instr("snd a"), Some(Instr::Out(Reg(A))));
+    assert_eq!(decode_instr("mod a b"), Some(Instr::Mod(A, Reg(1))));
+
+    // b counts down from 3, multiplying a by 2 each time, then a mod 5.
+    let mut vm = machine(
+        "set a 1\nset b 3\nmul a 2\nsub b 1\njgz b -2\nadd a 1\nmod a 5\n\
+         snd a",
+    );
+    assert_eq!(vm.run_until_output(), Some(4));
+    assert_eq!(vm.run_until_output(), None);
+}
+
+#[test]
+fn hlf_tpl_jie_jio_dialect() {
+    use Operand::Imm;
+    assert_eq!(decode_instr("jio a, +2"), Some(Instr::Jio(A, Imm(2))));
+    assert_eq!(decode_instr("jie b, -7"), Some(Instr::Jie(1, Imm(-7))));
+    assert_eq!(decode_instr("jmp +23"), Some(Instr::Jmp(Imm(23))));
+    assert_eq!(decode_instr("hlf a"), Some(Instr::Hlf(A)));
+
+    let mut vm = machine("inc a\njio a, +2\ntpl a\ninc a");
+    vm.run();
+    assert_eq!(vm.regs[A], 2);
+
+    let mut vm = machine("inc a\ntpl a\ntpl a\nhlf a\njie a, +2\ninc b\ninc b");
+    vm.run();
+    assert_eq!((vm.regs[A], vm.regs[1]), (4, 1));
+}
+
+#[test]
+fn decode_errors_keep_line_numbers() {
+    let err = decode(&["inc a", "", "bogus 3"]).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(err.to_string(), "line 3: cannot decode \"bogus 3\"");
+    assert_eq!(decode(&["", "nop +0", ""]), Ok(vec![Instr::Nop]));
+}
+
+#[test]
+fn jumping_before_the_start_halts() {
+    let mut vm = machine("inc a\njnz 1 -5\ninc a");
+    assert_eq!(vm.run(), Stop::Halted);
+    assert_eq!(vm.ip(), None);
+    assert_eq!(vm.regs[A], 1);
+    assert_eq!(vm.step(), Step::Halted);
+
+    let mut vm = machine("jmp -1");
+    assert_eq!(vm.step(), Step::Ran);
+    assert_eq!(vm.step(), Step::Halted);
+}
+
+#[test]
+fn breakpoints_stop_and_resume() {
+    let mut vm = machine("inc a\ninc a\ninc a\ninc a");
+    vm.add_breakpoint(2);
+    assert_eq!(vm.run(), Stop::Breakpoint(2));
+    assert_eq!((vm.regs[A], vm.ip()), (2, Some(2)));
+    // Resuming runs past the breakpoint it stopped on.
+    assert_eq!(vm.run(), Stop::Halted);
+    assert_eq!(vm.regs[A], 4);
+
+    let mut vm = machine("inc a\njnz 1 -1");
+    vm.add_breakpoint(0);
+    assert_eq!(vm.run(), Stop::Breakpoint(0));
+    assert_eq!(vm.run(), Stop::Breakpoint(0));
+    assert_eq!(vm.regs[A], 2);
+    vm.remove_breakpoint(0);
+    vm.add_breakpoint(1);
+    assert_eq!(vm.run(), Stop::Breakpoint(1));
+}
+
+#[test]
+fn run_until_output_yields_each_value() {
+    let mut vm = machine("out a\ninc a\njnz 1 -2");
+    let outputs: Vec<i64> =
+        (0..4).map(|_| vm.run_until_output().unwrap()).collect();
+    assert_eq!(outputs, [0, 1, 2, 3]);
+    // `run` carries on through output without stopping.
+    let mut vm = machine("out 1\nout 2\ninc a");
+    assert_eq!(vm.run(), Stop::Halted);
+    assert_eq!(vm.regs[A], 1);
+}
+
+#[test]
+fn hooks_override_opcodes() {
+    let program = "set a 3\nmul a 5\nadd a 1\nsnd a\ninc b";
+    let mut plain = machine(program);
+    assert_eq!(plain.run_until_output(), Some(16));
+
+    // Every `mul` becomes a no-op and every output halts.
+    let mut muls = 0;
+    let mut vm =
+        machine(program).with_hooks(|_, instr: Instr, _: &mut _| match instr {
+            Instr::Mul(..) => {
+                muls += 1;
+                Some(Flow::Next)
+            }
+            Instr::Out(_) => Some(Flow::Halt),
+            _ => None,
+        });
+    assert_eq!(vm.run(), Stop::Halted);
+    assert_eq!((vm.regs[A], vm.regs[1]), (4, 0));
+    drop(vm);
+    assert_eq!(muls, 1);
+}
+
+#[test]
+fn overflow_and_division_by_zero_fault() {
+    let max = i64::MAX;
+    for program in [
+        format!("set a {max}\ninc a"),
+        format!("set a {max}\nadd a 1"),
+        format!("set a {max}\ntpl a"),
+        format!("set a {max}\nmul a a"),
+        "set a -9223372036854775808\ndec a".to_string(),
+        "set a -9223372036854775808\nsub a 1".to_string(),
+        "set a -9223372036854775808\ndiv a -1".to_string(),
+        "set a 5\ndiv a b".to_string(),
+        "set a 5\nmod a 0".to_string(),
+    ] {
+        let mut vm = machine(&program);
+        assert_eq!(vm.run(), Stop::Fault(1), "{program}");
+        // A faulting instruction leaves the machine where it was.
+        assert_eq!(vm.ip(), Some(1));
+        assert_eq!(vm.step(), Step::Fault(1));
+    }
+}